@@ -0,0 +1,82 @@
+//! # Clock Abstraction
+//!
+//! Time source for engines whose behaviour depends on keystroke timing
+//! (multi-tap cycling, buffer auto-reset). Engines take an injectable
+//! [`Clock`] so that timing logic can be tested deterministically.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Instant;
+
+/// Monotonic millisecond time source
+pub trait Clock: Send + Sync {
+    /// Milliseconds elapsed since an arbitrary, fixed starting point
+    fn now_ms(&self) -> u64;
+}
+
+/// Clock backed by [`std::time::Instant`]
+pub struct SystemClock {
+    start: Instant,
+}
+
+impl SystemClock {
+    pub fn new() -> Self {
+        Self {
+            start: Instant::now(),
+        }
+    }
+}
+
+impl Default for SystemClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for SystemClock {
+    fn now_ms(&self) -> u64 {
+        self.start.elapsed().as_millis() as u64
+    }
+}
+
+/// Manually driven clock for tests
+///
+/// Clones share the same time, so a test can hand one clone to an engine
+/// and advance the other.
+///
+/// # Example
+///
+/// ```
+/// use vietime_core::clock::{Clock, ManualClock};
+///
+/// let clock = ManualClock::new();
+/// let shared = clock.clone();
+/// clock.advance(1500);
+/// assert_eq!(shared.now_ms(), 1500);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ManualClock {
+    now: Arc<AtomicU64>,
+}
+
+impl ManualClock {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Move time forward by `ms` milliseconds
+    pub fn advance(&self, ms: u64) {
+        self.now.fetch_add(ms, Ordering::SeqCst);
+    }
+
+    /// Set the current time
+    pub fn set(&self, ms: u64) {
+        self.now.store(ms, Ordering::SeqCst);
+    }
+}
+
+impl Clock for ManualClock {
+    fn now_ms(&self) -> u64 {
+        self.now.load(Ordering::SeqCst)
+    }
+}
@@ -0,0 +1,220 @@
+//! # Collation
+//!
+//! Vietnamese dictionary order, for sorting names and word lists.
+//!
+//! Text is compared in three passes:
+//!
+//! 1. Letters without tones, in alphabet order: a ă â b c d đ e ê f g h i
+//!    j k l m n o ô ơ p q r s t u ư v w x y z. Other characters (spaces,
+//!    digits, punctuation) sort before every letter, and letters outside
+//!    the Vietnamese alphabet after z.
+//! 2. Tones, letter by letter: none, grave, hook, tilde, acute, dot
+//!    (a à ả ã á ạ).
+//! 3. Case, lowercase first.
+//!
+//! Some dictionaries also treat ch, gh, kh, ng, nh, ph, th and tr as
+//! letters of their own, sorted after their first letter (c < ch < d,
+//! n < ng < nh < o); see [`Collator::with_digraphs`]. Precomposed and
+//! decomposed text compare equal.
+//!
+//! ```
+//! use vietime_core::collate;
+//!
+//! let mut names = vec!["Zoe", "Đặng", "Dương", "an", "Ánh", "Anh"];
+//! names.sort_by(|a, b| collate::compare(a, b));
+//! assert_eq!(names, ["an", "Anh", "Ánh", "Dương", "Đặng", "Zoe"]);
+//! ```
+
+use std::cmp::Ordering;
+
+use crate::models::vietnamese::{ToneIndex, VietnameseChar};
+
+/// Letters in order; the others sort after these by code point
+const ALPHABET: [char; 33] = [
+    'a', 'ă', 'â', 'b', 'c', 'd', 'đ', 'e', 'ê', 'f', 'g', 'h', 'i', 'j', 'k', 'l', 'm', 'n', 'o',
+    'ô', 'ơ', 'p', 'q', 'r', 's', 't', 'u', 'ư', 'v', 'w', 'x', 'y', 'z',
+];
+
+/// Letter pairs sorted as one letter with digraphs on, in order after
+/// their first letter
+const DIGRAPHS: [[char; 2]; 8] = [
+    ['c', 'h'],
+    ['g', 'h'],
+    ['k', 'h'],
+    ['n', 'g'],
+    ['n', 'h'],
+    ['p', 'h'],
+    ['t', 'h'],
+    ['t', 'r'],
+];
+
+/// First primary weight of a letter (other characters weigh their code
+/// point, all below this)
+const LETTER_WEIGHT: u32 = 0x11_0000;
+
+/// Primary weights between two letters, room for the digraphs after one
+const LETTER_STEP: u32 = 4;
+
+/// Sort key levels are separated by 0, below every weight
+const LEVEL_SEPARATOR: u32 = 0;
+
+/// Comparator for Vietnamese dictionary order
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Collator {
+    digraphs: bool,
+}
+
+impl Collator {
+    /// Collator treating every letter on its own
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set whether ch, gh, kh, ng, nh, ph, th and tr sort as single letters
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use vietime_core::collate::Collator;
+    ///
+    /// assert_eq!(Collator::new().compare("nhà", "nói"), Ordering::Less);
+    /// assert_eq!(Collator::new().with_digraphs(true).compare("nhà", "nói"), Ordering::Greater);
+    /// ```
+    pub fn with_digraphs(mut self, digraphs: bool) -> Self {
+        self.digraphs = digraphs;
+        self
+    }
+
+    /// Compare two strings in dictionary order
+    pub fn compare(&self, a: &str, b: &str) -> Ordering {
+        self.sort_key(a).cmp(&self.sort_key(b))
+    }
+
+    /// Key whose plain ordering is dictionary order
+    ///
+    /// Worth computing once per item when sorting long lists
+    /// (`slice::sort_by_cached_key`) or storing in an index.
+    pub fn sort_key(&self, text: &str) -> Vec<u32> {
+        let chars: Vec<char> = VietnameseChar::to_nfc(text).chars().collect();
+        let mut primary = Vec::with_capacity(chars.len());
+        let mut secondary = Vec::with_capacity(chars.len());
+        let mut tertiary = Vec::with_capacity(chars.len());
+
+        let mut i = 0;
+        while i < chars.len() {
+            let (weight, len) = self.primary(&chars[i..]);
+            primary.push(weight);
+            secondary.push(tone_weight(VietnameseChar::get_tone_index(chars[i])));
+            tertiary.extend(
+                chars[i..i + len]
+                    .iter()
+                    .map(|c| u32::from(c.is_uppercase()) + 1),
+            );
+            i += len;
+        }
+
+        let mut key = primary;
+        key.push(LEVEL_SEPARATOR);
+        key.extend(secondary);
+        key.push(LEVEL_SEPARATOR);
+        key.extend(tertiary);
+        key
+    }
+
+    /// Weight of the letter (or digraph) starting `chars`, and its length
+    fn primary(&self, chars: &[char]) -> (u32, usize) {
+        let letter = |c: char| VietnameseChar::to_lower(VietnameseChar::get_vowel_without_tone(c));
+        let first = letter(chars[0]);
+        let Some(index) = ALPHABET.iter().position(|&c| c == first) else {
+            let weight = u32::from(chars[0]);
+            if chars[0].is_alphabetic() {
+                return (
+                    LETTER_WEIGHT + ALPHABET.len() as u32 * LETTER_STEP + weight,
+                    1,
+                );
+            }
+            return (weight + 1, 1);
+        };
+        let weight = LETTER_WEIGHT + index as u32 * LETTER_STEP;
+
+        if self.digraphs {
+            if let Some(&second) = chars.get(1) {
+                let pair = [first, letter(second)];
+                let mut after = DIGRAPHS.iter().filter(|digraph| digraph[0] == first);
+                if let Some(rank) = after.position(|digraph| *digraph == pair) {
+                    return (weight + 1 + rank as u32, 2);
+                }
+            }
+        }
+        (weight, 1)
+    }
+}
+
+/// Compare two strings in dictionary order, digraphs off
+pub fn compare(a: &str, b: &str) -> Ordering {
+    Collator::new().compare(a, b)
+}
+
+/// Sort key for dictionary order, digraphs off
+pub fn sort_key(text: &str) -> Vec<u32> {
+    Collator::new().sort_key(text)
+}
+
+/// Tone order: none, grave, hook, tilde, acute, dot
+fn tone_weight(tone: ToneIndex) -> u32 {
+    match tone {
+        ToneIndex::None => 1,
+        ToneIndex::Grave => 2,
+        ToneIndex::Hook => 3,
+        ToneIndex::Tilde => 4,
+        ToneIndex::Acute => 5,
+        ToneIndex::Dot => 6,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tone_order() {
+        let mut words = vec!["mạ", "má", "mã", "mả", "mà", "ma"];
+        words.sort_by(|a, b| compare(a, b));
+        assert_eq!(words, ["ma", "mà", "mả", "mã", "má", "mạ"]);
+    }
+
+    #[test]
+    fn test_letters_before_tones() {
+        assert_eq!(compare("má", "mai"), Ordering::Less);
+        assert_eq!(compare("mạ", "mă"), Ordering::Less);
+        assert_eq!(compare("ma", "ma ma"), Ordering::Less);
+        assert_eq!(compare("ma ma", "maa"), Ordering::Less);
+    }
+
+    #[test]
+    fn test_case_and_normalisation() {
+        assert_eq!(compare("an", "An"), Ordering::Less);
+        assert_eq!(compare("An", "án"), Ordering::Less);
+        assert_eq!(
+            compare("Việt", &VietnameseChar::to_nfd("Việt")),
+            Ordering::Equal
+        );
+    }
+
+    #[test]
+    fn test_other_characters() {
+        assert_eq!(compare("1", "a"), Ordering::Less);
+        assert_eq!(compare("z", "ñ"), Ordering::Less);
+        assert_eq!(compare("", "a"), Ordering::Less);
+    }
+
+    #[test]
+    fn test_digraph_weights() {
+        let collator = Collator::new().with_digraphs(true);
+        let mut words = vec!["tr", "th", "t", "u", "nh", "ng", "n", "o"];
+        words.sort_by(|a, b| collator.compare(a, b));
+        assert_eq!(words, ["n", "ng", "nh", "o", "t", "th", "tr", "u"]);
+        assert_eq!(collator.compare("Ngà", "ngà"), Ordering::Greater);
+    }
+}
@@ -0,0 +1,199 @@
+//! # InputEngine Conformance Kit
+//!
+//! Generic checks for the [`InputEngine`] contract, for engines written
+//! outside this crate. Enabled with the `conformance` feature.
+//!
+//! Each `check_*` function types a keystroke string (with
+//! [`BACKSPACE`](crate::testing::BACKSPACE) for the Backspace key) and
+//! panics on the first violation. [`conformance_suite!`](crate::conformance_suite)
+//! instantiates all of them as `#[test]` functions:
+//!
+//! ```ignore
+//! use vietime_core::conformance_suite;
+//!
+//! conformance_suite!(my_engine, MyEngine::new(), "tieengs vieetj\u{8}\u{8}aa.");
+//! ```
+//!
+//! The contract checked:
+//! - `reset` clears the buffer
+//! - `get_buffer` always equals `get_buffer_slice`, and a result's
+//!   `current_buffer` equals the buffer after the key
+//! - Backspace removes at most one buffered character, and an empty buffer
+//!   leaves the Backspace to the host
+//! - Applying every result to a text field leaves text ending with the buffer
+
+use crate::engines::InputEngine;
+use crate::testing::{VirtualDocument, BACKSPACE};
+
+/// Keys for [`conformance_suite!`](crate::conformance_suite) when none are given
+///
+/// Letters, Telex/VNI modifier keys, digits, punctuation and Backspaces.
+pub const DEFAULT_KEYS: &str =
+    "tieengs vieetj\u{8}\u{8}aa ddoongf hoaf\u{8}\u{8}\u{8}xin chaof, 0123456789 ASDFzz qu gi.\u{8}";
+
+/// Type one key, returning the engine's result buffer for a non-Backspace key
+fn type_key<E: InputEngine + ?Sized>(engine: &mut E, key: char) -> Option<String> {
+    if key == BACKSPACE {
+        engine.process_backspace();
+        None
+    } else {
+        Some(engine.process_key(key, key.is_uppercase()).current_buffer)
+    }
+}
+
+/// The engine has a non-empty name
+pub fn check_name<E: InputEngine + ?Sized>(engine: &E) {
+    assert!(!engine.name().is_empty(), "engine name is empty");
+}
+
+/// A new engine has an empty buffer
+pub fn check_starts_empty<E: InputEngine + ?Sized>(engine: &E) {
+    assert_eq!(engine.get_buffer(), "", "new engine has a non-empty buffer");
+    assert!(
+        engine.get_buffer_slice().is_empty(),
+        "new engine has a non-empty buffer slice"
+    );
+}
+
+/// `reset` clears the buffer after any prefix of `keys`
+pub fn check_reset_clears<E: InputEngine + ?Sized>(engine: &mut E, keys: &str) {
+    for key in keys.chars() {
+        type_key(engine, key);
+        engine.reset();
+        assert_eq!(
+            engine.get_buffer(),
+            "",
+            "buffer not cleared by reset after {:?}",
+            key
+        );
+        assert!(
+            engine.get_buffer_slice().is_empty(),
+            "buffer slice not cleared by reset after {:?}",
+            key
+        );
+    }
+}
+
+/// `get_buffer`, `get_buffer_slice` and `current_buffer` agree after every key
+pub fn check_buffer_views_agree<E: InputEngine + ?Sized>(engine: &mut E, keys: &str) {
+    for (i, key) in keys.chars().enumerate() {
+        let reported = type_key(engine, key);
+        let buffer = engine.get_buffer();
+        let slice: String = engine.get_buffer_slice().iter().collect();
+
+        assert_eq!(
+            buffer, slice,
+            "get_buffer and get_buffer_slice differ after key {} ({:?})",
+            i, key
+        );
+        if let Some(reported) = reported {
+            assert_eq!(
+                reported, buffer,
+                "current_buffer differs from get_buffer after key {} ({:?})",
+                i, key
+            );
+        }
+    }
+}
+
+/// Backspace removes at most one buffered character and is left to the
+/// host when the buffer is empty
+pub fn check_backspace_consistency<E: InputEngine + ?Sized>(engine: &mut E, keys: &str) {
+    assert!(
+        !engine.process_backspace(),
+        "Backspace on an empty buffer was consumed"
+    );
+    assert_eq!(
+        engine.get_buffer(),
+        "",
+        "Backspace on an empty buffer changed it"
+    );
+
+    for (i, key) in keys.chars().enumerate() {
+        type_key(engine, key);
+
+        let before = engine.get_buffer_slice().len();
+        let handled = engine.process_backspace();
+        let after = engine.get_buffer_slice().len();
+        assert!(
+            after + 1 >= before,
+            "Backspace after key {} ({:?}) removed {} buffered characters",
+            i,
+            key,
+            before - after
+        );
+        if before == 0 {
+            assert!(
+                !handled,
+                "Backspace on an empty buffer was consumed after key {} ({:?})",
+                i, key
+            );
+        }
+    }
+}
+
+/// Applying every result to a text field leaves text ending with the buffer
+pub fn check_output_reconstructs_buffer<E: InputEngine + ?Sized>(engine: &mut E, keys: &str) {
+    let mut document = VirtualDocument::new();
+    for (i, key) in keys.chars().enumerate() {
+        document.type_key(engine, key);
+
+        let text = document.text();
+        let buffer = engine.get_buffer();
+        assert!(
+            text.ends_with(&buffer),
+            "text {:?} does not end with buffer {:?} after key {} ({:?})",
+            text,
+            buffer,
+            i,
+            key
+        );
+    }
+}
+
+/// Instantiate every conformance check as a `#[test]` in a module `$name`
+///
+/// `$engine` is evaluated once per test to build a fresh engine; `$keys`
+/// defaults to [`DEFAULT_KEYS`](crate::conformance::DEFAULT_KEYS).
+#[macro_export]
+macro_rules! conformance_suite {
+    ($name:ident, $engine:expr) => {
+        $crate::conformance_suite!($name, $engine, $crate::conformance::DEFAULT_KEYS);
+    };
+    ($name:ident, $engine:expr, $keys:expr) => {
+        mod $name {
+            #[allow(unused_imports)]
+            use super::*;
+
+            #[test]
+            fn name() {
+                $crate::conformance::check_name(&$engine);
+            }
+
+            #[test]
+            fn starts_empty() {
+                $crate::conformance::check_starts_empty(&$engine);
+            }
+
+            #[test]
+            fn reset_clears() {
+                $crate::conformance::check_reset_clears(&mut $engine, $keys);
+            }
+
+            #[test]
+            fn buffer_views_agree() {
+                $crate::conformance::check_buffer_views_agree(&mut $engine, $keys);
+            }
+
+            #[test]
+            fn backspace_consistency() {
+                $crate::conformance::check_backspace_consistency(&mut $engine, $keys);
+            }
+
+            #[test]
+            fn output_reconstructs_buffer() {
+                $crate::conformance::check_output_reconstructs_buffer(&mut $engine, $keys);
+            }
+        }
+    };
+}
@@ -0,0 +1,480 @@
+//! # Encoding Converter
+//!
+//! Converts text between the encodings Vietnamese documents turn up in:
+//! Unicode (precomposed or combining), the legacy charsets of
+//! [`Charset`], VIQR (RFC 1456, `Vie^.t`) and HTML numeric character
+//! references (`Vi&#7879;t`, `Vi&#x1EC7;t`).
+//!
+//! Input is decoded to Unicode, base letters and combining marks are
+//! composed, and the result is encoded again. [`Converter`] works on
+//! chunks of any size, holding back bytes that may belong to a sequence
+//! (a UTF-8 character, a VNI-Windows letter + mark, an NCR); [`convert`]
+//! drives it from a reader to a writer. Characters the target cannot
+//! represent are written as `?` and reported as [`Unmappable`].
+//!
+//! ```
+//! use vietime_core::convert::{convert_bytes, Encoding};
+//!
+//! let vni = convert_bytes("Tiếng Việt".as_bytes(), Encoding::Unicode, Encoding::VniWindows);
+//! assert_eq!(vni.bytes, b"Tie\xE1ng Vie\xE4t");
+//!
+//! let viqr = convert_bytes(&vni.bytes, Encoding::VniWindows, Encoding::Viqr);
+//! assert_eq!(viqr.bytes, b"Tie^'ng Vie^.t");
+//! ```
+
+use std::io::{self, Read, Write};
+
+use crate::models::charset::Charset;
+use crate::models::vietnamese::{Modifier, ToneIndex, VietnameseChar};
+
+/// Bytes read ahead before decoding, enough for any one character
+const LOOKAHEAD: usize = 16;
+
+/// Characters VIQR uses as marks, and its escape
+const VIQR_MARKS: [char; 9] = ['^', '(', '+', '\'', '`', '?', '~', '.', '\\'];
+
+/// A text encoding
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Encoding {
+    /// UTF-8, precomposed letters (NFC)
+    Unicode,
+    /// UTF-8, base letters + combining marks (NFD)
+    UnicodeCombining,
+    /// TCVN 5712 VN3 ("ABC")
+    Tcvn3,
+    /// VNI-Windows
+    VniWindows,
+    /// VISCII
+    Viscii,
+    /// Windows-1258
+    Cp1258,
+    /// VIQR: ASCII letters followed by mark characters (RFC 1456)
+    Viqr,
+    /// Decimal numeric character references (`&#7879;`)
+    NcrDecimal,
+    /// Hexadecimal numeric character references (`&#x1EC7;`)
+    NcrHex,
+}
+
+impl Encoding {
+    /// All supported encodings
+    pub const ALL: [Encoding; 9] = [
+        Self::Unicode,
+        Self::UnicodeCombining,
+        Self::Tcvn3,
+        Self::VniWindows,
+        Self::Viscii,
+        Self::Cp1258,
+        Self::Viqr,
+        Self::NcrDecimal,
+        Self::NcrHex,
+    ];
+
+    /// Display name
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Viqr => "VIQR",
+            Self::NcrDecimal => "NCR decimal",
+            Self::NcrHex => "NCR hex",
+            _ => self.charset().map_or("", Charset::name),
+        }
+    }
+
+    /// The charset behind a byte encoding
+    pub fn charset(self) -> Option<Charset> {
+        match self {
+            Self::Unicode => Some(Charset::Unicode),
+            Self::UnicodeCombining => Some(Charset::UnicodeCombining),
+            Self::Tcvn3 => Some(Charset::Tcvn3),
+            Self::VniWindows => Some(Charset::VniWindows),
+            Self::Viscii => Some(Charset::Viscii),
+            Self::Cp1258 => Some(Charset::Cp1258),
+            Self::Viqr | Self::NcrDecimal | Self::NcrHex => None,
+        }
+    }
+}
+
+impl From<Charset> for Encoding {
+    fn from(charset: Charset) -> Self {
+        match charset {
+            Charset::Unicode => Self::Unicode,
+            Charset::UnicodeCombining => Self::UnicodeCombining,
+            Charset::Tcvn3 => Self::Tcvn3,
+            Charset::VniWindows => Self::VniWindows,
+            Charset::Viscii => Self::Viscii,
+            Charset::Cp1258 => Self::Cp1258,
+        }
+    }
+}
+
+/// A character the target encoding cannot represent
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Unmappable {
+    /// Position in the decoded text, in chars
+    pub offset: usize,
+    pub character: char,
+}
+
+/// Result of [`convert_bytes`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Converted {
+    pub bytes: Vec<u8>,
+    pub unmappable: Vec<Unmappable>,
+}
+
+/// Streaming converter between two encodings
+///
+/// # Example
+///
+/// ```
+/// use vietime_core::convert::{Converter, Encoding};
+///
+/// let mut converter = Converter::new(Encoding::VniWindows, Encoding::Unicode);
+/// // "eá" (ế) split across chunks
+/// let mut out = converter.feed(b"tie");
+/// out.extend(converter.feed(b"\xE1ng"));
+/// out.extend(converter.finish());
+/// assert_eq!(String::from_utf8(out).unwrap(), "tiếng");
+/// ```
+#[derive(Debug, Clone)]
+pub struct Converter {
+    from: Encoding,
+    to: Encoding,
+    /// Input bytes not decoded yet
+    input: Vec<u8>,
+    /// Decoded letter waiting for combining marks
+    letter: Vec<char>,
+    /// Last character encoded (VIQR escapes depend on it)
+    previous: Option<char>,
+    /// Characters encoded so far
+    offset: usize,
+    unmappable: Vec<Unmappable>,
+}
+
+impl Converter {
+    pub fn new(from: Encoding, to: Encoding) -> Self {
+        Self {
+            from,
+            to,
+            input: Vec::new(),
+            letter: Vec::new(),
+            previous: None,
+            offset: 0,
+            unmappable: Vec::new(),
+        }
+    }
+
+    /// Convert a chunk, returning the output ready so far
+    pub fn feed(&mut self, input: &[u8]) -> Vec<u8> {
+        self.input.extend_from_slice(input);
+        self.run(false)
+    }
+
+    /// Convert what is left at the end of the input
+    pub fn finish(&mut self) -> Vec<u8> {
+        let mut out = self.run(true);
+        self.flush_letter(&mut out);
+        out
+    }
+
+    /// Characters reported unmappable so far
+    pub fn unmappable(&self) -> &[Unmappable] {
+        &self.unmappable
+    }
+
+    fn run(&mut self, last: bool) -> Vec<u8> {
+        let mut out = Vec::new();
+        let mut pos = 0;
+        while pos < self.input.len() && (last || self.input.len() - pos >= LOOKAHEAD) {
+            let (c, len) = decode(self.from, &self.input[pos..]);
+            pos += len;
+            self.push_char(c, &mut out);
+        }
+        self.input.drain(..pos);
+        out
+    }
+
+    /// Compose combining marks onto the pending letter
+    fn push_char(&mut self, c: char, out: &mut Vec<u8>) {
+        if VietnameseChar::is_combining_mark(c) && !self.letter.is_empty() {
+            let mut marks = self.letter[1..].to_vec();
+            marks.push(c);
+            if VietnameseChar::compose(self.letter[0], &marks).is_some() {
+                self.letter.push(c);
+                return;
+            }
+        }
+        self.flush_letter(out);
+        self.letter.push(c);
+    }
+
+    fn flush_letter(&mut self, out: &mut Vec<u8>) {
+        let letter = std::mem::take(&mut self.letter);
+        let Some((&base, marks)) = letter.split_first() else {
+            return;
+        };
+        match VietnameseChar::compose(base, marks) {
+            Some(c) => self.encode(c, out),
+            None => letter.iter().for_each(|&c| self.encode(c, out)),
+        }
+    }
+
+    fn encode(&mut self, c: char, out: &mut Vec<u8>) {
+        let encoded = match self.to.charset() {
+            Some(charset) => charset.encode_char(c, out),
+            None if self.to == Encoding::Viqr => {
+                encode_viqr(c, self.previous, out);
+                true
+            }
+            None => {
+                encode_ncr(c, self.to == Encoding::NcrHex, out);
+                true
+            }
+        };
+        if !encoded {
+            self.unmappable.push(Unmappable {
+                offset: self.offset,
+                character: c,
+            });
+            out.push(b'?');
+        }
+        self.previous = Some(c);
+        self.offset += 1;
+    }
+}
+
+/// Convert everything `input` yields into `output`
+pub fn convert<R: Read, W: Write>(
+    mut input: R,
+    mut output: W,
+    from: Encoding,
+    to: Encoding,
+) -> io::Result<Vec<Unmappable>> {
+    let mut converter = Converter::new(from, to);
+    let mut chunk = [0; 8192];
+    loop {
+        let read = input.read(&mut chunk)?;
+        if read == 0 {
+            break;
+        }
+        output.write_all(&converter.feed(&chunk[..read]))?;
+    }
+    output.write_all(&converter.finish())?;
+    Ok(converter.unmappable)
+}
+
+/// Convert a byte string in one go
+pub fn convert_bytes(input: &[u8], from: Encoding, to: Encoding) -> Converted {
+    let mut converter = Converter::new(from, to);
+    let mut bytes = converter.feed(input);
+    bytes.extend(converter.finish());
+    Converted {
+        bytes,
+        unmappable: converter.unmappable,
+    }
+}
+
+/// Decode one character (U+FFFD for undecodable bytes)
+fn decode(from: Encoding, bytes: &[u8]) -> (char, usize) {
+    let decoded = match from {
+        // Combining marks are composed by the converter
+        Encoding::Unicode | Encoding::UnicodeCombining => Charset::Unicode.decode_char(bytes),
+        Encoding::Viqr => decode_viqr(bytes),
+        Encoding::NcrDecimal | Encoding::NcrHex => {
+            decode_ncr(bytes).or_else(|| Charset::Unicode.decode_char(bytes))
+        }
+        _ => from
+            .charset()
+            .and_then(|charset| charset.decode_char(bytes)),
+    };
+    decoded.unwrap_or(('\u{FFFD}', 1))
+}
+
+/// `&#7879;` or `&#x1EC7;` (either form is read whatever the encoding)
+fn decode_ncr(bytes: &[u8]) -> Option<(char, usize)> {
+    let rest = bytes.strip_prefix(b"&#")?;
+    let end = rest.iter().take(LOOKAHEAD - 2).position(|&b| b == b';')?;
+    let number = std::str::from_utf8(&rest[..end]).ok()?;
+    let code = match number.strip_prefix(['x', 'X']) {
+        Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+        None => number.parse().ok()?,
+    };
+    char::from_u32(code).map(|c| (c, end + 3))
+}
+
+fn encode_ncr(c: char, hex: bool, out: &mut Vec<u8>) {
+    if c.is_ascii() && c != '&' {
+        out.push(c as u8);
+    } else if hex {
+        out.extend_from_slice(format!("&#x{:X};", u32::from(c)).as_bytes());
+    } else {
+        out.extend_from_slice(format!("&#{};", u32::from(c)).as_bytes());
+    }
+}
+
+/// VIQR mark -> combining mark
+fn viqr_combining(mark: char) -> Option<char> {
+    let modifier = [Modifier::Circumflex, Modifier::Breve, Modifier::Horn]
+        .into_iter()
+        .find(|modifier| modifier.viqr_key() == mark);
+    let tone = ToneIndex::ALL
+        .into_iter()
+        .find(|tone| tone.viqr_key() == Some(mark));
+    modifier
+        .and_then(Modifier::combining_mark)
+        .or_else(|| tone.and_then(ToneIndex::combining_mark))
+}
+
+/// A letter and the marks after it, `dd` for đ, or `\` + a literal mark
+fn decode_viqr(bytes: &[u8]) -> Option<(char, usize)> {
+    let (first, len) = Charset::Unicode.decode_char(bytes)?;
+    let next = bytes.get(len).map(|&b| char::from(b));
+    match (first, next) {
+        ('\\', Some(c)) if VIQR_MARKS.contains(&c) => Some((c, 2)),
+        ('d' | 'D', Some('d' | 'D')) => Some((VietnameseChar::with_case_of('đ', first), 2)),
+        // d\d: a plain d before another d
+        ('d' | 'D', Some('\\')) if matches!(bytes.get(2), Some(b'd' | b'D')) => Some((first, 2)),
+        _ if VietnameseChar::is_vowel(first) => {
+            let mut marks = Vec::new();
+            for &b in &bytes[len..] {
+                let Some(mark) = viqr_combining(char::from(b)) else {
+                    break;
+                };
+                marks.push(mark);
+                if VietnameseChar::compose(first, &marks).is_none() {
+                    marks.pop();
+                    break;
+                }
+            }
+            let letter = VietnameseChar::compose(first, &marks)?;
+            Some((letter, len + marks.len()))
+        }
+        _ => Some((first, len)),
+    }
+}
+
+/// Letters as base + marks; a `\` keeps mark characters from joining the
+/// previous vowel, and separates a plain d from a following d or đ
+fn encode_viqr(c: char, previous: Option<char>, out: &mut Vec<u8>) {
+    let after_vowel = previous.is_some_and(VietnameseChar::is_vowel);
+    let after_d = matches!(previous, Some('d' | 'D'));
+    let mut text = String::new();
+    if c == '\\'
+        || (after_vowel && VIQR_MARKS.contains(&c))
+        || (after_d && VietnameseChar::get_base_letter(c).eq_ignore_ascii_case(&'d'))
+    {
+        text.push('\\');
+    }
+
+    let base = VietnameseChar::get_base_letter(c);
+    if base != c || VietnameseChar::is_vowel(c) {
+        text.push(base);
+        if let Some(modifier) = VietnameseChar::get_modifier(c) {
+            let mark = modifier.viqr_key();
+            text.push(VietnameseChar::with_case_of(mark, base));
+        }
+        text.extend(VietnameseChar::get_tone_index(c).viqr_key());
+    } else {
+        text.push(c);
+    }
+    out.extend_from_slice(text.as_bytes());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn to_unicode(input: &[u8], from: Encoding) -> String {
+        String::from_utf8(convert_bytes(input, from, Encoding::Unicode).bytes).unwrap()
+    }
+
+    fn from_unicode(text: &str, to: Encoding) -> Vec<u8> {
+        convert_bytes(text.as_bytes(), Encoding::Unicode, to).bytes
+    }
+
+    #[test]
+    fn test_viqr() {
+        assert_eq!(
+            from_unicode("Đường đi.", Encoding::Viqr),
+            b"DDu+o+`ng ddi\\."
+        );
+        assert_eq!(to_unicode(b"DDu+o+`ng ddi\\.", Encoding::Viqr), "Đường đi.");
+        // Plain dd and marks after letters are escaped
+        assert_eq!(
+            from_unicode("add a? C:\\", Encoding::Viqr),
+            b"ad\\d a\\? C:\\\\"
+        );
+        assert_eq!(
+            to_unicode(b"ad\\d a\\? C:\\\\", Encoding::Viqr),
+            "add a? C:\\"
+        );
+        assert_eq!(from_unicode("dđ", Encoding::Viqr), b"d\\dd");
+        assert_eq!(to_unicode(b"d\\dd", Encoding::Viqr), "dđ");
+        // A mark that does not fit stays a character
+        assert_eq!(to_unicode(b"a'.", Encoding::Viqr), "á.");
+    }
+
+    #[test]
+    fn test_ncr() {
+        assert_eq!(
+            from_unicode("Việt & co", Encoding::NcrDecimal),
+            b"Vi&#7879;t &#38; co"
+        );
+        assert_eq!(from_unicode("Việt", Encoding::NcrHex), b"Vi&#x1EC7;t");
+        assert_eq!(
+            to_unicode(b"Vi&#x1ec7;t &#38; &#bad; &", Encoding::NcrHex),
+            "Việt & &#bad; &"
+        );
+        // NCRs of a base letter + combining mark compose
+        assert_eq!(to_unicode(b"e&#770;&#769;", Encoding::NcrDecimal), "ế");
+    }
+
+    #[test]
+    fn test_combining_marks_compose() {
+        assert_eq!(
+            to_unicode("vie\u{323}\u{302}t".as_bytes(), Encoding::Unicode),
+            "việt"
+        );
+        assert_eq!(
+            from_unicode("việt", Encoding::UnicodeCombining),
+            "vie\u{323}\u{302}t".as_bytes()
+        );
+    }
+
+    #[test]
+    fn test_unmappable_reported() {
+        let converted = convert_bytes("VIỆT €".as_bytes(), Encoding::Unicode, Encoding::Tcvn3);
+        assert_eq!(converted.bytes, b"VI?T ?");
+        assert_eq!(
+            converted.unmappable,
+            [
+                Unmappable {
+                    offset: 2,
+                    character: 'Ệ'
+                },
+                Unmappable {
+                    offset: 5,
+                    character: '€'
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_streaming_any_chunk_size() {
+        let text = "Người Việt &#x1EC7; dd\\d ờ";
+        for from in Encoding::ALL {
+            let encoded = from_unicode(text, from);
+            let whole = convert_bytes(&encoded, from, Encoding::Unicode).bytes;
+            for size in 1..8 {
+                let mut converter = Converter::new(from, Encoding::Unicode);
+                let mut out = Vec::new();
+                for chunk in encoded.chunks(size) {
+                    out.extend(converter.feed(chunk));
+                }
+                out.extend(converter.finish());
+                assert_eq!(out, whole, "{} chunks of {}", from.name(), size);
+            }
+        }
+    }
+}
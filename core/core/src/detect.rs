@@ -0,0 +1,213 @@
+//! # Encoding Detection
+//!
+//! Guesses the encoding of Vietnamese text nobody labelled, and repairs
+//! mojibake.
+//!
+//! Every candidate decoding is scored by the share of its words that are
+//! Vietnamese syllables ([`Syllable::parse`]); undecodable bytes and stray
+//! control characters count against it. Mojibake is text that was decoded
+//! with the wrong charset and saved again: UTF-8 read as Windows-1252
+//! (`"ViÃªt"`), or TCVN3 shown as Latin-1 and saved as UTF-8. It is undone
+//! by chaining steps: the decoded text is turned back into the bytes it
+//! was read from (Windows-1252 / Latin-1) and decoded again.
+//!
+//! ```
+//! use vietime_core::convert::Encoding;
+//! use vietime_core::detect::detect;
+//!
+//! let guesses = detect(b"Ti\xD5ng Vi\xD6t c\xE3 d\xCAu");
+//! assert_eq!(guesses[0].steps, [Encoding::Tcvn3]);
+//! assert_eq!(guesses[0].text, "Tiếng Việt có dấu");
+//! ```
+
+use crate::convert::{convert_bytes, Encoding};
+use crate::models::syllable::Syllable;
+
+/// Encodings tried for each step (NCR hex and NFD decode like their
+/// siblings)
+const CANDIDATES: [Encoding; 7] = [
+    Encoding::Unicode,
+    Encoding::Tcvn3,
+    Encoding::VniWindows,
+    Encoding::Viscii,
+    Encoding::Cp1258,
+    Encoding::Viqr,
+    Encoding::NcrDecimal,
+];
+
+/// Most decode steps in a chain
+const MAX_STEPS: usize = 3;
+
+/// Bytes of input scored (the best chain is then applied to all of it)
+const SAMPLE_LEN: usize = 16 * 1024;
+
+/// Windows-1252 characters for bytes 0x80..=0x9F (others are Latin-1)
+const CP1252_C1: [char; 32] = [
+    '\u{20AC}', '\u{81}', '\u{201A}', '\u{0192}', '\u{201E}', '\u{2026}', '\u{2020}', '\u{2021}',
+    '\u{02C6}', '\u{2030}', '\u{0160}', '\u{2039}', '\u{0152}', '\u{8D}', '\u{017D}', '\u{8F}',
+    '\u{90}', '\u{2018}', '\u{2019}', '\u{201C}', '\u{201D}', '\u{2022}', '\u{2013}', '\u{2014}',
+    '\u{02DC}', '\u{2122}', '\u{0161}', '\u{203A}', '\u{0153}', '\u{9D}', '\u{017E}', '\u{0178}',
+];
+
+/// A candidate decoding
+#[derive(Debug, Clone, PartialEq)]
+pub struct Guess {
+    /// Decodings applied in order; each after the first re-reads the text
+    /// so far as Windows-1252 bytes
+    pub steps: Vec<Encoding>,
+    /// Decoded text
+    pub text: String,
+    /// Share of words that are Vietnamese syllables, 0.0 to 1.0
+    pub confidence: f32,
+}
+
+/// Rank candidate decodings of `bytes`, best first
+///
+/// Chains giving the same text as a shorter one are left out.
+pub fn detect(bytes: &[u8]) -> Vec<Guess> {
+    let sample = &bytes[..bytes.len().min(SAMPLE_LEN)];
+    let mut guesses: Vec<Guess> = Vec::new();
+    let mut frontier = vec![(Vec::new(), sample.to_vec())];
+
+    for _ in 0..MAX_STEPS {
+        let mut next = Vec::new();
+        for (steps, input) in frontier {
+            for encoding in CANDIDATES {
+                let text = decode(&input, encoding);
+                if guesses.iter().any(|guess| guess.text == text) {
+                    continue;
+                }
+                let mut steps = steps.clone();
+                steps.push(encoding);
+                if let Some(reread) = to_cp1252(&text) {
+                    next.push((steps.clone(), reread));
+                }
+                guesses.push(Guess {
+                    confidence: score(&text),
+                    steps,
+                    text,
+                });
+            }
+        }
+        frontier = next;
+    }
+
+    // Stable: ties keep the order above (fewer steps, Unicode first)
+    guesses.sort_by(|a, b| b.confidence.total_cmp(&a.confidence));
+    if bytes.len() > sample.len() {
+        for guess in &mut guesses {
+            guess.text = apply(bytes, &guess.steps);
+        }
+    }
+    guesses
+}
+
+/// Decode `bytes` with the best guess
+///
+/// # Example
+///
+/// ```
+/// use vietime_core::detect::repair;
+///
+/// assert_eq!(repair("Tiáº¿ng Viá»‡t".as_bytes()), "Tiếng Việt");
+/// ```
+pub fn repair(bytes: &[u8]) -> String {
+    detect(bytes)
+        .into_iter()
+        .next()
+        .map(|guess| guess.text)
+        .unwrap_or_default()
+}
+
+/// Run a chain of decode steps
+pub fn apply(bytes: &[u8], steps: &[Encoding]) -> String {
+    let mut input = bytes.to_vec();
+    let mut text = String::from_utf8_lossy(bytes).into_owned();
+    for (i, &encoding) in steps.iter().enumerate() {
+        if i > 0 {
+            match to_cp1252(&text) {
+                Some(reread) => input = reread,
+                None => break,
+            }
+        }
+        text = decode(&input, encoding);
+    }
+    text
+}
+
+fn decode(bytes: &[u8], encoding: Encoding) -> String {
+    String::from_utf8_lossy(&convert_bytes(bytes, encoding, Encoding::Unicode).bytes).into_owned()
+}
+
+/// The Windows-1252 bytes `text` was read from, if it has non-ASCII
+/// characters and all of them are in Windows-1252 or Latin-1
+fn to_cp1252(text: &str) -> Option<Vec<u8>> {
+    if text.is_ascii() {
+        return None;
+    }
+    text.chars()
+        .map(|c| match CP1252_C1.iter().position(|&x| x == c) {
+            Some(i) => Some(0x80 + i as u8),
+            None => u8::try_from(c).ok(),
+        })
+        .collect()
+}
+
+/// Share of words that are syllables, less the share of garbage characters
+fn score(text: &str) -> f32 {
+    let mut words = 0;
+    let mut syllables = 0;
+    for token in text.split_whitespace() {
+        let word = token.trim_matches(|c: char| c.is_ascii_punctuation());
+        if !word.chars().any(char::is_alphabetic) {
+            continue;
+        }
+        words += 1;
+        if Syllable::parse(word).is_some() {
+            syllables += 1;
+        }
+    }
+
+    let chars = text.chars().count().max(1);
+    let garbage = text
+        .chars()
+        .filter(|&c| c == '\u{FFFD}' || (c.is_control() && !c.is_ascii_whitespace()))
+        .count();
+    let valid = if words == 0 {
+        0.0
+    } else {
+        syllables as f32 / words as f32
+    };
+    (valid - garbage as f32 / chars as f32).max(0.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_score() {
+        assert_eq!(score("tiếng việt"), 1.0);
+        assert_eq!(score("tiếng xyz"), 0.5);
+        assert_eq!(score("123 ..."), 0.0);
+        assert!(score("tiếng\u{FFFD}") < 0.1);
+    }
+
+    #[test]
+    fn test_cp1252_reread() {
+        assert_eq!(to_cp1252("á»‡"), Some("ệ".as_bytes().to_vec()));
+        assert_eq!(to_cp1252("abc"), None);
+        assert_eq!(to_cp1252("ệ"), None);
+    }
+
+    #[test]
+    fn test_apply_chain() {
+        let double = "Việt"
+            .as_bytes()
+            .iter()
+            .map(|&b| char::from(b))
+            .collect::<String>();
+        let steps = [Encoding::Unicode, Encoding::Unicode];
+        assert_eq!(apply(double.as_bytes(), &steps), "Việt");
+    }
+}
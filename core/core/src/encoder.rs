@@ -0,0 +1,196 @@
+//! # Keystroke Encoder
+//!
+//! Turns Vietnamese text back into the keystrokes that type it, for typing
+//! tutors, macro playback and round-trip tests.
+//!
+//! Each letter is typed as its base letter followed by its modifier key
+//! ([`Modifier`]); the tone key ([`ToneIndex`]) goes either right after the
+//! toned vowel or at the end of the word. Characters other than letters
+//! are copied, escaped where the input method would otherwise treat them
+//! as marks.
+//!
+//! ```
+//! use vietime_core::encoder::{Encoder, InputMethod, TonePlacement};
+//!
+//! assert_eq!(Encoder::new(InputMethod::Telex).encode("tiếng việt"), "tieengs vieetj");
+//! assert_eq!(Encoder::new(InputMethod::Vni).encode("tiếng việt"), "tie6ng1 vie6t5");
+//!
+//! let viqr = Encoder::new(InputMethod::Viqr).with_tone_placement(TonePlacement::AfterVowel);
+//! assert_eq!(viqr.encode("tiếng việt"), "tie^'ng vie^.t");
+//! ```
+
+use crate::models::vietnamese::{Modifier, ToneIndex, VietnameseChar};
+
+/// Input method to encode for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputMethod {
+    /// Telex (aa, aw, dd, s f r x j)
+    Telex,
+    /// VNI (digits 1-9)
+    Vni,
+    /// VIQR (ASCII marks: ^ ( + ' ` ? ~ .)
+    Viqr,
+}
+
+/// Where the tone key goes within a word
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TonePlacement {
+    /// After the whole word (tieengs)
+    #[default]
+    EndOfWord,
+    /// Right after the vowel carrying the tone (tieesng)
+    AfterVowel,
+}
+
+/// Keystroke encoder for one input method
+#[derive(Debug, Clone, Copy)]
+pub struct Encoder {
+    method: InputMethod,
+    tone_placement: TonePlacement,
+}
+
+impl Encoder {
+    /// Encoder with tones at the end of each word
+    pub fn new(method: InputMethod) -> Self {
+        Self {
+            method,
+            tone_placement: TonePlacement::default(),
+        }
+    }
+
+    /// Set where tone keys go
+    pub fn with_tone_placement(mut self, tone_placement: TonePlacement) -> Self {
+        self.tone_placement = tone_placement;
+        self
+    }
+
+    /// Keystrokes typing `text`
+    pub fn encode(&self, text: &str) -> String {
+        let mut keys = String::new();
+        let mut word: Vec<char> = Vec::new();
+        for c in text.chars() {
+            if c.is_alphabetic() {
+                word.push(c);
+                continue;
+            }
+            self.encode_word(&word, &mut keys);
+            self.encode_other(c, !word.is_empty(), &mut keys);
+            word.clear();
+        }
+        self.encode_word(&word, &mut keys);
+        keys
+    }
+
+    fn tone_key(&self, tone: ToneIndex, toned: char) -> Option<char> {
+        let key = match self.method {
+            InputMethod::Telex => tone.telex_key(),
+            InputMethod::Vni => tone.vni_key(),
+            InputMethod::Viqr => tone.viqr_key(),
+        }?;
+        Some(Self::match_case(key, toned))
+    }
+
+    fn modifier_key(&self, modifier: Modifier, base: char) -> char {
+        match self.method {
+            InputMethod::Telex => Self::match_case(modifier.telex_key(base), base),
+            InputMethod::Vni => modifier.vni_key(),
+            InputMethod::Viqr => Self::match_case(modifier.viqr_key(), base),
+        }
+    }
+
+    /// Uppercase letter keys for uppercase letters (DD, AA, OW)
+    fn match_case(key: char, letter: char) -> char {
+        if letter.is_uppercase() {
+            key.to_ascii_uppercase()
+        } else {
+            key
+        }
+    }
+
+    fn doubles_plain_vowel(word: &[char], i: usize) -> bool {
+        let plain = |c: char| {
+            let letter = VietnameseChar::to_lower(VietnameseChar::get_vowel_without_tone(c));
+            matches!(letter, 'a' | 'e' | 'o').then_some(letter)
+        };
+        i > 0 && plain(word[i]).is_some() && plain(word[i]) == plain(word[i - 1])
+    }
+
+    fn encode_word(&self, word: &[char], keys: &mut String) {
+        let toned: Vec<usize> = (0..word.len())
+            .filter(|&i| VietnameseChar::get_tone_index(word[i]).is_toned())
+            .collect();
+        // Several tones cannot be one syllable: keep each next to its vowel
+        let tone_at_end = self.tone_placement == TonePlacement::EndOfWord && toned.len() == 1;
+
+        for (i, &c) in word.iter().enumerate() {
+            let base = VietnameseChar::get_base_letter(c);
+            let modifier = VietnameseChar::get_modifier(c);
+            if self.method == InputMethod::Telex && Self::doubles_plain_vowel(word, i) {
+                // oo (xoong): the second o would add a circumflex, a third undoes it
+                keys.push(base);
+            }
+            keys.push(base);
+            keys.extend(modifier.map(|m| self.modifier_key(m, base)));
+            if !tone_at_end && toned.contains(&i) {
+                keys.extend(self.tone_key(VietnameseChar::get_tone_index(c), c));
+            }
+        }
+
+        if tone_at_end {
+            let c = word[toned[0]];
+            keys.extend(self.tone_key(VietnameseChar::get_tone_index(c), c));
+        }
+    }
+
+    fn encode_other(&self, c: char, after_word: bool, keys: &mut String) {
+        match self.method {
+            // The first bracket types ư/ơ, the second gives the bracket back
+            InputMethod::Telex if matches!(c, '[' | ']' | '{' | '}') => {
+                keys.push(c);
+                keys.push(c);
+            }
+            // A backslash keeps a mark character literal after a letter
+            InputMethod::Viqr
+                if after_word
+                    && matches!(c, '^' | '(' | '+' | '\'' | '`' | '?' | '~' | '.' | '\\') =>
+            {
+                keys.push('\\');
+                keys.push(c);
+            }
+            _ => keys.push(c),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_telex_modifiers() {
+        let telex = Encoder::new(InputMethod::Telex);
+        assert_eq!(telex.encode("â ă ê ô ơ ư đ"), "aa aw ee oo ow uw dd");
+        assert_eq!(telex.encode("Đường"), "DDuwowngf");
+        assert_eq!(telex.encode("thuở"), "thuowr");
+        assert_eq!(telex.encode("THUỞ"), "THUOWR");
+        assert_eq!(telex.encode("xoong"), "xooong");
+    }
+
+    #[test]
+    fn test_tone_after_vowel() {
+        let telex = Encoder::new(InputMethod::Telex).with_tone_placement(TonePlacement::AfterVowel);
+        assert_eq!(telex.encode("người Việt"), "nguwowfi Vieejt");
+    }
+
+    #[test]
+    fn test_vni_and_viqr() {
+        assert_eq!(
+            Encoder::new(InputMethod::Vni).encode("Đường ăn"),
+            "D9u7o7ng2 a8n"
+        );
+        assert_eq!(
+            Encoder::new(InputMethod::Viqr).encode("Đường ăn?"),
+            "DDu+o+ng` a(n\\?"
+        );
+    }
+}
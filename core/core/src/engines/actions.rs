@@ -0,0 +1,333 @@
+//! # Edit Actions
+//!
+//! Structured form of engine output: an ordered list of [`EditAction`]s
+//! that a host applies to its text field.
+//!
+//! Hosts differ in the editing primitives they offer (synthetic Backspace,
+//! Shift+Left selection, IME `delete_surrounding_text`, caret moves). A
+//! [`HostCapabilities`] descriptor lets [`ProcessKeyResult::actions`] pick
+//! the cheapest sequence the host supports, and [`replay`] applies a list
+//! to a string for verification.
+
+use crate::engines::strategy::OutputStrategy;
+use crate::engines::ProcessKeyResult;
+
+/// A single editing primitive
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EditAction {
+    /// Press Backspace `n` times (deletes the selection first, if any)
+    Backspace(usize),
+
+    /// Extend the selection `n` characters to the left (Shift+Left)
+    SelectLeft(usize),
+
+    /// Delete `before` characters before and `after` characters after the
+    /// caret in one request (IBus/Wayland `delete_surrounding_text`)
+    DeleteSurrounding { before: usize, after: usize },
+
+    /// Move the caret by `n` characters (negative = left), clearing the selection
+    MoveCursor(isize),
+
+    /// Insert text at the caret, replacing the selection if any
+    Insert(String),
+}
+
+impl EditAction {
+    /// Number of host operations needed to perform the action
+    ///
+    /// Key-based primitives cost one operation per character; requests to
+    /// the host's text API (including caret moves) cost one.
+    pub fn cost(&self) -> usize {
+        match self {
+            Self::Backspace(n) | Self::SelectLeft(n) => *n,
+            Self::DeleteSurrounding { .. } | Self::MoveCursor(_) | Self::Insert(_) => 1,
+        }
+    }
+}
+
+/// Editing primitives supported by a host
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HostCapabilities {
+    /// Synthetic Backspace key presses
+    pub backspace: bool,
+
+    /// Synthetic Shift+Left selection, replaced by the next insert
+    pub select_left: bool,
+
+    /// Direct deletion around the caret through an input method API
+    pub delete_surrounding: bool,
+
+    /// Caret moves through the host's text API (Android `setSelection`,
+    /// accessibility APIs), letting a change inside the word skip the
+    /// letters after it
+    pub move_cursor: bool,
+}
+
+impl HostCapabilities {
+    /// Low-level keyboard hook sending synthetic key events (Windows, macOS)
+    pub const KEYBOARD_HOOK: Self = Self {
+        backspace: true,
+        select_left: true,
+        delete_surrounding: false,
+        move_cursor: false,
+    };
+
+    /// Input method framework client (IBus, Fcitx, Wayland input-method)
+    pub const INPUT_METHOD: Self = Self {
+        backspace: false,
+        select_left: false,
+        delete_surrounding: true,
+        move_cursor: false,
+    };
+}
+
+impl Default for HostCapabilities {
+    fn default() -> Self {
+        Self::KEYBOARD_HOOK
+    }
+}
+
+/// Total cost of an action list (see [`EditAction::cost`])
+pub fn total_cost(actions: &[EditAction]) -> usize {
+    actions.iter().map(EditAction::cost).sum()
+}
+
+impl ProcessKeyResult {
+    /// Convert the result into the cheapest action list the host supports
+    ///
+    /// A pass-through result gives an empty list: the host lets the original
+    /// key through.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use vietime_core::engines::actions::{EditAction, HostCapabilities};
+    /// use vietime_core::engines::ProcessKeyResult;
+    ///
+    /// let result = ProcessKeyResult::replace(2, "ết".to_string(), "việt".to_string());
+    ///
+    /// assert_eq!(
+    ///     result.actions(&HostCapabilities::INPUT_METHOD),
+    ///     vec![
+    ///         EditAction::DeleteSurrounding { before: 2, after: 0 },
+    ///         EditAction::Insert("ết".to_string()),
+    ///     ]
+    /// );
+    /// ```
+    pub fn actions(&self, caps: &HostCapabilities) -> Vec<EditAction> {
+        self.actions_with_strategy(caps, OutputStrategy::Direct)
+    }
+
+    /// Convert the result into an action list using an output strategy
+    ///
+    /// With [`OutputStrategy::DismissSuggestion`], key-based deletions are
+    /// preceded by the dummy character, which is deleted along with them.
+    /// Caret moves are only used with [`OutputStrategy::Direct`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use vietime_core::engines::actions::{EditAction, HostCapabilities};
+    /// use vietime_core::engines::strategy::OutputStrategy;
+    /// use vietime_core::engines::ProcessKeyResult;
+    ///
+    /// let result = ProcessKeyResult::replace(1, "ê".to_string(), "viê".to_string());
+    ///
+    /// assert_eq!(
+    ///     result.actions_with_strategy(&HostCapabilities::KEYBOARD_HOOK, OutputStrategy::SPACE),
+    ///     vec![
+    ///         EditAction::Insert(" ".to_string()),
+    ///         EditAction::Backspace(2),
+    ///         EditAction::Insert("ê".to_string()),
+    ///     ]
+    /// );
+    /// ```
+    pub fn actions_with_strategy(
+        &self,
+        caps: &HostCapabilities,
+        strategy: OutputStrategy,
+    ) -> Vec<EditAction> {
+        if !self.handled {
+            return Vec::new();
+        }
+
+        let text = self.output_text.clone().unwrap_or_default();
+        let count = self.backspace_count;
+        let mut candidates = replacements(caps, count, &text);
+
+        // Step over the unchanged end of the word, edit, and step back
+        let kept = self.kept_suffix.min(count).min(text.chars().count());
+        if caps.move_cursor && kept > 0 && strategy == OutputStrategy::Direct {
+            let changed: String = text.chars().take(text.chars().count() - kept).collect();
+            for inner in replacements(caps, count - kept, &changed) {
+                let mut candidate = vec![EditAction::MoveCursor(-(kept as isize))];
+                candidate.extend(inner);
+                candidate.push(EditAction::MoveCursor(kept as isize));
+                candidates.push(candidate);
+            }
+        }
+
+        // Among equally cheap lists, rewrite the fewest letters
+        let mut actions = candidates
+            .into_iter()
+            .min_by_key(|candidate| (total_cost(candidate), inserted_len(candidate)))
+            .unwrap_or_default();
+
+        if let OutputStrategy::DismissSuggestion(dummy) = strategy {
+            if let Some(EditAction::Backspace(n) | EditAction::SelectLeft(n)) = actions.first_mut()
+            {
+                *n += 1;
+                actions.insert(0, EditAction::Insert(dummy.to_string()));
+            }
+        }
+        actions
+    }
+}
+
+/// Characters inserted by an action list
+fn inserted_len(actions: &[EditAction]) -> usize {
+    actions
+        .iter()
+        .map(|action| match action {
+            EditAction::Insert(text) => text.chars().count(),
+            _ => 0,
+        })
+        .sum()
+}
+
+/// Ways to delete `count` characters before the caret and insert `text`
+fn replacements(caps: &HostCapabilities, count: usize, text: &str) -> Vec<Vec<EditAction>> {
+    let mut candidates: Vec<Vec<EditAction>> = Vec::new();
+    if count == 0 {
+        candidates.push(Vec::new());
+    } else {
+        if caps.delete_surrounding {
+            candidates.push(vec![EditAction::DeleteSurrounding {
+                before: count,
+                after: 0,
+            }]);
+        }
+        if caps.backspace {
+            candidates.push(vec![EditAction::Backspace(count)]);
+        }
+        if caps.select_left && !text.is_empty() {
+            // The insert replaces the selection
+            candidates.push(vec![EditAction::SelectLeft(count)]);
+        }
+        if candidates.is_empty() {
+            // Nothing better available; Backspace is understood everywhere
+            candidates.push(vec![EditAction::Backspace(count)]);
+        }
+    }
+
+    if !text.is_empty() {
+        for candidate in &mut candidates {
+            candidate.push(EditAction::Insert(text.to_string()));
+        }
+    }
+    candidates
+}
+
+/// In-memory text field with caret and selection, used to replay actions
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct TextField {
+    pub(crate) text: Vec<char>,
+    pub(crate) caret: usize,
+    /// Other end of the selection, if any
+    pub(crate) anchor: Option<usize>,
+}
+
+impl TextField {
+    /// Field holding `text` with the caret at the end
+    pub(crate) fn new(text: &str) -> Self {
+        let text: Vec<char> = text.chars().collect();
+        Self {
+            caret: text.len(),
+            text,
+            anchor: None,
+        }
+    }
+
+    fn selection(&self) -> Option<(usize, usize)> {
+        self.anchor
+            .filter(|&anchor| anchor != self.caret)
+            .map(|anchor| (anchor.min(self.caret), anchor.max(self.caret)))
+    }
+
+    /// Delete the selection, returning true if there was one
+    fn delete_selection(&mut self) -> bool {
+        let selection = self.selection();
+        self.anchor = None;
+        match selection {
+            Some((start, end)) => {
+                self.text.drain(start..end);
+                self.caret = start;
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub(crate) fn apply(&mut self, action: &EditAction) {
+        match action {
+            EditAction::Backspace(n) => {
+                let mut n = *n;
+                if n > 0 && self.delete_selection() {
+                    n -= 1;
+                }
+                let start = self.caret.saturating_sub(n);
+                self.text.drain(start..self.caret);
+                self.caret = start;
+            }
+            EditAction::SelectLeft(n) => {
+                self.anchor = Some(self.anchor.unwrap_or(self.caret));
+                self.caret = self.caret.saturating_sub(*n);
+            }
+            EditAction::DeleteSurrounding { before, after } => {
+                self.anchor = None;
+                let start = self.caret.saturating_sub(*before);
+                let end = (self.caret + after).min(self.text.len());
+                self.text.drain(start..end);
+                self.caret = start;
+            }
+            EditAction::MoveCursor(n) => self.move_caret(*n),
+            EditAction::Insert(text) => {
+                self.delete_selection();
+                let chars: Vec<char> = text.chars().collect();
+                let len = chars.len();
+                self.text.splice(self.caret..self.caret, chars);
+                self.caret += len;
+            }
+        }
+    }
+
+    /// Move the caret by `offset` characters (negative = left), as the user
+    /// would with the arrow keys, clearing the selection
+    pub(crate) fn move_caret(&mut self, offset: isize) {
+        self.anchor = None;
+        let caret = self.caret as isize + offset;
+        self.caret = caret.clamp(0, self.text.len() as isize) as usize;
+    }
+
+    pub(crate) fn text(&self) -> String {
+        self.text.iter().collect()
+    }
+}
+
+/// Apply an action list to `text`, with the caret starting at the end
+///
+/// # Example
+///
+/// ```
+/// use vietime_core::engines::actions::{replay, EditAction};
+///
+/// let actions = [EditAction::SelectLeft(2), EditAction::Insert("ết".to_string())];
+/// assert_eq!(replay("viêt", &actions), "viết");
+/// ```
+pub fn replay(text: &str, actions: &[EditAction]) -> String {
+    let mut field = TextField::new(text);
+    for action in actions {
+        field.apply(action);
+    }
+    field.text()
+}
@@ -0,0 +1,449 @@
+//! # Charset Layer
+//!
+//! Drives any [`InputEngine`] for hosts that do not hold precomposed
+//! Unicode: legacy fonts (TCVN3, VNI-Windows, VISCII), the CP1258 code
+//! page, or decomposed Unicode (macOS file names, some web forms).
+//!
+//! The engine runs unchanged on Unicode NFC text:
+//!
+//! - Output: every result is re-encoded with [`Charset::host_text`]. In
+//!   VNI-Windows and CP1258 a letter can take two characters (`ế` is
+//!   `"eá"` in VNI-Windows), and three in NFD (`"e\u{302}\u{301}"`), so
+//!   replacing it takes as many backspaces.
+//! - Input: keys are read with [`Charset::from_host_text`], so a combining
+//!   mark or a legacy mark byte typed after a letter changes that letter in
+//!   the engine, and a legacy letter code is the letter.
+//! - The on-screen text of every buffered letter is kept as it arrived, so
+//!   a letter typed or pasted as `e` + U+0301 is still deleted with two
+//!   backspaces.
+
+use crate::engines::{EngineEvent, InputEngine, KeyCode, KeyEvent, ProcessKeyResult};
+use crate::models::charset::Charset;
+use crate::VietnameseChar;
+
+/// Wrapper reading and writing an engine's text in a [`Charset`]
+///
+/// Buffers, results and backspace counts are all in host characters.
+///
+/// # Example
+///
+/// ```
+/// use vietime_core::engines::charset::CharsetOutput;
+/// use vietime_core::engines::telex::TelexEngine;
+/// use vietime_core::models::charset::Charset;
+/// use vietime_core::testing::type_keys;
+///
+/// let mut engine = CharsetOutput::new(TelexEngine::new(), Charset::VniWindows);
+/// assert_eq!(type_keys(&mut engine, "tieengs vieetj"), "tieáng vieät");
+/// ```
+pub struct CharsetOutput<E: InputEngine> {
+    engine: E,
+    charset: Charset,
+    /// On-screen text of each character of the engine buffer
+    forms: Vec<String>,
+    /// `forms` joined
+    buffer: Vec<char>,
+}
+
+impl<E: InputEngine> CharsetOutput<E> {
+    pub fn new(engine: E, charset: Charset) -> Self {
+        Self {
+            engine,
+            charset,
+            forms: Vec::new(),
+            buffer: Vec::new(),
+        }
+    }
+
+    /// Wrapped engine
+    pub fn engine(&self) -> &E {
+        &self.engine
+    }
+
+    /// Wrapped engine (mutable)
+    pub fn engine_mut(&mut self) -> &mut E {
+        &mut self.engine
+    }
+
+    /// Host charset
+    pub fn charset(&self) -> Charset {
+        self.charset
+    }
+
+    /// Change the host charset
+    ///
+    /// Resets the engine: the word on screen is in the old charset.
+    pub fn set_charset(&mut self, charset: Charset) {
+        self.charset = charset;
+        self.reset();
+    }
+
+    /// Host text for one character
+    ///
+    /// Letters the charset lacks fall back to lower case (TCVN3 capitals
+    /// fonts use the lowercase codes), then to the character itself.
+    fn host_text(&self, c: char) -> String {
+        self.charset
+            .host_text(c)
+            .or_else(|| self.charset.host_text(VietnameseChar::to_lower(c)))
+            .unwrap_or_else(|| c.to_string())
+    }
+
+    fn encode(&self, text: &str) -> String {
+        text.chars().map(|c| self.host_text(c)).collect()
+    }
+
+    /// The character host text stands for, if it is exactly one
+    fn single_char(&self, text: &str) -> Option<char> {
+        let decoded = self.charset.from_host_text(text);
+        let mut chars = decoded.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Some(c),
+            _ => None,
+        }
+    }
+
+    /// Engine buffer characters with their on-screen text
+    fn letters(&self) -> Vec<(char, String)> {
+        self.engine
+            .get_buffer()
+            .chars()
+            .zip(self.forms.iter().cloned())
+            .collect()
+    }
+
+    /// Host characters taken by the last `count` of `letters`
+    ///
+    /// Characters before the buffer are assumed to take one each.
+    fn screen_len(letters: &[(char, String)], count: usize) -> usize {
+        let start = letters.len().saturating_sub(count);
+        let outside = count - (letters.len() - start);
+        outside
+            + letters[start..]
+                .iter()
+                .map(|(_, form)| form.chars().count())
+                .sum::<usize>()
+    }
+
+    /// Match the engine buffer against the end of the on-screen letters
+    ///
+    /// Letters the engine changed without telling (none of the bundled
+    /// engines do) are assumed to be in the charset's form.
+    fn align(&mut self, screen: &[(char, String)]) {
+        let buffer: Vec<char> = self.engine.get_buffer().chars().collect();
+        let tail = &screen[screen.len().saturating_sub(buffer.len())..];
+        let skip = buffer.len() - tail.len();
+        self.forms = buffer
+            .iter()
+            .enumerate()
+            .map(|(i, &c)| match i.checked_sub(skip).map(|j| &tail[j]) {
+                Some((letter, form)) if *letter == c => form.clone(),
+                _ => self.host_text(c),
+            })
+            .collect();
+        self.buffer = self.forms.iter().flat_map(|form| form.chars()).collect();
+    }
+
+    /// Re-encode an engine result given the buffered letters before the key
+    ///
+    /// `key` is the key as typed and the character the engine received.
+    fn translate(
+        &mut self,
+        old: Vec<(char, String)>,
+        result: ProcessKeyResult,
+        key: Option<(char, char)>,
+    ) -> ProcessKeyResult {
+        let mut screen = old;
+        let mut translated = ProcessKeyResult::pass_through(String::new());
+        if result.handled {
+            let keep = screen.len().saturating_sub(result.backspace_count);
+            let backspaces = Self::screen_len(&screen, result.backspace_count);
+            screen.truncate(keep);
+            let output = result.output_text.unwrap_or_default();
+            screen.extend(output.chars().map(|c| (c, self.host_text(c))));
+            translated = ProcessKeyResult::replace(backspaces, self.encode(&output), String::new());
+        } else if let Some((typed, c)) = key {
+            // The key reaches the host as typed; send the charset's form
+            // instead when the typed one cannot be in the document (ư from a
+            // Vietnamese keyboard layout into a VNI-Windows document)
+            if self.charset.is_host_text(&typed.to_string()) {
+                screen.push((c, typed.to_string()));
+            } else {
+                screen.push((c, self.host_text(c)));
+                translated = ProcessKeyResult::replace(0, self.host_text(c), String::new());
+            }
+        }
+
+        self.align(&screen);
+        translated.current_buffer = self.get_buffer();
+        translated
+    }
+
+    /// A key completing the last letter: a combining mark (NFD, CP1258)
+    /// or a legacy mark byte (the `á` of VNI-Windows `"eá"`)
+    ///
+    /// The engine's letter is replaced by the combined one; on screen the
+    /// key is added to the letter as typed.
+    fn combine(&mut self, key: char) -> Option<ProcessKeyResult> {
+        let old = self.letters();
+        let (last, form) = old.last()?;
+        let combined = self.single_char(&format!("{}{}", form, key))?;
+        if combined == *last {
+            return None;
+        }
+
+        self.engine.process_backspace();
+        self.engine.process_key(combined, combined.is_uppercase());
+        let mut expected: Vec<char> = old[..old.len() - 1].iter().map(|(c, _)| *c).collect();
+        expected.push(combined);
+
+        if self
+            .engine
+            .get_buffer()
+            .chars()
+            .ne(expected.iter().copied())
+        {
+            // The engine took the letter as a key: replace the whole word
+            let backspaces = Self::screen_len(&old, old.len());
+            self.align(&[]);
+            return Some(ProcessKeyResult::replace(
+                backspaces,
+                self.get_buffer(),
+                self.get_buffer(),
+            ));
+        }
+
+        let mut screen = old;
+        let (_, form) = screen.pop()?;
+        let result = if self.charset.is_host_text(&key.to_string()) {
+            screen.push((combined, format!("{}{}", form, key)));
+            ProcessKeyResult::pass_through(String::new())
+        } else {
+            let text = self.host_text(combined);
+            let backspaces = form.chars().count();
+            screen.push((combined, text.clone()));
+            ProcessKeyResult::replace(backspaces, text, String::new())
+        };
+        self.align(&screen);
+        Some(ProcessKeyResult {
+            current_buffer: self.get_buffer(),
+            ..result
+        })
+    }
+}
+
+impl<E: InputEngine> InputEngine for CharsetOutput<E> {
+    fn name(&self) -> &str {
+        self.engine.name()
+    }
+
+    fn process_key(&mut self, key: char, is_shift_pressed: bool) -> ProcessKeyResult {
+        if !key.is_ascii() {
+            if let Some(result) = self.combine(key) {
+                return result;
+            }
+        }
+        let c = self.single_char(&key.to_string()).unwrap_or(key);
+        let old = self.letters();
+        let result = self.engine.process_key(c, is_shift_pressed);
+        self.translate(old, result, Some((key, c)))
+    }
+
+    /// Process a full key event
+    ///
+    /// Backspace over a letter taking several host characters deletes all
+    /// of them.
+    fn process_key_event(&mut self, event: &KeyEvent) -> ProcessKeyResult {
+        let plain = event.is_press() && !event.modifiers.is_command();
+        match (event.code, event.key) {
+            (KeyCode::Backspace, _) if plain => {
+                let old = self.letters();
+                let backspaces = Self::screen_len(&old, 1);
+                if backspaces == 1 {
+                    let handled = self.process_backspace();
+                    return ProcessKeyResult {
+                        handled,
+                        ..ProcessKeyResult::pass_through(self.get_buffer())
+                    };
+                }
+                let handled = self.engine.process_backspace();
+                let mut screen = old;
+                if !handled {
+                    screen.pop();
+                }
+                self.align(&screen);
+                if handled {
+                    return ProcessKeyResult {
+                        handled,
+                        ..ProcessKeyResult::pass_through(self.get_buffer())
+                    };
+                }
+                ProcessKeyResult::replace(backspaces, String::new(), self.get_buffer())
+            }
+            (KeyCode::Char, Some(key)) if plain => {
+                self.set_caps_lock(event.modifiers.caps_lock);
+                self.process_key(key, event.modifiers.shift)
+            }
+            _ => {
+                let old = self.letters();
+                let result = self.engine.process_key_event(event);
+                self.translate(old, result, None)
+            }
+        }
+    }
+
+    fn set_caps_lock(&mut self, caps_lock: bool) {
+        self.engine.set_caps_lock(caps_lock);
+    }
+
+    fn process_chord(&mut self, keys: &[char]) -> ProcessKeyResult {
+        let old = self.letters();
+        let result = self.engine.process_chord(keys);
+        self.translate(old, result, None)
+    }
+
+    fn reset(&mut self) {
+        self.engine.reset();
+        self.forms.clear();
+        self.buffer.clear();
+    }
+
+    fn handle_event(&mut self, event: &EngineEvent) {
+        let old = self.letters();
+        self.engine.handle_event(event);
+        self.align(&old);
+    }
+
+    /// Process backspace
+    ///
+    /// The host deletes one character. For a letter taking several (VNI-Windows
+    /// `"eá"`) that leaves part of it on screen, which no engine buffer can
+    /// hold, so the word is ended instead; use
+    /// [`process_key_event`](Self::process_key_event) to delete whole letters.
+    fn process_backspace(&mut self) -> bool {
+        let mut screen = self.letters();
+        if Self::screen_len(&screen, 1) > 1 {
+            self.reset();
+            return false;
+        }
+        let handled = self.engine.process_backspace();
+        if !handled {
+            screen.pop();
+        }
+        self.align(&screen);
+        handled
+    }
+
+    fn get_buffer(&self) -> String {
+        self.buffer.iter().collect()
+    }
+
+    fn get_buffer_slice(&self) -> &[char] {
+        &self.buffer
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engines::telex::TelexEngine;
+    use crate::engines::Modifiers;
+
+    fn telex(charset: Charset) -> CharsetOutput<TelexEngine> {
+        CharsetOutput::new(TelexEngine::new(), charset)
+    }
+
+    #[test]
+    fn test_backspaces_count_host_characters() {
+        let mut engine = telex(Charset::VniWindows);
+        for key in "tiee".chars() {
+            engine.process_key(key, false);
+        }
+        // tiê -> tiế: ê is "eâ", ế is "eá"
+        let result = engine.process_key('s', false);
+        assert_eq!(result.backspace_count, 2);
+        assert_eq!(result.output_text.as_deref(), Some("eá"));
+        assert_eq!(result.current_buffer, "tieá");
+    }
+
+    #[test]
+    fn test_single_byte_charsets() {
+        let mut engine = telex(Charset::Tcvn3);
+        for key in "tiee".chars() {
+            engine.process_key(key, false);
+        }
+        let result = engine.process_key('s', false);
+        assert_eq!(result.backspace_count, 1);
+        assert_eq!(result.output_text.as_deref(), Some("Õ"));
+    }
+
+    #[test]
+    fn test_tcvn3_capitals_use_lowercase_codes() {
+        let mut engine = telex(Charset::Tcvn3);
+        for key in "VIEETJ".chars() {
+            engine.process_key(key, true);
+        }
+        assert_eq!(engine.get_buffer(), "VIÖT");
+    }
+
+    #[test]
+    fn test_backspace_event_deletes_whole_letter() {
+        let mut engine = telex(Charset::Cp1258);
+        for key in "vieetj".chars() {
+            engine.process_key(key, false);
+        }
+        assert_eq!(engine.get_buffer(), "viê\u{323}t");
+
+        let backspace = KeyEvent::new(KeyCode::Backspace);
+        let result = engine.process_key_event(&backspace);
+        assert!(!result.handled);
+        assert_eq!(engine.get_buffer(), "viê\u{323}");
+
+        let result = engine.process_key_event(&backspace);
+        assert!(result.handled);
+        assert_eq!(result.backspace_count, 2);
+        assert_eq!(engine.get_buffer(), "vi");
+    }
+
+    #[test]
+    fn test_backspace_event_deletes_pasted_nfd_letter() {
+        let mut engine = telex(Charset::Unicode);
+        for key in "a\u{306}\u{301}".chars() {
+            engine.process_key(key, false);
+        }
+        assert_eq!(engine.engine().get_buffer(), "ắ");
+        assert_eq!(engine.get_buffer(), "a\u{306}\u{301}");
+
+        let result = engine.process_key_event(&KeyEvent::new(KeyCode::Backspace));
+        assert_eq!(result.backspace_count, 3);
+        assert_eq!(engine.get_buffer(), "");
+    }
+
+    #[test]
+    fn test_set_charset_resets() {
+        let mut engine = telex(Charset::Viscii);
+        engine.process_key('a', false);
+        engine.set_charset(Charset::Unicode);
+        assert_eq!(engine.get_buffer(), "");
+        assert_eq!(engine.charset(), Charset::Unicode);
+    }
+
+    #[test]
+    fn test_caps_lock_reaches_engine() {
+        let mut engine = telex(Charset::Unicode);
+        let caps_lock = Modifiers {
+            caps_lock: true,
+            ..Modifiers::NONE
+        };
+        for key in ['A', 'W'] {
+            engine.process_key_event(&KeyEvent::char(key).with_modifiers(caps_lock));
+        }
+        // Shift with CapsLock types a lowercase w, kept in the word's case
+        let shifted = Modifiers {
+            shift: true,
+            ..caps_lock
+        };
+        engine.process_key_event(&KeyEvent::char('w').with_modifiers(shifted));
+        assert_eq!(engine.get_buffer(), "AW");
+    }
+}
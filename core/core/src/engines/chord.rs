@@ -0,0 +1,466 @@
+//! # Chord Input Engine
+//!
+//! Stenographic Vietnamese input: one stroke (a set of keys pressed
+//! together) produces one syllable.
+//!
+//! A stroke is split into three key groups defined by a [`ChordTheory`]:
+//! onset keys, rhyme keys and tone keys. Each group is looked up in the
+//! theory table and the parts are joined into a syllable, with the tone
+//! placed using [`VietnameseChar::find_tone_position`].
+//!
+//! Briefs (dictionary entries) map a whole stroke to arbitrary text and
+//! take precedence over the theory. The theory's undo key removes the
+//! output of the previous stroke.
+
+use std::collections::HashMap;
+
+use crate::engines::diff::{kept_suffix, minimal_edit};
+use crate::engines::{InputEngine, ProcessKeyResult};
+use crate::models::vietnamese::{ToneIndex, VietnameseChar};
+
+/// Maximum number of strokes that can be undone
+pub const MAX_UNDO_STROKES: usize = 32;
+
+/// Key layout and lookup tables for a chord system
+///
+/// # Example
+///
+/// ```
+/// use vietime_core::engines::chord::ChordTheory;
+/// use vietime_core::ToneIndex;
+///
+/// let theory = ChordTheory::new("td", "ao", "s")
+///     .with_onset("t", "t")
+///     .with_onset("td", "th")
+///     .with_rhyme("a", "a")
+///     .with_rhyme("ao", "ao")
+///     .with_tone("s", ToneIndex::Acute);
+///
+/// assert_eq!(theory.syllable(&['d', 't', 'a', 'o', 's']), Some("tháo".to_string()));
+/// ```
+#[derive(Debug, Clone)]
+pub struct ChordTheory {
+    onset_keys: Vec<char>,
+    rhyme_keys: Vec<char>,
+    tone_keys: Vec<char>,
+    undo_key: char,
+    onsets: HashMap<String, String>,
+    rhymes: HashMap<String, String>,
+    tones: HashMap<String, ToneIndex>,
+}
+
+impl ChordTheory {
+    /// Create an empty theory with the given key groups
+    ///
+    /// The order of keys in each group defines the canonical stroke order.
+    pub fn new(onset_keys: &str, rhyme_keys: &str, tone_keys: &str) -> Self {
+        Self {
+            onset_keys: onset_keys.chars().collect(),
+            rhyme_keys: rhyme_keys.chars().collect(),
+            tone_keys: tone_keys.chars().collect(),
+            undo_key: '*',
+            onsets: HashMap::new(),
+            rhymes: HashMap::new(),
+            tones: HashMap::new(),
+        }
+    }
+
+    /// Map an onset chord to an initial consonant (cluster)
+    pub fn with_onset(mut self, chord: &str, onset: &str) -> Self {
+        let keys: Vec<char> = chord.chars().collect();
+        self.onsets.insert(
+            Self::group_chord(&self.onset_keys, &keys),
+            onset.to_string(),
+        );
+        self
+    }
+
+    /// Map a rhyme chord to a rhyme (vowel nucleus + final)
+    pub fn with_rhyme(mut self, chord: &str, rhyme: &str) -> Self {
+        let keys: Vec<char> = chord.chars().collect();
+        self.rhymes.insert(
+            Self::group_chord(&self.rhyme_keys, &keys),
+            rhyme.to_string(),
+        );
+        self
+    }
+
+    /// Map a tone chord to a tone
+    pub fn with_tone(mut self, chord: &str, tone: ToneIndex) -> Self {
+        let keys: Vec<char> = chord.chars().collect();
+        self.tones
+            .insert(Self::group_chord(&self.tone_keys, &keys), tone);
+        self
+    }
+
+    /// Set the key that undoes the previous stroke when pressed alone
+    pub fn with_undo_key(mut self, key: char) -> Self {
+        self.undo_key = key;
+        self
+    }
+
+    /// Key that undoes the previous stroke
+    pub fn undo_key(&self) -> char {
+        self.undo_key
+    }
+
+    /// Basic QWERTY theory
+    ///
+    /// - Onset (left hand): `q w e r a s d f`
+    /// - Rhyme: nucleus on `c v b n m`, final on `u i o p`
+    /// - Tone: `j` huyền, `k` sắc, `l` hỏi, `;` ngã, `'` nặng
+    /// - Undo: `*`
+    pub fn basic() -> Self {
+        const ONSETS: [(&str, &str); 27] = [
+            ("a", "b"),
+            ("s", "c"),
+            ("d", "d"),
+            ("f", "g"),
+            ("q", "h"),
+            ("w", "k"),
+            ("e", "l"),
+            ("r", "m"),
+            ("as", "n"),
+            ("sd", "p"),
+            ("df", "r"),
+            ("qw", "s"),
+            ("we", "t"),
+            ("er", "v"),
+            ("qa", "x"),
+            ("ws", "đ"),
+            ("ed", "ph"),
+            ("rf", "th"),
+            ("af", "tr"),
+            ("qr", "ch"),
+            ("sf", "kh"),
+            ("ad", "nh"),
+            ("wr", "ng"),
+            ("qe", "gh"),
+            ("qs", "gi"),
+            ("wd", "qu"),
+            ("ef", "ngh"),
+        ];
+        const NUCLEI: [(&str, &str); 25] = [
+            ("c", "a"),
+            ("v", "e"),
+            ("b", "i"),
+            ("n", "o"),
+            ("m", "u"),
+            ("cv", "ă"),
+            ("cb", "â"),
+            ("vb", "ê"),
+            ("nm", "ô"),
+            ("bn", "ơ"),
+            ("bm", "ư"),
+            ("cm", "y"),
+            ("cn", "oa"),
+            ("vn", "iê"),
+            ("vm", "uô"),
+            ("cvb", "ia"),
+            ("cvn", "oe"),
+            ("cvm", "ua"),
+            ("cbn", "ươ"),
+            ("cbm", "ưa"),
+            ("cnm", "uy"),
+            ("vbn", "yê"),
+            ("vbm", "uê"),
+            ("vnm", "oă"),
+            ("bnm", "uâ"),
+        ];
+        const FINALS: [(&str, &str); 13] = [
+            ("", ""),
+            ("u", "n"),
+            ("i", "ng"),
+            ("o", "t"),
+            ("p", "c"),
+            ("ui", "m"),
+            ("io", "nh"),
+            ("op", "ch"),
+            ("up", "p"),
+            ("uo", "i"),
+            ("ip", "u"),
+            ("uop", "o"),
+            ("uip", "y"),
+        ];
+
+        let mut theory = Self::new("qwerasdf", "cvbnmuiop", "jkl;'")
+            .with_tone("j", ToneIndex::Grave)
+            .with_tone("k", ToneIndex::Acute)
+            .with_tone("l", ToneIndex::Hook)
+            .with_tone(";", ToneIndex::Tilde)
+            .with_tone("'", ToneIndex::Dot);
+
+        for (chord, onset) in ONSETS {
+            theory = theory.with_onset(chord, onset);
+        }
+        for (nucleus_chord, nucleus) in NUCLEI {
+            for (final_chord, final_part) in FINALS {
+                theory = theory.with_rhyme(
+                    &format!("{}{}", nucleus_chord, final_chord),
+                    &format!("{}{}", nucleus, final_part),
+                );
+            }
+        }
+        theory
+    }
+
+    /// Onset chords and the onsets they produce
+    pub fn onsets(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.onsets
+            .iter()
+            .map(|(chord, onset)| (chord.as_str(), onset.as_str()))
+    }
+
+    /// Rhyme chords and the rhymes they produce
+    pub fn rhymes(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.rhymes
+            .iter()
+            .map(|(chord, rhyme)| (chord.as_str(), rhyme.as_str()))
+    }
+
+    /// Tone chords and their tones
+    pub fn tones(&self) -> impl Iterator<Item = (&str, ToneIndex)> {
+        self.tones
+            .iter()
+            .map(|(chord, &tone)| (chord.as_str(), tone))
+    }
+
+    /// Keys of `keys` that belong to `group`, in the group's canonical order
+    fn group_chord(group: &[char], keys: &[char]) -> String {
+        group.iter().filter(|k| keys.contains(k)).collect()
+    }
+
+    /// Canonical text form of a stroke (used as dictionary key)
+    ///
+    /// Keys are ordered onset, rhyme, tone; keys outside every group
+    /// follow in their natural order.
+    pub fn stroke(&self, keys: &[char]) -> String {
+        let mut stroke = Self::group_chord(&self.onset_keys, keys);
+        stroke.push_str(&Self::group_chord(&self.rhyme_keys, keys));
+        stroke.push_str(&Self::group_chord(&self.tone_keys, keys));
+
+        let mut others: Vec<char> = keys
+            .iter()
+            .copied()
+            .filter(|k| {
+                !self.onset_keys.contains(k)
+                    && !self.rhyme_keys.contains(k)
+                    && !self.tone_keys.contains(k)
+            })
+            .collect();
+        others.sort_unstable();
+        others.dedup();
+        stroke.extend(others);
+        stroke
+    }
+
+    /// Translate a stroke into a syllable using the theory tables
+    ///
+    /// Returns None if the stroke has keys outside the theory, no rhyme, or
+    /// a group chord missing from the tables.
+    pub fn syllable(&self, keys: &[char]) -> Option<String> {
+        let all_keys_known = keys.iter().all(|k| {
+            self.onset_keys.contains(k) || self.rhyme_keys.contains(k) || self.tone_keys.contains(k)
+        });
+        if !all_keys_known {
+            return None;
+        }
+
+        let onset_chord = Self::group_chord(&self.onset_keys, keys);
+        let onset = if onset_chord.is_empty() {
+            ""
+        } else {
+            self.onsets.get(&onset_chord)?.as_str()
+        };
+
+        let rhyme = self
+            .rhymes
+            .get(&Self::group_chord(&self.rhyme_keys, keys))?;
+
+        let tone_chord = Self::group_chord(&self.tone_keys, keys);
+        let tone = if tone_chord.is_empty() {
+            ToneIndex::None
+        } else {
+            *self.tones.get(&tone_chord)?
+        };
+
+        // Placed on the whole syllable: a g onset + ia rhyme spells gi + a
+        let mut syllable: Vec<char> = onset.chars().chain(rhyme.chars()).collect();
+        if let Some(pos) = VietnameseChar::find_tone_position(&syllable) {
+            syllable[pos] = VietnameseChar::apply_tone(syllable[pos], tone);
+        }
+        Some(syllable.into_iter().collect())
+    }
+}
+
+impl Default for ChordTheory {
+    fn default() -> Self {
+        Self::basic()
+    }
+}
+
+/// Chord (stenographic) input engine
+pub struct ChordEngine {
+    theory: ChordTheory,
+    briefs: HashMap<String, String>,
+    buffer: Vec<char>,
+    /// Length in chars of each stroke's output, most recent last
+    history: Vec<usize>,
+}
+
+impl ChordEngine {
+    pub fn new() -> Self {
+        Self::with_theory(ChordTheory::basic())
+    }
+
+    /// Create an engine using the given theory
+    pub fn with_theory(theory: ChordTheory) -> Self {
+        Self {
+            theory,
+            briefs: HashMap::new(),
+            buffer: Vec::new(),
+            history: Vec::with_capacity(MAX_UNDO_STROKES),
+        }
+    }
+
+    /// Add a brief: a stroke that produces the given (multi-syllable) text
+    pub fn with_brief(mut self, chord: &str, text: &str) -> Self {
+        self.add_brief(chord, text);
+        self
+    }
+
+    /// Add a brief: a stroke that produces the given (multi-syllable) text
+    pub fn add_brief(&mut self, chord: &str, text: &str) {
+        let keys: Vec<char> = chord.chars().collect();
+        self.briefs
+            .insert(self.theory.stroke(&keys), text.to_string());
+    }
+
+    /// Theory used to translate strokes
+    pub fn theory(&self) -> &ChordTheory {
+        &self.theory
+    }
+
+    fn current_state(&self) -> String {
+        self.buffer.iter().collect()
+    }
+
+    fn translate(&self, keys: &[char], stroke: &str) -> String {
+        if let Some(text) = self.briefs.get(stroke) {
+            return text.clone();
+        }
+        // Untranslatable strokes are written out raw so the misstroke is visible
+        self.theory
+            .syllable(keys)
+            .unwrap_or_else(|| stroke.to_string())
+    }
+
+    /// Result for a change of the translated text from `old` to the current buffer
+    ///
+    /// Strokes are always consumed, even when nothing changes on screen.
+    fn edit_result(&self, old: &[char]) -> ProcessKeyResult {
+        let (backspace_count, text) = minimal_edit(old, &self.buffer);
+        ProcessKeyResult {
+            handled: true,
+            output_text: if text.is_empty() { None } else { Some(text) },
+            backspace_count,
+            kept_suffix: kept_suffix(old, &self.buffer),
+            current_buffer: self.current_state(),
+        }
+    }
+
+    fn undo_stroke(&mut self) -> ProcessKeyResult {
+        let old = self.buffer.clone();
+        let removed = self.history.pop().unwrap_or(0);
+        self.buffer.truncate(self.buffer.len() - removed);
+        self.edit_result(&old)
+    }
+}
+
+impl Default for ChordEngine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl InputEngine for ChordEngine {
+    fn name(&self) -> &str {
+        "Chord"
+    }
+
+    fn process_key(&mut self, _key: char, _is_shift_pressed: bool) -> ProcessKeyResult {
+        // Single keys are not strokes; let them through and start over
+        self.reset();
+        ProcessKeyResult::pass_through(String::new())
+    }
+
+    fn process_chord(&mut self, keys: &[char]) -> ProcessKeyResult {
+        let stroke = self.theory.stroke(keys);
+        if stroke.is_empty() {
+            return ProcessKeyResult::pass_through(self.current_state());
+        }
+
+        if keys.iter().all(|&k| k == self.theory.undo_key) {
+            return self.undo_stroke();
+        }
+
+        let mut output = self.translate(keys, &stroke);
+        if !self.buffer.is_empty() {
+            output.insert(0, ' ');
+        }
+
+        if self.history.len() == MAX_UNDO_STROKES {
+            let oldest = self.history.remove(0);
+            self.buffer.drain(..oldest);
+        }
+        let old = self.buffer.clone();
+        self.history.push(output.chars().count());
+        self.buffer.extend(output.chars());
+
+        self.edit_result(&old)
+    }
+
+    fn reset(&mut self) {
+        self.buffer.clear();
+        self.history.clear();
+    }
+
+    fn process_backspace(&mut self) -> bool {
+        if self.buffer.pop().is_some() {
+            if let Some(last) = self.history.last_mut() {
+                *last -= 1;
+                if *last == 0 {
+                    self.history.pop();
+                }
+            }
+        }
+        false
+    }
+
+    fn get_buffer(&self) -> String {
+        self.current_state()
+    }
+
+    fn get_buffer_slice(&self) -> &[char] {
+        &self.buffer
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_engine_name() {
+        let engine = ChordEngine::new();
+        assert_eq!(engine.name(), "Chord");
+    }
+
+    #[test]
+    fn test_basic_theory_chords_are_unique() {
+        let theory = ChordTheory::basic();
+        assert_eq!(theory.onsets.len(), 27);
+        assert_eq!(theory.rhymes.len(), 25 * 13);
+        assert_eq!(theory.tones.len(), 5);
+    }
+}
@@ -0,0 +1,86 @@
+//! # Minimal Diff Output
+//!
+//! Shared helper for turning a change in an engine's rendering into the
+//! fewest synthetic backspaces. Only the suffix after the longest common
+//! prefix is rewritten; every extra backspace is another chance for the
+//! host application to drop or reorder input.
+
+use crate::engines::ProcessKeyResult;
+
+/// Minimal edit turning `old` into `new`
+///
+/// Returns the number of characters to delete from the end of `old` and
+/// the text to append afterwards.
+///
+/// # Examples
+///
+/// ```
+/// use vietime_core::engines::diff::minimal_edit;
+///
+/// let old: Vec<char> = "tiêng".chars().collect();
+/// let new: Vec<char> = "tiếng".chars().collect();
+/// assert_eq!(minimal_edit(&old, &new), (3, "ếng".to_string()));
+///
+/// let old: Vec<char> = "thế".chars().collect();
+/// let new: Vec<char> = "thế".chars().collect();
+/// assert_eq!(minimal_edit(&old, &new), (0, String::new()));
+/// ```
+pub fn minimal_edit(old: &[char], new: &[char]) -> (usize, String) {
+    let common = old
+        .iter()
+        .zip(new.iter())
+        .take_while(|(a, b)| a == b)
+        .count();
+    (old.len() - common, new[common..].iter().collect())
+}
+
+/// Characters at the end of the [`minimal_edit`] rewrite that are the same
+/// before and after (tiêng -> tiếng keeps "ng")
+pub(crate) fn kept_suffix(old: &[char], new: &[char]) -> usize {
+    let common = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+    old[common..]
+        .iter()
+        .rev()
+        .zip(new[common..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count()
+}
+
+impl ProcessKeyResult {
+    /// Result for a keystroke that changed an engine's rendering from `old` to `new`
+    ///
+    /// If the rendering is unchanged, or `new` is just `old` followed by
+    /// `key`, the key passes through. Otherwise only the changed suffix is
+    /// replaced.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use vietime_core::engines::ProcessKeyResult;
+    ///
+    /// let old: Vec<char> = "tha".chars().collect();
+    /// let new: Vec<char> = "thá".chars().collect();
+    /// let result = ProcessKeyResult::from_edit(&old, &new, 's');
+    ///
+    /// assert_eq!(result.backspace_count, 1);
+    /// assert_eq!(result.output_text, Some("á".to_string()));
+    /// assert_eq!(result.kept_suffix, 0);
+    /// ```
+    pub fn from_edit(old: &[char], new: &[char], key: char) -> Self {
+        let buffer: String = new.iter().collect();
+        let appended_key =
+            new.len() == old.len() + 1 && new.starts_with(old) && new[old.len()] == key;
+        if old == new || appended_key {
+            return Self::pass_through(buffer);
+        }
+
+        let (backspace_count, text) = minimal_edit(old, new);
+        Self {
+            handled: true,
+            output_text: if text.is_empty() { None } else { Some(text) },
+            backspace_count,
+            kept_suffix: kept_suffix(old, new),
+            current_buffer: buffer,
+        }
+    }
+}
@@ -0,0 +1,28 @@
+//! # Engine Events
+//!
+//! Non-key events reported by the platform layer (focus changes, caret
+//! movement, idle time). Engines handle them through
+//! [`InputEngine::handle_event`](crate::engines::InputEngine::handle_event)
+//! instead of the platform guessing when to call `reset()`.
+
+use std::time::Duration;
+
+/// Event outside the keystroke stream that may invalidate the buffer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EngineEvent {
+    /// The text field lost keyboard focus
+    FocusLost,
+
+    /// A text field gained focus, with an identifier of the owning application
+    /// (executable name, bundle id, ...)
+    FocusGained { app_id: String },
+
+    /// The caret moved without typing (click, navigation by the application)
+    CursorMoved,
+
+    /// The selection in the text field changed
+    SelectionChanged,
+
+    /// No keystroke has occurred for the given duration
+    Idle(Duration),
+}
@@ -0,0 +1,172 @@
+//! # Key Events
+//!
+//! Full keystroke description passed from platform hooks to engines via
+//! [`InputEngine::process_key_event`](crate::engines::InputEngine::process_key_event).
+//!
+//! Unlike the plain `(char, shift)` pair used by `process_key`, a
+//! [`KeyEvent`] can tell `Ctrl+S` from `s`, carries navigation keys and
+//! key-up events, so engines can decide for themselves when the buffer
+//! must be reset.
+
+/// Modifier key state at the time of the event
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Modifiers {
+    pub shift: bool,
+    pub ctrl: bool,
+    pub alt: bool,
+    /// Windows / Command / Super key
+    pub meta: bool,
+    pub caps_lock: bool,
+}
+
+impl Modifiers {
+    /// No modifiers held, CapsLock off
+    pub const NONE: Self = Self {
+        shift: false,
+        ctrl: false,
+        alt: false,
+        meta: false,
+        caps_lock: false,
+    };
+
+    /// Only Shift held
+    pub const SHIFT: Self = Self {
+        shift: true,
+        ..Self::NONE
+    };
+
+    /// Only Ctrl held
+    pub const CTRL: Self = Self {
+        ctrl: true,
+        ..Self::NONE
+    };
+
+    /// Check if a command modifier (Ctrl, Alt or Meta) is held
+    ///
+    /// Keys pressed with a command modifier are shortcuts, not text.
+    pub fn is_command(self) -> bool {
+        self.ctrl || self.alt || self.meta
+    }
+}
+
+/// Which key was pressed, independent of keyboard layout
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyCode {
+    /// A key that produces text (letters, digits, punctuation, space)
+    Char,
+    Backspace,
+    Delete,
+    Enter,
+    Tab,
+    Escape,
+    Left,
+    Right,
+    Up,
+    Down,
+    Home,
+    End,
+    PageUp,
+    PageDown,
+    Insert,
+    /// F1-F24
+    Function(u8),
+    /// Shift, Ctrl, Alt, Meta or CapsLock pressed on its own
+    Modifier,
+    /// Mouse button reported through the keyboard hook
+    Mouse,
+    /// Any other key, with its platform virtual key code
+    Other(u32),
+}
+
+/// Whether the key went down or up
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum KeyState {
+    #[default]
+    Pressed,
+    Released,
+}
+
+/// A single keyboard event
+///
+/// # Example
+///
+/// ```
+/// use vietime_core::engines::{KeyCode, KeyEvent, Modifiers};
+///
+/// let save = KeyEvent::char('s').with_modifiers(Modifiers::CTRL);
+/// assert!(save.modifiers.is_command());
+///
+/// let left = KeyEvent::new(KeyCode::Left);
+/// assert_eq!(left.key, None);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyEvent {
+    /// Logical character produced by the key (after layout, Shift and CapsLock)
+    pub key: Option<char>,
+
+    /// Which key was pressed
+    pub code: KeyCode,
+
+    /// Platform physical scan code (0 if unknown)
+    pub scan_code: u32,
+
+    /// Modifier state
+    pub modifiers: Modifiers,
+
+    /// true if generated by key auto-repeat
+    pub repeat: bool,
+
+    /// Press or release
+    pub state: KeyState,
+}
+
+impl KeyEvent {
+    /// Create a key press with no character
+    pub fn new(code: KeyCode) -> Self {
+        Self {
+            key: None,
+            code,
+            scan_code: 0,
+            modifiers: Modifiers::NONE,
+            repeat: false,
+            state: KeyState::Pressed,
+        }
+    }
+
+    /// Create a key press for a text-producing key
+    pub fn char(key: char) -> Self {
+        Self {
+            key: Some(key),
+            ..Self::new(KeyCode::Char)
+        }
+    }
+
+    /// Set the modifier state
+    pub fn with_modifiers(mut self, modifiers: Modifiers) -> Self {
+        self.modifiers = modifiers;
+        self
+    }
+
+    /// Set the platform scan code
+    pub fn with_scan_code(mut self, scan_code: u32) -> Self {
+        self.scan_code = scan_code;
+        self
+    }
+
+    /// Mark the event as an auto-repeat
+    pub fn repeated(mut self) -> Self {
+        self.repeat = true;
+        self
+    }
+
+    /// Turn the event into a key release
+    pub fn released(mut self) -> Self {
+        self.state = KeyState::Released;
+        self
+    }
+
+    /// Check if this is a key press (including auto-repeat)
+    pub fn is_press(&self) -> bool {
+        self.state == KeyState::Pressed
+    }
+}
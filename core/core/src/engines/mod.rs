@@ -1,13 +1,28 @@
 //! # Input Engine Module
 //!
 //! Defines the [`InputEngine`] trait and implements various input methods
-//! for Vietnamese text entry (Telex, VNI).
-
+//! for Vietnamese text entry (Telex, VNI, multi-tap, chord).
+
+pub mod actions;
+pub mod charset;
+pub mod chord;
+pub mod diff;
+pub mod event;
+pub mod key_event;
+pub mod multitap;
+pub mod preedit;
+pub mod strategy;
 pub mod telex;
 
 // pub mod vni; // Future story
 
 use std::fmt;
+use std::time::Duration;
+
+use crate::BUFFER_TIMEOUT_MS;
+
+pub use event::EngineEvent;
+pub use key_event::{KeyCode, KeyEvent, KeyState, Modifiers};
 
 /// Result of processing a keystroke
 ///
@@ -28,6 +43,10 @@ pub struct ProcessKeyResult {
     /// Number of backspaces to send before output_text
     pub backspace_count: usize,
 
+    /// Characters at the end of output_text that were already on screen
+    /// (tiêng -> tiếng keeps "ng"); hosts able to move the caret may skip them
+    pub kept_suffix: usize,
+
     /// Buffer state after processing
     pub current_buffer: String,
 }
@@ -39,6 +58,7 @@ impl ProcessKeyResult {
             handled: false,
             output_text: None,
             backspace_count: 0,
+            kept_suffix: 0,
             current_buffer: String::new(),
         }
     }
@@ -49,6 +69,7 @@ impl ProcessKeyResult {
             handled: false,
             output_text: None,
             backspace_count: 0,
+            kept_suffix: 0,
             current_buffer: buffer,
         }
     }
@@ -59,6 +80,7 @@ impl ProcessKeyResult {
             handled: true,
             output_text: Some(output),
             backspace_count: backspaces,
+            kept_suffix: 0,
             current_buffer: buffer,
         }
     }
@@ -119,9 +141,86 @@ pub trait InputEngine: Send + Sync {
     /// A [`ProcessKeyResult`] indicating what should be done
     fn process_key(&mut self, key: char, is_shift_pressed: bool) -> ProcessKeyResult;
 
+    /// Process a full key event
+    ///
+    /// The default implementation decides which events affect the word
+    /// being composed:
+    ///
+    /// - Key releases and lone modifier keys are ignored
+    /// - Shortcuts (Ctrl/Alt/Meta + key), navigation keys, Enter, Escape,
+    ///   Delete, mouse buttons and unknown keys reset the buffer and pass through
+    /// - Backspace goes to [`process_backspace`](Self::process_backspace)
+    /// - Text keys report CapsLock to [`set_caps_lock`](Self::set_caps_lock),
+    ///   then go to [`process_key`](Self::process_key)
+    fn process_key_event(&mut self, event: &KeyEvent) -> ProcessKeyResult {
+        if !event.is_press() || event.code == KeyCode::Modifier {
+            return ProcessKeyResult::pass_through(self.get_buffer());
+        }
+
+        if event.modifiers.is_command() {
+            self.reset();
+            return ProcessKeyResult::pass_through(self.get_buffer());
+        }
+
+        match (event.code, event.key) {
+            (KeyCode::Backspace, _) => {
+                let handled = self.process_backspace();
+                ProcessKeyResult {
+                    handled,
+                    ..ProcessKeyResult::pass_through(self.get_buffer())
+                }
+            }
+            (KeyCode::Char, Some(key)) => {
+                self.set_caps_lock(event.modifiers.caps_lock);
+                self.process_key(key, event.modifiers.shift)
+            }
+            _ => {
+                self.reset();
+                ProcessKeyResult::pass_through(self.get_buffer())
+            }
+        }
+    }
+
+    /// Record the CapsLock state of the key about to be processed
+    ///
+    /// `process_key` only knows about Shift; engines that keep the case of
+    /// a word take CapsLock from here. The default ignores it.
+    fn set_caps_lock(&mut self, _caps_lock: bool) {}
+
+    /// Process a chord: a set of keys pressed together and released as one stroke
+    ///
+    /// Used by stenographic engines. Engines that work key by key let the
+    /// chord through unchanged.
+    ///
+    /// # Arguments
+    ///
+    /// * `keys` - All keys that were down during the stroke, in any order
+    fn process_chord(&mut self, _keys: &[char]) -> ProcessKeyResult {
+        ProcessKeyResult::pass_through(self.get_buffer())
+    }
+
     /// Reset the internal buffer (called on context switch, space, enter, etc.)
     fn reset(&mut self);
 
+    /// Handle a non-key event from the platform layer
+    ///
+    /// The default implementation resets the buffer on focus, caret and
+    /// selection changes, and on idle periods of at least
+    /// [`BUFFER_TIMEOUT_MS`].
+    fn handle_event(&mut self, event: &EngineEvent) {
+        match event {
+            EngineEvent::Idle(duration) => {
+                if *duration >= Duration::from_millis(BUFFER_TIMEOUT_MS) {
+                    self.reset();
+                }
+            }
+            EngineEvent::FocusLost
+            | EngineEvent::FocusGained { .. }
+            | EngineEvent::CursorMoved
+            | EngineEvent::SelectionChanged => self.reset(),
+        }
+    }
+
     /// Process backspace key
     ///
     /// # Returns
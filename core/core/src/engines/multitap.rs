@@ -0,0 +1,243 @@
+//! # Multi-tap Input Engine
+//!
+//! T9-style Vietnamese input for numeric keypads (kiosks, POS terminals).
+//!
+//! Each digit cycles through a group of letters, including the Vietnamese
+//! diacritic variants. Pressing the same digit again within the cycle
+//! timeout replaces the pending letter; a different digit (or the timeout
+//! expiring) commits it.
+//!
+//! | Key | Letters       |
+//! |-----|---------------|
+//! | `1` | cycle tone on the current word |
+//! | `2` | a ă â b c     |
+//! | `3` | d đ e ê f     |
+//! | `4` | g h i         |
+//! | `5` | j k l         |
+//! | `6` | m n o ô ơ     |
+//! | `7` | p q r s       |
+//! | `8` | t u ư v       |
+//! | `9` | w x y z       |
+//! | `0` | space (ends the word) |
+
+use std::sync::Arc;
+
+use crate::clock::{Clock, SystemClock};
+use crate::engines::{InputEngine, ProcessKeyResult};
+use crate::models::vietnamese::{ToneIndex, VietnameseChar};
+use crate::MAX_BUFFER_SIZE;
+
+/// Default time window (ms) in which repeated taps cycle the same letter
+pub const MULTI_TAP_TIMEOUT_MS: u64 = 1000;
+
+/// Letters cycled by a digit key, or None if the key is not a letter key
+///
+/// Hosts can use it to label an on-screen keypad.
+///
+/// # Example
+///
+/// ```
+/// use vietime_core::engines::multitap::letters_for_key;
+///
+/// assert_eq!(letters_for_key('8'), Some(&['t', 'u', 'ư', 'v'][..]));
+/// assert_eq!(letters_for_key('1'), None);
+/// ```
+pub fn letters_for_key(key: char) -> Option<&'static [char]> {
+    match key {
+        '2' => Some(&['a', 'ă', 'â', 'b', 'c']),
+        '3' => Some(&['d', 'đ', 'e', 'ê', 'f']),
+        '4' => Some(&['g', 'h', 'i']),
+        '5' => Some(&['j', 'k', 'l']),
+        '6' => Some(&['m', 'n', 'o', 'ô', 'ơ']),
+        '7' => Some(&['p', 'q', 'r', 's']),
+        '8' => Some(&['t', 'u', 'ư', 'v']),
+        '9' => Some(&['w', 'x', 'y', 'z']),
+        _ => None,
+    }
+}
+
+/// Letter currently being cycled
+#[derive(Debug, Clone, Copy)]
+struct PendingTap {
+    key: char,
+    index: usize,
+    uppercase: bool,
+    last_tap_ms: u64,
+}
+
+/// Multi-tap input engine
+pub struct MultiTapEngine {
+    buffer: Vec<char>,
+    pending: Option<PendingTap>,
+    clock: Arc<dyn Clock>,
+    timeout_ms: u64,
+}
+
+impl MultiTapEngine {
+    pub fn new() -> Self {
+        Self::with_clock(Arc::new(SystemClock::new()))
+    }
+
+    /// Create an engine driven by the given clock
+    pub fn with_clock(clock: Arc<dyn Clock>) -> Self {
+        Self {
+            buffer: Vec::with_capacity(MAX_BUFFER_SIZE),
+            pending: None,
+            clock,
+            timeout_ms: MULTI_TAP_TIMEOUT_MS,
+        }
+    }
+
+    /// Set the cycle timeout in milliseconds
+    pub fn with_timeout(mut self, timeout_ms: u64) -> Self {
+        self.timeout_ms = timeout_ms;
+        self
+    }
+
+    fn current_state(&self) -> String {
+        self.buffer.iter().collect()
+    }
+
+    fn letter(letters: &[char], index: usize, uppercase: bool) -> char {
+        let c = letters[index];
+        if uppercase {
+            VietnameseChar::to_upper(c)
+        } else {
+            c
+        }
+    }
+
+    fn process_letter_key(
+        &mut self,
+        key: char,
+        letters: &[char],
+        uppercase: bool,
+    ) -> ProcessKeyResult {
+        let now = self.clock.now_ms();
+        let old = self.buffer.clone();
+
+        // Same key within the timeout: cycle the pending letter in place
+        if let Some(pending) = self.pending.as_mut() {
+            if pending.key == key && now.saturating_sub(pending.last_tap_ms) < self.timeout_ms {
+                pending.index = (pending.index + 1) % letters.len();
+                pending.last_tap_ms = now;
+                let c = Self::letter(letters, pending.index, pending.uppercase);
+                self.buffer.pop();
+                self.buffer.push(c);
+                return ProcessKeyResult::from_edit(&old, &self.buffer, key);
+            }
+        }
+
+        // Otherwise the pending letter is committed and a new one starts
+        let old = if self.buffer.len() >= MAX_BUFFER_SIZE {
+            self.buffer.clear();
+            Vec::new()
+        } else {
+            old
+        };
+        let c = Self::letter(letters, 0, uppercase);
+        self.buffer.push(c);
+        self.pending = Some(PendingTap {
+            key,
+            index: 0,
+            uppercase,
+            last_tap_ms: now,
+        });
+        ProcessKeyResult::from_edit(&old, &self.buffer, key)
+    }
+
+    fn process_tone_key(&mut self) -> ProcessKeyResult {
+        self.pending = None;
+
+        let Some(vowel_pos) = VietnameseChar::find_tone_position(&self.buffer) else {
+            // Nothing to put a tone on; swallow the key
+            return ProcessKeyResult {
+                handled: true,
+                output_text: None,
+                backspace_count: 0,
+                kept_suffix: 0,
+                current_buffer: self.current_state(),
+            };
+        };
+
+        let old_vowel = self.buffer[vowel_pos];
+        let tone = VietnameseChar::get_tone_index(old_vowel);
+        let next_tone = ToneIndex::ALL[(tone as usize + 1) % ToneIndex::ALL.len()];
+
+        let old = self.buffer.clone();
+        self.buffer[vowel_pos] = VietnameseChar::apply_tone(old_vowel, next_tone);
+
+        ProcessKeyResult::from_edit(&old, &self.buffer, '1')
+    }
+}
+
+impl Default for MultiTapEngine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl InputEngine for MultiTapEngine {
+    fn name(&self) -> &str {
+        "MultiTap"
+    }
+
+    fn process_key(&mut self, key: char, is_shift_pressed: bool) -> ProcessKeyResult {
+        if let Some(letters) = letters_for_key(key) {
+            return self.process_letter_key(key, letters, is_shift_pressed);
+        }
+
+        match key {
+            '1' => self.process_tone_key(),
+            '0' => {
+                self.reset();
+                ProcessKeyResult::replace(0, " ".to_string(), String::new())
+            }
+            _ => {
+                self.reset();
+                ProcessKeyResult::pass_through(String::new())
+            }
+        }
+    }
+
+    fn reset(&mut self) {
+        self.buffer.clear();
+        self.pending = None;
+    }
+
+    fn process_backspace(&mut self) -> bool {
+        self.pending = None;
+        self.buffer.pop();
+        false
+    }
+
+    fn get_buffer(&self) -> String {
+        self.current_state()
+    }
+
+    fn get_buffer_slice(&self) -> &[char] {
+        &self.buffer
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_engine_name() {
+        let engine = MultiTapEngine::new();
+        assert_eq!(engine.name(), "MultiTap");
+    }
+
+    #[test]
+    fn test_letter_key_groups() {
+        for key in '2'..='9' {
+            let letters = letters_for_key(key).unwrap();
+            assert!(!letters.is_empty());
+            assert!(letters.iter().all(|c| c.is_lowercase()));
+        }
+        assert!(letters_for_key('1').is_none());
+        assert!(letters_for_key('0').is_none());
+    }
+}
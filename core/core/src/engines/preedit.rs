@@ -0,0 +1,192 @@
+//! # Preedit Output Mode
+//!
+//! Drives any [`InputEngine`] for hosts with a composition (preedit) API —
+//! IBus, Fcitx, Wayland input-method, XIM — instead of sending synthetic
+//! backspaces.
+//!
+//! The word being composed is reported as a [`PreeditEvent::Preedit`]
+//! string; text the engine is done with is reported as
+//! [`PreeditEvent::Commit`]. The engine itself runs unchanged: the session
+//! replays its backspace/replace results into a private copy of the
+//! composing text, whose trailing part equal to the engine buffer is the
+//! preedit and whose leading part has been committed.
+
+use crate::engines::{InputEngine, ProcessKeyResult};
+
+/// How a preedit segment should be drawn
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SegmentStyle {
+    /// Regular composing text (usually underlined)
+    Underline,
+}
+
+/// Styled range of the preedit string, in chars
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PreeditSegment {
+    pub start: usize,
+    pub end: usize,
+    pub style: SegmentStyle,
+}
+
+/// Event sent to a preedit-capable host
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PreeditEvent {
+    /// Replace the current preedit (an empty `text` clears it)
+    Preedit {
+        text: String,
+        /// Caret position within `text`, in chars
+        cursor: usize,
+        segments: Vec<PreeditSegment>,
+    },
+
+    /// Insert final text at the caret
+    Commit(String),
+}
+
+/// Preedit-mode wrapper around an input engine
+///
+/// # Example
+///
+/// ```
+/// use vietime_core::engines::preedit::{PreeditEvent, PreeditSession};
+/// use vietime_core::engines::telex::TelexEngine;
+///
+/// let mut session = PreeditSession::new(TelexEngine::new());
+/// for key in "thees".chars() {
+///     session.process_key(key, false);
+/// }
+/// assert_eq!(session.preedit(), "thế");
+///
+/// let events = session.process_key(' ', false);
+/// assert_eq!(events[0], PreeditEvent::Commit("thế ".to_string()));
+/// ```
+pub struct PreeditSession<E: InputEngine> {
+    engine: E,
+    /// Text the engine has emitted since the last commit
+    composing: Vec<char>,
+}
+
+impl<E: InputEngine> PreeditSession<E> {
+    pub fn new(engine: E) -> Self {
+        Self {
+            engine,
+            composing: Vec::new(),
+        }
+    }
+
+    /// Wrapped engine
+    pub fn engine(&self) -> &E {
+        &self.engine
+    }
+
+    /// Wrapped engine (mutable)
+    pub fn engine_mut(&mut self) -> &mut E {
+        &mut self.engine
+    }
+
+    /// Current preedit text
+    pub fn preedit(&self) -> String {
+        self.composing.iter().collect()
+    }
+
+    /// Process a keystroke
+    ///
+    /// Every key is consumed: it ends up either in the preedit or in a commit.
+    pub fn process_key(&mut self, key: char, is_shift_pressed: bool) -> Vec<PreeditEvent> {
+        let result = self.engine.process_key(key, is_shift_pressed);
+        self.apply(&result, key);
+        self.sync()
+    }
+
+    /// Process a chord (see [`InputEngine::process_chord`])
+    pub fn process_chord(&mut self, keys: &[char]) -> Vec<PreeditEvent> {
+        let result = self.engine.process_chord(keys);
+        if result.handled {
+            self.apply_replace(&result);
+        } else {
+            self.composing.extend(keys);
+        }
+        self.sync()
+    }
+
+    /// Process backspace
+    ///
+    /// Returns no events if there is no preedit, in which case the host
+    /// should delete before the caret itself.
+    pub fn process_backspace(&mut self) -> Vec<PreeditEvent> {
+        if self.composing.is_empty() {
+            self.engine.process_backspace();
+            return Vec::new();
+        }
+
+        self.engine.process_backspace();
+        self.composing.pop();
+        self.sync()
+    }
+
+    /// Commit the preedit as is and reset the engine (focus out, Enter, ...)
+    pub fn commit(&mut self) -> Vec<PreeditEvent> {
+        self.engine.reset();
+        let mut events = Vec::new();
+        if !self.composing.is_empty() {
+            events.push(PreeditEvent::Commit(self.composing.drain(..).collect()));
+        }
+        events.push(Self::preedit_event(&[]));
+        events
+    }
+
+    fn apply(&mut self, result: &ProcessKeyResult, key: char) {
+        if result.handled {
+            self.apply_replace(result);
+        } else {
+            self.composing.push(key);
+        }
+    }
+
+    fn apply_replace(&mut self, result: &ProcessKeyResult) {
+        let keep = self.composing.len().saturating_sub(result.backspace_count);
+        self.composing.truncate(keep);
+        if let Some(text) = &result.output_text {
+            self.composing.extend(text.chars());
+        }
+    }
+
+    /// Commit everything before the engine buffer and report the preedit
+    fn sync(&mut self) -> Vec<PreeditEvent> {
+        let buffer: Vec<char> = self.engine.get_buffer().chars().collect();
+        let mut events = Vec::new();
+
+        let committed_len = if self.composing.ends_with(&buffer) {
+            self.composing.len() - buffer.len()
+        } else {
+            // Engine output diverged from its buffer; keep what was emitted
+            self.composing.len()
+        };
+
+        if committed_len > 0 {
+            events.push(PreeditEvent::Commit(
+                self.composing.drain(..committed_len).collect(),
+            ));
+        }
+
+        events.push(Self::preedit_event(&self.composing));
+        events
+    }
+
+    fn preedit_event(text: &[char]) -> PreeditEvent {
+        let segments = if text.is_empty() {
+            Vec::new()
+        } else {
+            vec![PreeditSegment {
+                start: 0,
+                end: text.len(),
+                style: SegmentStyle::Underline,
+            }]
+        };
+        PreeditEvent::Preedit {
+            text: text.iter().collect(),
+            cursor: text.len(),
+            segments,
+        }
+    }
+}
@@ -0,0 +1,146 @@
+//! # Output Strategies
+//!
+//! Per-application tweaks to how edit actions are generated.
+//!
+//! Browser address bars (Chrome, Edge) and Google Docs show an inline,
+//! selected autocomplete suggestion after each typed character. The first
+//! synthetic Backspace then deletes the suggestion instead of our
+//! character, so "việt" turns into "vit". The usual workaround is to type a
+//! dummy character first: it replaces the suggestion, matches no
+//! completion, and is deleted together with the characters being replaced.
+
+use crate::engines::EngineEvent;
+
+/// How deletions are emitted
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OutputStrategy {
+    /// Delete and insert directly
+    #[default]
+    Direct,
+
+    /// Type the given dummy character before deleting, and delete it too
+    DismissSuggestion(char),
+}
+
+impl OutputStrategy {
+    /// Dismiss suggestions with an invisible zero-width space
+    pub const ZERO_WIDTH: Self = Self::DismissSuggestion('\u{200B}');
+
+    /// Dismiss suggestions with a space (for fields that drop zero-width characters)
+    pub const SPACE: Self = Self::DismissSuggestion(' ');
+}
+
+/// Application ids that get [`OutputStrategy::ZERO_WIDTH`] by default
+///
+/// Windows executable names, macOS bundle ids and Linux desktop ids of
+/// browsers with inline autocomplete.
+pub const AUTOCOMPLETE_APPS: &[&str] = &[
+    "chrome.exe",
+    "msedge.exe",
+    "brave.exe",
+    "com.google.chrome",
+    "com.microsoft.edgemac",
+    "com.brave.browser",
+    "google-chrome",
+    "microsoft-edge",
+    "brave-browser",
+];
+
+/// Chooses the output strategy for the focused application
+///
+/// The host reports focus changes with the application id; lookups are
+/// case-insensitive.
+///
+/// # Example
+///
+/// ```
+/// use vietime_core::engines::strategy::{OutputStrategy, StrategySelector};
+/// use vietime_core::EngineEvent;
+///
+/// let mut selector = StrategySelector::new();
+/// selector.handle_event(&EngineEvent::FocusGained { app_id: "Chrome.exe".to_string() });
+/// assert_eq!(selector.strategy(), OutputStrategy::ZERO_WIDTH);
+///
+/// selector.handle_event(&EngineEvent::FocusGained { app_id: "notepad.exe".to_string() });
+/// assert_eq!(selector.strategy(), OutputStrategy::Direct);
+/// ```
+#[derive(Debug, Clone)]
+pub struct StrategySelector {
+    default: OutputStrategy,
+    apps: Vec<(String, OutputStrategy)>,
+    current: Option<String>,
+}
+
+impl StrategySelector {
+    /// Selector with the built-in browser list ([`AUTOCOMPLETE_APPS`])
+    pub fn new() -> Self {
+        let mut selector = Self::empty();
+        for app in AUTOCOMPLETE_APPS {
+            selector.set_app(app, OutputStrategy::ZERO_WIDTH);
+        }
+        selector
+    }
+
+    /// Selector without any per-application entries
+    pub fn empty() -> Self {
+        Self {
+            default: OutputStrategy::Direct,
+            apps: Vec::new(),
+            current: None,
+        }
+    }
+
+    /// Set the strategy used for applications without an entry
+    pub fn with_default(mut self, strategy: OutputStrategy) -> Self {
+        self.default = strategy;
+        self
+    }
+
+    /// Add or replace the entry for an application
+    pub fn with_app(mut self, app_id: &str, strategy: OutputStrategy) -> Self {
+        self.set_app(app_id, strategy);
+        self
+    }
+
+    /// Add or replace the entry for an application
+    pub fn set_app(&mut self, app_id: &str, strategy: OutputStrategy) {
+        let app_id = app_id.to_lowercase();
+        match self.apps.iter_mut().find(|(id, _)| *id == app_id) {
+            Some(entry) => entry.1 = strategy,
+            None => self.apps.push((app_id, strategy)),
+        }
+    }
+
+    /// Strategy for a given application
+    pub fn strategy_for(&self, app_id: &str) -> OutputStrategy {
+        let app_id = app_id.to_lowercase();
+        self.apps
+            .iter()
+            .find(|(id, _)| *id == app_id)
+            .map(|(_, strategy)| *strategy)
+            .unwrap_or(self.default)
+    }
+
+    /// Strategy for the focused application
+    pub fn strategy(&self) -> OutputStrategy {
+        match &self.current {
+            Some(app_id) => self.strategy_for(app_id),
+            None => self.default,
+        }
+    }
+
+    /// Track focus changes reported by the host
+    pub fn handle_event(&mut self, event: &EngineEvent) {
+        match event {
+            EngineEvent::FocusGained { app_id } => self.current = Some(app_id.clone()),
+            EngineEvent::FocusLost => self.current = None,
+            _ => {}
+        }
+    }
+}
+
+impl Default for StrategySelector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -1,11 +1,30 @@
 //! # Telex Input Engine
 //!
 //! Vietnamese Telex input method implementation.
+//!
+//! Keys follow the Telex table in the README:
+//!
+//! | Keys                | Result                     |
+//! |---------------------|----------------------------|
+//! | `aa` `ee` `oo`      | â ê ô                      |
+//! | `aw` `ow` `uw`      | ă ơ ư (`uow` → ươ)         |
+//! | `]` `[`             | ơ ư                        |
+//! | `dd`                | đ                          |
+//! | `s` `f` `r` `x` `j` | sắc, huyền, hỏi, ngã, nặng |
+//! | `z`                 | no tone                    |
+//!
+//! Typing a transform key again undoes it and keeps the key as a letter
+//! (`aww` → aw, `ddd` → dd, `[[` → [). At the end of a word `uow` gives
+//! uơ (thuở); the u takes its horn once more letters follow (thương).
 
+use std::ops::Range;
+use std::sync::Arc;
 use std::vec::Vec;
 
+use crate::clock::{Clock, SystemClock};
 use crate::engines::{InputEngine, ProcessKeyResult};
 use crate::models::vietnamese::{ToneIndex, VietnameseChar};
+use crate::BUFFER_TIMEOUT_MS;
 
 /// Maximum buffer size
 const MAX_BUFFER_SIZE: usize = 20;
@@ -13,15 +32,64 @@ const MAX_BUFFER_SIZE: usize = 20;
 /// Telex input engine
 pub struct TelexEngine {
     buffer: Vec<char>,
+    clock: Arc<dyn Clock>,
+    last_key_ms: u64,
+    /// CapsLock state reported with the last key event
+    caps_lock: bool,
+    /// Previous key of the word, for undoing a bracket (`[[` -> [)
+    last_key: Option<char>,
 }
 
 impl TelexEngine {
     pub fn new() -> Self {
+        Self::with_clock(Arc::new(SystemClock::new()))
+    }
+
+    /// Create an engine driven by the given clock (used for buffer auto-reset)
+    pub fn with_clock(clock: Arc<dyn Clock>) -> Self {
+        let last_key_ms = clock.now_ms();
         Self {
             buffer: Vec::with_capacity(MAX_BUFFER_SIZE),
+            clock,
+            last_key_ms,
+            caps_lock: false,
+            last_key: None,
         }
     }
 
+    /// Clear the buffer if no keystroke occurred within [`BUFFER_TIMEOUT_MS`]
+    fn expire_stale_buffer(&mut self) {
+        let now = self.clock.now_ms();
+        if now.saturating_sub(self.last_key_ms) >= BUFFER_TIMEOUT_MS {
+            self.buffer.clear();
+        }
+        self.last_key_ms = now;
+    }
+
+    /// Whether the word is typed in capitals: two or more uppercase letters
+    /// (VIỆT), or one with CapsLock on
+    fn is_all_caps(&self) -> bool {
+        let letters: Vec<char> = self
+            .buffer
+            .iter()
+            .copied()
+            .filter(|c| c.is_alphabetic())
+            .collect();
+        letters.iter().all(|c| c.is_uppercase())
+            && (letters.len() > 1 || (self.caps_lock && !letters.is_empty()))
+    }
+
+    /// Push a key kept as a letter after undoing a transform (aww -> aw);
+    /// in an all-caps word it takes the word's case (AWw -> AW)
+    fn push_literal(&mut self, key: char) {
+        let key = if self.is_all_caps() {
+            VietnameseChar::to_upper(key)
+        } else {
+            key
+        };
+        self.push(key);
+    }
+
     fn push(&mut self, key: char) {
         if self.buffer.len() < MAX_BUFFER_SIZE {
             self.buffer.push(key);
@@ -32,257 +100,316 @@ impl TelexEngine {
         self.buffer.iter().collect()
     }
 
-    fn is_vowel(c: char) -> bool {
-        VietnameseChar::is_vowel(c)
-    }
-
+    /// Whether `key` ends the word: any key that is not a letter
+    ///
+    /// Consonants after a vowel are final consonants (tiếng, việt) and stay
+    /// in the word so later tone keys still apply.
     fn is_flush_trigger_key(&self, key: char) -> bool {
-        if !key.is_alphabetic() {
-            return true;
-        }
-
-        let lower = key.to_ascii_lowercase();
-        if lower == 'w' || lower == 's' || lower == 'f' || lower == 'r'
-            || lower == 'x' || lower == 'j' || lower == 'z'
-        {
-            return false;
-        }
-
-        if !self.buffer.is_empty() {
-            let last = self.buffer.last().copied().unwrap_or('\0');
-            if Self::is_vowel(last) && !Self::is_vowel(key) {
-                return true;
-            }
-        }
-
-        false
+        !key.is_alphabetic()
     }
 
-    fn flush(&mut self) -> ProcessKeyResult {
-        let content = self.current_state();
+    /// End the current word
+    ///
+    /// Every key of the word has already reached the screen (passed through
+    /// or replaced), so nothing is re-sent.
+    fn flush(&mut self) {
         self.buffer.clear();
-        ProcessKeyResult::replace(0, content, String::new())
     }
 
     // ========== TELEX TRANSFORMS (Story 1.1/1.3) ==========
 
-    fn try_process_double_vowel(&mut self, key: char) -> Option<ProcessKeyResult> {
-        if self.buffer.is_empty() {
+    /// Replace a vowel letter, keeping the case and tone of `old`
+    fn replace_vowel(old: char, new_vowel: char) -> char {
+        let new_vowel = VietnameseChar::with_case_of(new_vowel, old);
+        VietnameseChar::apply_tone(new_vowel, VietnameseChar::get_tone_index(old))
+    }
+
+    /// Lowercase vowel letter without tone (ậ -> â)
+    fn vowel_letter(c: char) -> char {
+        VietnameseChar::to_lower(VietnameseChar::get_vowel_without_tone(c))
+    }
+
+    /// Positions of the last vowel group, if only final consonants follow it
+    fn last_vowel_group(&self) -> Option<Range<usize>> {
+        let end = self
+            .buffer
+            .iter()
+            .rposition(|&c| VietnameseChar::is_vowel(c))?
+            + 1;
+        let finals_only = self.buffer[end..].iter().all(|&c| {
+            matches!(
+                VietnameseChar::to_lower(c),
+                'c' | 'm' | 'n' | 'p' | 't' | 'g' | 'h'
+            )
+        });
+        if !finals_only {
             return None;
         }
+        let start = self.buffer[..end]
+            .iter()
+            .rposition(|&c| !VietnameseChar::is_vowel(c))
+            .map_or(0, |i| i + 1);
+        Some(start..end)
+    }
 
-        let last = self.buffer.last().copied()?;
-        let last_base = VietnameseChar::get_vowel_without_tone(last).to_ascii_lowercase();
-        let key_lower = key.to_ascii_lowercase();
+    fn try_process_double_vowel(&mut self, key: char) -> bool {
+        let Some(last) = self.buffer.last().copied() else {
+            return false;
+        };
+        let last_base = Self::vowel_letter(last);
+        let key_lower = VietnameseChar::to_lower(key);
+
+        // A third key undoes the circumflex and keeps both letters (ooo -> oo, xoong)
+        if matches!(
+            (Self::vowel_letter(last), key_lower),
+            ('â', 'a') | ('ê', 'e') | ('ô', 'o')
+        ) {
+            self.buffer.pop();
+            self.push(Self::replace_vowel(last, key_lower));
+            self.push_literal(key);
+            return true;
+        }
 
         if last_base == key_lower && matches!(key_lower, 'a' | 'e' | 'o') {
             let new_vowel = match key_lower {
                 'a' => 'â',
                 'e' => 'ê',
                 'o' => 'ô',
-                _ => return None,
-            };
-
-            // Maintain case
-            let new_vowel = if last.is_uppercase() {
-                new_vowel.to_uppercase().next()?
-            } else {
-                new_vowel
+                _ => return false,
             };
 
-            // Maintain tone
-            let tone = VietnameseChar::get_tone_index(last);
-            let new_vowel = VietnameseChar::apply_tone(new_vowel, tone);
+            // Maintain case and tone
+            let new_vowel = Self::replace_vowel(last, new_vowel);
 
             self.buffer.pop();
             self.push(new_vowel);
-
-            return Some(ProcessKeyResult {
-                handled: true,
-                output_text: Some(new_vowel.to_string()),
-                backspace_count: 1,
-                current_buffer: self.current_state(),
-            });
+            return true;
         }
 
-        None
+        false
     }
 
-    // ========== TONE MARK METHODS (Story 1.2) ==========
-
-    /// Detect if a key is a tone key (s, f, r, x, j, z)
-    /// Returns Some(ToneIndex) if tone key, None otherwise
-    pub fn is_tone_key(key: char) -> Option<ToneIndex> {
-        let lower = key.to_ascii_lowercase();
-        match lower {
-            's' => Some(ToneIndex::Acute),
-            'f' => Some(ToneIndex::Grave),
-            'r' => Some(ToneIndex::Hook),
-            'x' => Some(ToneIndex::Tilde),
-            'j' => Some(ToneIndex::Dot),
-            'z' => Some(ToneIndex::None),
-            _ => None,
+    /// w adds a breve or horn: aw -> ă, ow -> ơ, uw -> ư, uow -> ươ
+    /// (uơ at the end of the word: thuở)
+    ///
+    /// Typing w again removes it and keeps the w as a letter (uww -> uw).
+    fn try_process_w(&mut self, key: char) -> bool {
+        if !key.eq_ignore_ascii_case(&'w') {
+            return false;
+        }
+        let Some(group) = self.last_vowel_group() else {
+            return false;
+        };
+        let letters: Vec<char> = self.buffer[group.clone()]
+            .iter()
+            .map(|&c| Self::vowel_letter(c))
+            .collect();
+
+        // uo and ưo both become ươ (được, người); an open uo keeps its
+        // plain u (thuở, quơ) until settle_uo_horn sees more letters
+        if let Some(i) = letters
+            .windows(2)
+            .position(|pair| matches!(pair[0], 'u' | 'ư') && pair[1] == 'o')
+        {
+            let pos = group.start + i;
+            let open = pos + 2 == self.buffer.len();
+            if !open {
+                self.buffer[pos] = Self::replace_vowel(self.buffer[pos], 'ư');
+            }
+            self.buffer[pos + 1] = Self::replace_vowel(self.buffer[pos + 1], 'ơ');
+            return true;
         }
-    }
 
-    /// Find the vowel position for tone placement following Vietnamese rules
-    /// Priority: horn/accented vowels (ê, ô, ơ, â, ă, ư) > special patterns > first vowel
-    pub fn find_vowel_position_for_tone(&self, buffer: &[char]) -> Option<usize> {
-        // Find all vowel positions in buffer
-        let mut vowel_positions: Vec<usize> = Vec::new();
-        for (i, &c) in buffer.iter().enumerate() {
-            if Self::is_vowel(c) {
-                vowel_positions.push(i);
+        // Toggle: remove existing breves and horns
+        if letters.iter().any(|c| matches!(c, 'ă' | 'ơ' | 'ư')) {
+            for (pos, letter) in group.zip(letters) {
+                let plain = match letter {
+                    'ă' => 'a',
+                    'ơ' => 'o',
+                    'ư' => 'u',
+                    _ => continue,
+                };
+                self.buffer[pos] = Self::replace_vowel(self.buffer[pos], plain);
             }
+            self.push_literal(key);
+            return true;
         }
 
-        if vowel_positions.is_empty() {
-            return None;
+        match letters.iter().rposition(|c| matches!(c, 'a' | 'o' | 'u')) {
+            Some(i) => {
+                let pos = group.start + i;
+                let new_vowel = match letters[i] {
+                    'a' => 'ă',
+                    'o' => 'ơ',
+                    _ => 'ư',
+                };
+                self.buffer[pos] = Self::replace_vowel(self.buffer[pos], new_vowel);
+                true
+            }
+            None => false,
         }
+    }
 
-        if vowel_positions.len() == 1 {
-            return Some(vowel_positions[0]);
+    /// dd -> đ; typing d again gives dd back
+    fn try_process_dd(&mut self, key: char) -> bool {
+        if !key.eq_ignore_ascii_case(&'d') {
+            return false;
         }
+        let replacement = match self.buffer.last() {
+            Some('d') => 'đ',
+            Some('D') => 'Đ',
+            Some('đ') => 'd',
+            Some('Đ') => 'D',
+            _ => return false,
+        };
+
+        self.buffer.pop();
+        self.push(replacement);
+        if matches!(replacement, 'd' | 'D') {
+            self.push_literal(key);
+        }
+        true
+    }
 
-        // Find the last consecutive vowel group
-        let mut last_group: Vec<usize> = Vec::new();
-        for &pos in vowel_positions.iter().rev() {
-            if last_group.is_empty() || pos == last_group[0] - 1 {
-                last_group.insert(0, pos);
-            } else {
-                break;
-            }
+    /// [ types ư and ] types ơ ({ and } for Ư and Ơ); the same bracket again
+    /// gives the bracket back
+    ///
+    /// Only the bracket just typed is undone: the ư of `tuw` followed by `[`
+    /// gives tưư, not t[.
+    fn try_process_bracket(
+        &mut self,
+        key: char,
+        previous_key: Option<char>,
+    ) -> Option<ProcessKeyResult> {
+        let vowel = match key {
+            '[' => 'ư',
+            ']' => 'ơ',
+            '{' => 'Ư',
+            '}' => 'Ơ',
+            _ => return None,
+        };
+
+        if previous_key == Some(key)
+            && self.buffer.last().map(|&c| Self::vowel_letter(c))
+                == Some(VietnameseChar::to_lower(vowel))
+        {
+            // The bracket ends the word, like any other punctuation
+            self.flush();
+            return Some(ProcessKeyResult::replace(1, key.to_string(), String::new()));
         }
 
-        if last_group.len() == 1 {
-            return Some(last_group[0]);
+        if self.buffer.len() >= MAX_BUFFER_SIZE {
+            self.flush();
         }
+        let old = self.buffer.clone();
+        // Brackets have no CapsLock form: follow the word (TH[ -> THƯ)
+        let vowel = if self.is_all_caps() {
+            VietnameseChar::to_upper(vowel)
+        } else {
+            vowel
+        };
+        self.push(vowel);
+        self.settle_uo_horn();
+        self.reposition_tone();
+        Some(ProcessKeyResult::from_edit(&old, &self.buffer, key))
+    }
 
-        if last_group.len() >= 2 {
-            let first_vowel = VietnameseChar::get_vowel_without_tone(buffer[last_group[0]]).to_ascii_lowercase();
-            let second_vowel = VietnameseChar::get_vowel_without_tone(buffer[last_group[1]]).to_ascii_lowercase();
-            let last_vowel_pos = last_group[last_group.len() - 1];
-            let has_consonant_after = last_vowel_pos < buffer.len() - 1
-                && !Self::is_vowel(buffer[last_vowel_pos + 1]);
+    /// Put the horn on the u of uơ once a letter follows the ơ
+    ///
+    /// Open syllables are written uơ (thuở, huơ, quơ), so `uow` at the end
+    /// of the word leaves the u plain; a final consonant or vowel typed
+    /// after it makes the pair ươ (thuowng -> thương).
+    fn settle_uo_horn(&mut self) {
+        let last = self.buffer.len().saturating_sub(1);
+        let Some(i) = self.buffer[..last].windows(2).rposition(|pair| {
+            Self::vowel_letter(pair[0]) == 'u' && Self::vowel_letter(pair[1]) == 'ơ'
+        }) else {
+            return;
+        };
+        self.buffer[i] = Self::replace_vowel(self.buffer[i], 'ư');
+    }
 
-            // Special pattern: ƯƠ -> tone on Ơ (second vowel) when followed by consonant
-            if first_vowel == 'ư' && second_vowel == 'ơ' && has_consonant_after {
-                return Some(last_group[1]);
-            }
+    /// Move the tone to the vowel that should carry it now
+    ///
+    /// Tones may be typed before the word is complete: hofa -> hoà,
+    /// nguwowfi -> người.
+    fn reposition_tone(&mut self) {
+        let Some(pos) = self
+            .buffer
+            .iter()
+            .position(|&c| VietnameseChar::get_tone_index(c).is_toned())
+        else {
+            return;
+        };
+        let tone = VietnameseChar::get_tone_index(self.buffer[pos]);
+        self.buffer[pos] = VietnameseChar::apply_tone(self.buffer[pos], ToneIndex::None);
+
+        let target = VietnameseChar::find_tone_position(&self.buffer).unwrap_or(pos);
+        self.buffer[target] = VietnameseChar::apply_tone(self.buffer[target], tone);
+    }
 
-            // Special pattern: oa, oe, oă -> tone on second vowel
-            if first_vowel == 'o' && matches!(second_vowel, 'a' | 'e' | 'ă') {
-                return Some(last_group[1]);
-            }
+    // ========== TONE MARK METHODS (Story 1.2) ==========
 
-            // Priority: horn/accented vowels (ê, ô, ơ, â, ă, ư)
-            for &pos in &last_group {
-                let c = buffer[pos];
-                let vowel_without_tone = VietnameseChar::get_vowel_without_tone(c).to_ascii_lowercase();
-                if matches!(vowel_without_tone, 'ê' | 'ô' | 'ơ' | 'â' | 'ă' | 'ư') {
-                    return Some(pos);
-                }
-            }
+    /// Detect if a key is a tone key (s, f, r, x, j, z)
+    /// Returns Some(ToneIndex) if tone key, None otherwise
+    pub fn is_tone_key(key: char) -> Option<ToneIndex> {
+        match VietnameseChar::to_lower(key) {
+            's' => Some(ToneIndex::Acute),
+            'f' => Some(ToneIndex::Grave),
+            'r' => Some(ToneIndex::Hook),
+            'x' => Some(ToneIndex::Tilde),
+            'j' => Some(ToneIndex::Dot),
+            'z' => Some(ToneIndex::None),
+            _ => None,
         }
+    }
 
-        // Default: first vowel in the group
-        Some(last_group[0])
+    /// Find the vowel position for tone placement following Vietnamese rules
+    /// (delegates to [`VietnameseChar::find_tone_position`])
+    pub fn find_vowel_position_for_tone(&self, buffer: &[char]) -> Option<usize> {
+        VietnameseChar::find_tone_position(buffer)
     }
 
     /// Process a tone mark key and apply it to the buffer
-    fn process_tone_mark(&mut self, key: char) -> Option<ProcessKeyResult> {
-        let tone = Self::is_tone_key(key)?;
+    ///
+    /// Returns true if the key was consumed as a tone mark
+    fn process_tone_mark(&mut self, key: char) -> bool {
+        let Some(tone) = Self::is_tone_key(key) else {
+            return false;
+        };
 
         // Find vowel position for tone
-        let vowel_pos = self.find_vowel_position_for_tone(&self.buffer);
-
-        if vowel_pos.is_none() {
-            return None;
-        }
-
-        let vowel_pos = vowel_pos.unwrap();
+        let Some(vowel_pos) = self.find_vowel_position_for_tone(&self.buffer) else {
+            return false;
+        };
         let old_vowel = self.buffer[vowel_pos];
         let current_tone = VietnameseChar::get_tone_index(old_vowel);
 
         // Handle 'z' (Removal)
         if tone == ToneIndex::None {
             // Remove everything: both tone and horn/circumflex
-            let vowel_without_tone = VietnameseChar::apply_tone(old_vowel, ToneIndex::None);
-            let base_vowel = match vowel_without_tone.to_ascii_lowercase() {
-                'ă' | 'â' => 'a',
-                'ê' => 'e',
-                'ô' | 'ơ' => 'o',
-                'ư' => 'u',
-                _ => vowel_without_tone.to_ascii_lowercase(),
-            };
-
-            let new_vowel = if vowel_without_tone.is_uppercase() {
-                base_vowel.to_uppercase().next().unwrap_or(base_vowel)
-            } else {
-                base_vowel
-            };
+            let new_vowel = VietnameseChar::get_base_vowel(old_vowel);
 
             if new_vowel != old_vowel {
-                let backspace_count = self.buffer.len() - vowel_pos;
                 self.buffer[vowel_pos] = new_vowel;
-                self.push(key);
-                let output = self.current_state();
-                return Some(ProcessKeyResult {
-                    handled: true,
-                    output_text: Some(output.chars().skip(vowel_pos).collect()),
-                    backspace_count,
-                    current_buffer: output,
-                });
+                self.push_literal(key);
+                return true;
             }
-            return None;
+            return false;
         }
 
         // Toggle: if same tone already present, remove it and add key to buffer
         if current_tone == tone {
             // Remove both tone and horn/circumflex
-            let vowel_without_tone = VietnameseChar::apply_tone(old_vowel, ToneIndex::None);
-            let base_vowel = match vowel_without_tone.to_ascii_lowercase() {
-                'ă' | 'â' => 'a',
-                'ê' => 'e',
-                'ô' | 'ơ' => 'o',
-                'ư' => 'u',
-                _ => vowel_without_tone.to_ascii_lowercase(),
-            };
-
-            let final_vowel = if vowel_without_tone.is_uppercase() {
-                base_vowel.to_uppercase().next().unwrap_or(base_vowel)
-            } else {
-                base_vowel
-            };
-
-            let backspace_count = self.buffer.len() - vowel_pos;
+            let final_vowel = VietnameseChar::get_base_vowel(old_vowel);
             self.buffer[vowel_pos] = final_vowel;
-            self.push(key);
-
-            let output: String = self.buffer.iter().skip(vowel_pos).collect();
-
-            return Some(ProcessKeyResult {
-                handled: true,
-                output_text: Some(output),
-                backspace_count,
-                current_buffer: self.current_state(),
-            });
+            self.push_literal(key);
+            return true;
         }
 
         // Apply or Override tone
-        let new_vowel = VietnameseChar::apply_tone(old_vowel, tone);
-
-        let backspace_count = self.buffer.len() - vowel_pos;
-        self.buffer[vowel_pos] = new_vowel;
-
-        let output: String = self.buffer.iter().skip(vowel_pos).collect();
-
-        Some(ProcessKeyResult {
-            handled: true,
-            output_text: Some(output),
-            backspace_count,
-            current_buffer: self.current_state(),
-        })
+        self.buffer[vowel_pos] = VietnameseChar::apply_tone(old_vowel, tone);
+        true
     }
 }
 
@@ -297,42 +424,61 @@ impl InputEngine for TelexEngine {
         "Telex"
     }
 
-    fn process_key(&mut self, key: char, _is_shift_pressed: bool) -> ProcessKeyResult {
-        // NEW: Check for tone mark keys first (Story 1.2)
-        if let Some(result) = self.process_tone_mark(key) {
+    fn process_key(&mut self, key: char, is_shift_pressed: bool) -> ProcessKeyResult {
+        self.expire_stale_buffer();
+        // With CapsLock on, Shift inside an all-caps word does not drop a
+        // letter to lowercase (VIEEtj -> VIỆT); the typed key is replaced
+        let typed = key;
+        let key = if self.caps_lock && is_shift_pressed && self.is_all_caps() {
+            VietnameseChar::to_upper(key)
+        } else {
+            key
+        };
+        let previous_key = self.last_key.replace(key);
+
+        // [ and ] are vowels in Telex, not word boundaries
+        if let Some(result) = self.try_process_bracket(key, previous_key) {
             return result;
         }
 
-        // NEW: Check for double vowel (aa, ee, oo)
-        if let Some(result) = self.try_process_double_vowel(key) {
-            return result;
+        // Flush trigger logic from Story 1.1 (never true for tone or double vowel keys).
+        // A full buffer cannot be a Vietnamese word either.
+        if self.is_flush_trigger_key(key) || self.buffer.len() >= MAX_BUFFER_SIZE {
+            self.flush();
         }
 
-        // EXISTING: Flush trigger logic from Story 1.1
-        if self.is_flush_trigger_key(key) {
-            if !self.buffer.is_empty() {
-                let flush_result = self.flush();
-                self.push(key);
-
-                return ProcessKeyResult {
-                    handled: true,
-                    output_text: flush_result.output_text,
-                    backspace_count: flush_result.backspace_count,
-                    current_buffer: self.current_state(),
-                };
-            }
+        // Output is the minimal diff between the word before and after the key
+        let old = self.buffer.clone();
+
+        // Tone mark keys first (Story 1.2), then w, dd and double vowel
+        // (aa, ee, oo), then normal character push (non-letters never start a word)
+        let transformed = self.process_tone_mark(key)
+            || self.try_process_w(key)
+            || self.try_process_dd(key)
+            || self.try_process_double_vowel(key);
+        if !transformed && key.is_alphabetic() {
+            self.push(key);
         }
+        // ươ/uơ follows the letters after it; a tone typed early (hofa)
+        // moves once the rest of the vowels arrive
+        self.settle_uo_horn();
+        self.reposition_tone();
 
-        // EXISTING: Normal character push
-        self.push(key);
-        ProcessKeyResult::pass_through(self.current_state())
+        ProcessKeyResult::from_edit(&old, &self.buffer, typed)
+    }
+
+    fn set_caps_lock(&mut self, caps_lock: bool) {
+        self.caps_lock = caps_lock;
     }
 
     fn reset(&mut self) {
         self.buffer.clear();
+        self.last_key = None;
     }
 
     fn process_backspace(&mut self) -> bool {
+        self.expire_stale_buffer();
+        self.last_key = None;
         if !self.buffer.is_empty() {
             self.buffer.pop();
             false
@@ -379,19 +525,80 @@ mod tests {
         let mut engine = TelexEngine::new();
         let result = engine.process_key('a', false);
         assert_eq!(engine.get_buffer(), "a");
-        assert_eq!(result.handled, false);
+        assert!(!result.handled);
     }
 
     #[test]
-    fn test_process_key_flushes_on_consonant() {
+    fn test_process_key_keeps_final_consonant() {
         let mut engine = TelexEngine::new();
         engine.process_key('t', false);
         engine.process_key('i', false);
         assert_eq!(engine.get_buffer(), "ti");
 
+        // 'n' passes through and stays in the word as its final consonant
         let result = engine.process_key('n', false);
-        assert_eq!(result.output_text, Some("ti".to_string()));
-        assert_eq!(engine.get_buffer(), "n");
+        assert!(!result.handled);
+        assert_eq!(result.output_text, None);
+        assert_eq!(engine.get_buffer(), "tin");
+
+        // "tin" is already on screen; the space passes through and ends the word
+        let result = engine.process_key(' ', false);
+        assert!(!result.handled);
+        assert_eq!(engine.get_buffer(), "");
+    }
+
+    #[test]
+    fn test_tone_moves_with_later_vowels() {
+        for (keys, expected) in [
+            ("hofa", "hoà"),
+            ("hoaf", "hoà"),
+            ("nguwowfi", "người"),
+            ("tuf", "tù"),
+            ("tufi", "tùi"),
+        ] {
+            let mut engine = TelexEngine::new();
+            for key in keys.chars() {
+                engine.process_key(key, false);
+            }
+            assert_eq!(engine.get_buffer(), expected, "keys {:?}", keys);
+        }
+    }
+
+    #[test]
+    fn test_w_adds_breve_and_horn() {
+        for (keys, expected) in [
+            ("aw", "ă"),
+            ("ow", "ơ"),
+            ("uw", "ư"),
+            ("duocw", "dươc"),
+            ("hoaw", "hoă"),
+            ("uww", "uw"),
+        ] {
+            let mut engine = TelexEngine::new();
+            for key in keys.chars() {
+                engine.process_key(key, false);
+            }
+            assert_eq!(engine.get_buffer(), expected, "keys {:?}", keys);
+        }
+    }
+
+    #[test]
+    fn test_dd_and_brackets() {
+        let mut engine = TelexEngine::new();
+        engine.process_key('D', false);
+        engine.process_key('d', false);
+        assert_eq!(engine.get_buffer(), "Đ");
+
+        engine.reset();
+        let result = engine.process_key('[', false);
+        assert!(result.handled);
+        assert_eq!(result.output_text, Some("ư".to_string()));
+
+        // Second bracket gives the bracket back and ends the word
+        let result = engine.process_key('[', false);
+        assert_eq!(result.backspace_count, 1);
+        assert_eq!(result.output_text, Some("[".to_string()));
+        assert_eq!(engine.get_buffer(), "");
     }
 
     #[test]
@@ -412,7 +619,7 @@ mod tests {
             "Rapid typing took {}ms, expected < 100ms", duration.as_millis());
 
         // AC:3 - No keystrokes dropped (verify performance requirement met)
-        // The buffer may not contain all chars due to flush behavior (consonant after vowel),
+        // The buffer may not contain all chars due to flush behavior (full buffer),
         // but no keystrokes are lost - they are either in buffer or output
         // This test verifies the <5ms per keystroke requirement
     }
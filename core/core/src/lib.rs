@@ -6,8 +6,19 @@
 //!
 //! ## Architecture
 //!
-//! - [`engines`] - Input engine implementations (Telex, VNI)
+//! - [`engines`] - Input engine implementations (Telex, VNI, multi-tap, chord)
 //! - [`models`] - Vietnamese character models and mappings
+//! - [`encoder`] - Vietnamese text to Telex/VNI/VIQR keystrokes
+//! - [`convert`] - Text conversion between Unicode, legacy charsets, VIQR and NCR
+//! - [`detect`] - Encoding detection and mojibake repair
+//! - [`text`] - Diacritic stripping, ASCII folding and slugs
+//! - [`search`] - Accent-insensitive matching with offsets into the original
+//! - [`collate`] - Vietnamese dictionary sort order
+//! - [`transform`] - Case conversion and other whole-text transforms
+//! - [`normalize`] - Canonical tone placement, i/y spelling and composition
+//! - [`clock`] - Injectable time source for timing-dependent behaviour
+//! - [`testing`] - Virtual text field for checking what the user sees
+//! - `conformance` - Generic `InputEngine` contract tests (`conformance` feature)
 //!
 //! ## Usage
 //!
@@ -19,11 +30,23 @@
 //! let result = engine.process_key('a', false);
 //! ```
 
+pub mod clock;
+pub mod collate;
+#[cfg(feature = "conformance")]
+pub mod conformance;
+pub mod convert;
+pub mod detect;
+pub mod encoder;
 pub mod engines;
 pub mod models;
+pub mod normalize;
+pub mod search;
+pub mod testing;
+pub mod text;
+pub mod transform;
 
 /// Core engine exports
-pub use engines::{InputEngine, ProcessKeyResult};
+pub use engines::{EngineEvent, InputEngine, KeyEvent, ProcessKeyResult};
 
 // Re-export commonly used types
 pub use models::vietnamese::{ToneIndex, VietnameseChar};
@@ -41,4 +64,5 @@ pub const MAX_BUFFER_SIZE: usize = 20;
 
 /// Auto-reset timeout for buffer in milliseconds
 /// If no keystroke occurs within 2 seconds, buffer is cleared
+/// (applied by engines on the next keystroke, or via [`EngineEvent::Idle`])
 pub const BUFFER_TIMEOUT_MS: u64 = 2000;
@@ -0,0 +1,611 @@
+//! # Legacy Charsets
+//!
+//! Mapping tables between Unicode and the 8-bit Vietnamese charsets still
+//! found in older documents and fonts:
+//!
+//! | Charset | Fonts | Bytes per letter |
+//! |---------|-------|------------------|
+//! | [`Charset::Unicode`] (UTF-8, NFC) | any font | 1 character |
+//! | [`Charset::UnicodeCombining`] (UTF-8, NFD) | any font | 1 to 3 characters (base + marks) |
+//! | [`Charset::Tcvn3`] (TCVN 5712 VN3, "ABC") | `.VnTime`, `.VnArial` | 1 |
+//! | [`Charset::VniWindows`] | `VNI-Times`, `VNI-Helve` | 1 or 2 (base + mark) |
+//! | [`Charset::Viscii`] (RFC 1456) | `VISCII` fonts | 1 |
+//! | [`Charset::Cp1258`] (Windows-1258) | any font | 1 or 2 (base + combining tone) |
+//!
+//! Font-based charsets (TCVN3, VNI-Windows, VISCII) reach an application as
+//! the ISO-8859-1 characters with the same codes; CP1258 is a real code
+//! page and decodes to Unicode (with combining tones). See
+//! [`Charset::host_char`].
+//!
+//! TCVN3 has no uppercase letters with tones: documents use a separate
+//! capitals font (`.VnTimeH`) with the lowercase codes. Such letters are
+//! unmappable here.
+//!
+//! VPS is not supported yet and is tracked as its own backlog item. Its
+//! table places letters in control-code positions, so it will be added
+//! once checked against a reference font; until then there is no
+//! `Charset` variant for it, rather than one that might write the wrong
+//! bytes.
+
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use phf::{phf_map, Map};
+
+use crate::models::vietnamese::{ToneIndex, VietnameseChar};
+
+/// A Vietnamese character set
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Charset {
+    /// Unicode precomposed (NFC), as UTF-8
+    #[default]
+    Unicode,
+    /// Unicode decomposed (NFD): base letter + combining marks, as UTF-8
+    UnicodeCombining,
+    /// TCVN 5712:1993 VN3 ("ABC")
+    Tcvn3,
+    /// VNI-Windows (VNI fonts)
+    VniWindows,
+    /// VISCII (RFC 1456)
+    Viscii,
+    /// Windows-1258
+    Cp1258,
+}
+
+/// TCVN3: lowercase letters and the uppercase letters without tone
+fn tcvn3_map() -> &'static Map<char, &'static [u8]> {
+    static MAP: OnceLock<Map<char, &'static [u8]>> = OnceLock::new();
+    MAP.get_or_init(|| {
+        phf_map! {
+            'à' => &[0xB5], 'á' => &[0xB8], 'ả' => &[0xB6], 'ã' => &[0xB7],
+            'ạ' => &[0xB9], 'ă' => &[0xA8], 'ằ' => &[0xBB], 'ắ' => &[0xBE],
+            'ẳ' => &[0xBC], 'ẵ' => &[0xBD], 'ặ' => &[0xC6], 'â' => &[0xA9],
+            'ầ' => &[0xC7], 'ấ' => &[0xCA], 'ẩ' => &[0xC8], 'ẫ' => &[0xC9],
+            'ậ' => &[0xCB], 'è' => &[0xCC], 'é' => &[0xD0], 'ẻ' => &[0xCE],
+            'ẽ' => &[0xCF], 'ẹ' => &[0xD1], 'ê' => &[0xAA], 'ề' => &[0xD2],
+            'ế' => &[0xD5], 'ể' => &[0xD3], 'ễ' => &[0xD4], 'ệ' => &[0xD6],
+            'ì' => &[0xD7], 'í' => &[0xDD], 'ỉ' => &[0xD8], 'ĩ' => &[0xDC],
+            'ị' => &[0xDE], 'ò' => &[0xDF], 'ó' => &[0xE3], 'ỏ' => &[0xE1],
+            'õ' => &[0xE2], 'ọ' => &[0xE4], 'ô' => &[0xAB], 'ồ' => &[0xE5],
+            'ố' => &[0xE8], 'ổ' => &[0xE6], 'ỗ' => &[0xE7], 'ộ' => &[0xE9],
+            'ơ' => &[0xAC], 'ờ' => &[0xEA], 'ớ' => &[0xED], 'ở' => &[0xEB],
+            'ỡ' => &[0xEC], 'ợ' => &[0xEE], 'ù' => &[0xEF], 'ú' => &[0xF3],
+            'ủ' => &[0xF1], 'ũ' => &[0xF2], 'ụ' => &[0xF4], 'ư' => &[0xAD],
+            'ừ' => &[0xF5], 'ứ' => &[0xF8], 'ử' => &[0xF6], 'ữ' => &[0xF7],
+            'ự' => &[0xF9], 'ỳ' => &[0xFA], 'ý' => &[0xFD], 'ỷ' => &[0xFB],
+            'ỹ' => &[0xFC], 'ỵ' => &[0xFE], 'đ' => &[0xAE], 'Ă' => &[0xA1],
+            'Â' => &[0xA2], 'Ê' => &[0xA3], 'Ô' => &[0xA4], 'Ơ' => &[0xA5],
+            'Ư' => &[0xA6], 'Đ' => &[0xA7],
+        }
+    })
+}
+
+/// VNI-Windows: base letter + mark byte; i, ỵ, ơ, ư and đ have their own byte
+fn vni_map() -> &'static Map<char, &'static [u8]> {
+    static MAP: OnceLock<Map<char, &'static [u8]>> = OnceLock::new();
+    MAP.get_or_init(|| {
+        phf_map! {
+            'à' => &[0x61, 0xF8], 'á' => &[0x61, 0xF9], 'ả' => &[0x61, 0xFB], 'ã' => &[0x61, 0xF5],
+            'ạ' => &[0x61, 0xEF], 'ă' => &[0x61, 0xEA], 'ằ' => &[0x61, 0xE8], 'ắ' => &[0x61, 0xE9],
+            'ẳ' => &[0x61, 0xFA], 'ẵ' => &[0x61, 0xFC], 'ặ' => &[0x61, 0xEB], 'â' => &[0x61, 0xE2],
+            'ầ' => &[0x61, 0xE0], 'ấ' => &[0x61, 0xE1], 'ẩ' => &[0x61, 0xE5], 'ẫ' => &[0x61, 0xE3],
+            'ậ' => &[0x61, 0xE4], 'è' => &[0x65, 0xF8], 'é' => &[0x65, 0xF9], 'ẻ' => &[0x65, 0xFB],
+            'ẽ' => &[0x65, 0xF5], 'ẹ' => &[0x65, 0xEF], 'ê' => &[0x65, 0xE2], 'ề' => &[0x65, 0xE0],
+            'ế' => &[0x65, 0xE1], 'ể' => &[0x65, 0xE5], 'ễ' => &[0x65, 0xE3], 'ệ' => &[0x65, 0xE4],
+            'ì' => &[0xEC], 'í' => &[0xED], 'ỉ' => &[0xE6], 'ĩ' => &[0xF3],
+            'ị' => &[0xF2], 'ò' => &[0x6F, 0xF8], 'ó' => &[0x6F, 0xF9], 'ỏ' => &[0x6F, 0xFB],
+            'õ' => &[0x6F, 0xF5], 'ọ' => &[0x6F, 0xEF], 'ô' => &[0x6F, 0xE2], 'ồ' => &[0x6F, 0xE0],
+            'ố' => &[0x6F, 0xE1], 'ổ' => &[0x6F, 0xE5], 'ỗ' => &[0x6F, 0xE3], 'ộ' => &[0x6F, 0xE4],
+            'ơ' => &[0xF4], 'ờ' => &[0xF4, 0xF8], 'ớ' => &[0xF4, 0xF9], 'ở' => &[0xF4, 0xFB],
+            'ỡ' => &[0xF4, 0xF5], 'ợ' => &[0xF4, 0xEF], 'ù' => &[0x75, 0xF8], 'ú' => &[0x75, 0xF9],
+            'ủ' => &[0x75, 0xFB], 'ũ' => &[0x75, 0xF5], 'ụ' => &[0x75, 0xEF], 'ư' => &[0xF6],
+            'ừ' => &[0xF6, 0xF8], 'ứ' => &[0xF6, 0xF9], 'ử' => &[0xF6, 0xFB], 'ữ' => &[0xF6, 0xF5],
+            'ự' => &[0xF6, 0xEF], 'ỳ' => &[0x79, 0xF8], 'ý' => &[0x79, 0xF9], 'ỷ' => &[0x79, 0xFB],
+            'ỹ' => &[0x79, 0xF5], 'ỵ' => &[0xEE], 'đ' => &[0xF1], 'À' => &[0x41, 0xD8],
+            'Á' => &[0x41, 0xD9], 'Ả' => &[0x41, 0xDB], 'Ã' => &[0x41, 0xD5], 'Ạ' => &[0x41, 0xCF],
+            'Ă' => &[0x41, 0xCA], 'Ằ' => &[0x41, 0xC8], 'Ắ' => &[0x41, 0xC9], 'Ẳ' => &[0x41, 0xDA],
+            'Ẵ' => &[0x41, 0xDC], 'Ặ' => &[0x41, 0xCB], 'Â' => &[0x41, 0xC2], 'Ầ' => &[0x41, 0xC0],
+            'Ấ' => &[0x41, 0xC1], 'Ẩ' => &[0x41, 0xC5], 'Ẫ' => &[0x41, 0xC3], 'Ậ' => &[0x41, 0xC4],
+            'È' => &[0x45, 0xD8], 'É' => &[0x45, 0xD9], 'Ẻ' => &[0x45, 0xDB], 'Ẽ' => &[0x45, 0xD5],
+            'Ẹ' => &[0x45, 0xCF], 'Ê' => &[0x45, 0xC2], 'Ề' => &[0x45, 0xC0], 'Ế' => &[0x45, 0xC1],
+            'Ể' => &[0x45, 0xC5], 'Ễ' => &[0x45, 0xC3], 'Ệ' => &[0x45, 0xC4], 'Ì' => &[0xCC],
+            'Í' => &[0xCD], 'Ỉ' => &[0xC6], 'Ĩ' => &[0xD3], 'Ị' => &[0xD2],
+            'Ò' => &[0x4F, 0xD8], 'Ó' => &[0x4F, 0xD9], 'Ỏ' => &[0x4F, 0xDB], 'Õ' => &[0x4F, 0xD5],
+            'Ọ' => &[0x4F, 0xCF], 'Ô' => &[0x4F, 0xC2], 'Ồ' => &[0x4F, 0xC0], 'Ố' => &[0x4F, 0xC1],
+            'Ổ' => &[0x4F, 0xC5], 'Ỗ' => &[0x4F, 0xC3], 'Ộ' => &[0x4F, 0xC4], 'Ơ' => &[0xD4],
+            'Ờ' => &[0xD4, 0xD8], 'Ớ' => &[0xD4, 0xD9], 'Ở' => &[0xD4, 0xDB], 'Ỡ' => &[0xD4, 0xD5],
+            'Ợ' => &[0xD4, 0xCF], 'Ù' => &[0x55, 0xD8], 'Ú' => &[0x55, 0xD9], 'Ủ' => &[0x55, 0xDB],
+            'Ũ' => &[0x55, 0xD5], 'Ụ' => &[0x55, 0xCF], 'Ư' => &[0xD6], 'Ừ' => &[0xD6, 0xD8],
+            'Ứ' => &[0xD6, 0xD9], 'Ử' => &[0xD6, 0xDB], 'Ữ' => &[0xD6, 0xD5], 'Ự' => &[0xD6, 0xCF],
+            'Ỳ' => &[0x59, 0xD8], 'Ý' => &[0x59, 0xD9], 'Ỷ' => &[0x59, 0xDB], 'Ỹ' => &[0x59, 0xD5],
+            'Ỵ' => &[0xCE], 'Đ' => &[0xD1],
+        }
+    })
+}
+
+/// VISCII: one byte per letter, including six C0 control codes
+fn viscii_map() -> &'static Map<char, &'static [u8]> {
+    static MAP: OnceLock<Map<char, &'static [u8]>> = OnceLock::new();
+    MAP.get_or_init(|| {
+        phf_map! {
+            'à' => &[0xE0], 'á' => &[0xE1], 'ả' => &[0xE4], 'ã' => &[0xE3],
+            'ạ' => &[0xD5], 'ă' => &[0xE5], 'ằ' => &[0xA2], 'ắ' => &[0xA1],
+            'ẳ' => &[0xC6], 'ẵ' => &[0xC7], 'ặ' => &[0xA3], 'â' => &[0xE2],
+            'ầ' => &[0xA5], 'ấ' => &[0xA4], 'ẩ' => &[0xA6], 'ẫ' => &[0xE7],
+            'ậ' => &[0xA7], 'è' => &[0xE8], 'é' => &[0xE9], 'ẻ' => &[0xEB],
+            'ẽ' => &[0xA8], 'ẹ' => &[0xA9], 'ê' => &[0xEA], 'ề' => &[0xAB],
+            'ế' => &[0xAA], 'ể' => &[0xAC], 'ễ' => &[0xAD], 'ệ' => &[0xAE],
+            'ì' => &[0xEC], 'í' => &[0xED], 'ỉ' => &[0xEF], 'ĩ' => &[0xEE],
+            'ị' => &[0xB8], 'ò' => &[0xF2], 'ó' => &[0xF3], 'ỏ' => &[0xF6],
+            'õ' => &[0xF5], 'ọ' => &[0xF7], 'ô' => &[0xF4], 'ồ' => &[0xB0],
+            'ố' => &[0xAF], 'ổ' => &[0xB1], 'ỗ' => &[0xB2], 'ộ' => &[0xB5],
+            'ơ' => &[0xBD], 'ờ' => &[0xB6], 'ớ' => &[0xBE], 'ở' => &[0xB7],
+            'ỡ' => &[0xDE], 'ợ' => &[0xFE], 'ù' => &[0xF9], 'ú' => &[0xFA],
+            'ủ' => &[0xFC], 'ũ' => &[0xFB], 'ụ' => &[0xF8], 'ư' => &[0xDF],
+            'ừ' => &[0xD7], 'ứ' => &[0xD1], 'ử' => &[0xD8], 'ữ' => &[0xE6],
+            'ự' => &[0xF1], 'ỳ' => &[0xCF], 'ý' => &[0xFD], 'ỷ' => &[0xD6],
+            'ỹ' => &[0xDB], 'ỵ' => &[0xDC], 'đ' => &[0xF0], 'À' => &[0xC0],
+            'Á' => &[0xC1], 'Ả' => &[0xC4], 'Ã' => &[0xC3], 'Ạ' => &[0x80],
+            'Ă' => &[0xC5], 'Ằ' => &[0x82], 'Ắ' => &[0x81], 'Ẳ' => &[0x02],
+            'Ẵ' => &[0x05], 'Ặ' => &[0x83], 'Â' => &[0xC2], 'Ầ' => &[0x85],
+            'Ấ' => &[0x84], 'Ẩ' => &[0x86], 'Ẫ' => &[0x06], 'Ậ' => &[0x87],
+            'È' => &[0xC8], 'É' => &[0xC9], 'Ẻ' => &[0xCB], 'Ẽ' => &[0x88],
+            'Ẹ' => &[0x89], 'Ê' => &[0xCA], 'Ề' => &[0x8B], 'Ế' => &[0x8A],
+            'Ể' => &[0x8C], 'Ễ' => &[0x8D], 'Ệ' => &[0x8E], 'Ì' => &[0xCC],
+            'Í' => &[0xCD], 'Ỉ' => &[0x9B], 'Ĩ' => &[0xCE], 'Ị' => &[0x98],
+            'Ò' => &[0xD2], 'Ó' => &[0xD3], 'Ỏ' => &[0x99], 'Õ' => &[0xA0],
+            'Ọ' => &[0x9A], 'Ô' => &[0xD4], 'Ồ' => &[0x90], 'Ố' => &[0x8F],
+            'Ổ' => &[0x91], 'Ỗ' => &[0x92], 'Ộ' => &[0x93], 'Ơ' => &[0xB4],
+            'Ờ' => &[0x96], 'Ớ' => &[0x95], 'Ở' => &[0x97], 'Ỡ' => &[0xB3],
+            'Ợ' => &[0x94], 'Ù' => &[0xD9], 'Ú' => &[0xDA], 'Ủ' => &[0x9C],
+            'Ũ' => &[0x9D], 'Ụ' => &[0x9E], 'Ư' => &[0xBF], 'Ừ' => &[0xBB],
+            'Ứ' => &[0xBA], 'Ử' => &[0xBC], 'Ữ' => &[0xFF], 'Ự' => &[0xB9],
+            'Ỳ' => &[0x9F], 'Ý' => &[0xDD], 'Ỷ' => &[0x14], 'Ỹ' => &[0x19],
+            'Ỵ' => &[0x1E], 'Đ' => &[0xD0],
+        }
+    })
+}
+
+/// CP1258: precomposed where the code page has the letter, otherwise the
+/// base letter + combining tone (0xCC grave, 0xEC acute, 0xD2 hook,
+/// 0xDE tilde, 0xF2 dot)
+fn cp1258_map() -> &'static Map<char, &'static [u8]> {
+    static MAP: OnceLock<Map<char, &'static [u8]>> = OnceLock::new();
+    MAP.get_or_init(|| {
+        phf_map! {
+            'à' => &[0xE0], 'á' => &[0xE1], 'ả' => &[0x61, 0xD2], 'ã' => &[0x61, 0xDE],
+            'ạ' => &[0x61, 0xF2], 'ă' => &[0xE3], 'ằ' => &[0xE3, 0xCC], 'ắ' => &[0xE3, 0xEC],
+            'ẳ' => &[0xE3, 0xD2], 'ẵ' => &[0xE3, 0xDE], 'ặ' => &[0xE3, 0xF2], 'â' => &[0xE2],
+            'ầ' => &[0xE2, 0xCC], 'ấ' => &[0xE2, 0xEC], 'ẩ' => &[0xE2, 0xD2], 'ẫ' => &[0xE2, 0xDE],
+            'ậ' => &[0xE2, 0xF2], 'è' => &[0xE8], 'é' => &[0xE9], 'ẻ' => &[0x65, 0xD2],
+            'ẽ' => &[0x65, 0xDE], 'ẹ' => &[0x65, 0xF2], 'ê' => &[0xEA], 'ề' => &[0xEA, 0xCC],
+            'ế' => &[0xEA, 0xEC], 'ể' => &[0xEA, 0xD2], 'ễ' => &[0xEA, 0xDE], 'ệ' => &[0xEA, 0xF2],
+            'ì' => &[0x69, 0xCC], 'í' => &[0xED], 'ỉ' => &[0x69, 0xD2], 'ĩ' => &[0x69, 0xDE],
+            'ị' => &[0x69, 0xF2], 'ò' => &[0x6F, 0xCC], 'ó' => &[0xF3], 'ỏ' => &[0x6F, 0xD2],
+            'õ' => &[0x6F, 0xDE], 'ọ' => &[0x6F, 0xF2], 'ô' => &[0xF4], 'ồ' => &[0xF4, 0xCC],
+            'ố' => &[0xF4, 0xEC], 'ổ' => &[0xF4, 0xD2], 'ỗ' => &[0xF4, 0xDE], 'ộ' => &[0xF4, 0xF2],
+            'ơ' => &[0xF5], 'ờ' => &[0xF5, 0xCC], 'ớ' => &[0xF5, 0xEC], 'ở' => &[0xF5, 0xD2],
+            'ỡ' => &[0xF5, 0xDE], 'ợ' => &[0xF5, 0xF2], 'ù' => &[0xF9], 'ú' => &[0xFA],
+            'ủ' => &[0x75, 0xD2], 'ũ' => &[0x75, 0xDE], 'ụ' => &[0x75, 0xF2], 'ư' => &[0xFD],
+            'ừ' => &[0xFD, 0xCC], 'ứ' => &[0xFD, 0xEC], 'ử' => &[0xFD, 0xD2], 'ữ' => &[0xFD, 0xDE],
+            'ự' => &[0xFD, 0xF2], 'ỳ' => &[0x79, 0xCC], 'ý' => &[0x79, 0xEC], 'ỷ' => &[0x79, 0xD2],
+            'ỹ' => &[0x79, 0xDE], 'ỵ' => &[0x79, 0xF2], 'đ' => &[0xF0], 'À' => &[0xC0],
+            'Á' => &[0xC1], 'Ả' => &[0x41, 0xD2], 'Ã' => &[0x41, 0xDE], 'Ạ' => &[0x41, 0xF2],
+            'Ă' => &[0xC3], 'Ằ' => &[0xC3, 0xCC], 'Ắ' => &[0xC3, 0xEC], 'Ẳ' => &[0xC3, 0xD2],
+            'Ẵ' => &[0xC3, 0xDE], 'Ặ' => &[0xC3, 0xF2], 'Â' => &[0xC2], 'Ầ' => &[0xC2, 0xCC],
+            'Ấ' => &[0xC2, 0xEC], 'Ẩ' => &[0xC2, 0xD2], 'Ẫ' => &[0xC2, 0xDE], 'Ậ' => &[0xC2, 0xF2],
+            'È' => &[0xC8], 'É' => &[0xC9], 'Ẻ' => &[0x45, 0xD2], 'Ẽ' => &[0x45, 0xDE],
+            'Ẹ' => &[0x45, 0xF2], 'Ê' => &[0xCA], 'Ề' => &[0xCA, 0xCC], 'Ế' => &[0xCA, 0xEC],
+            'Ể' => &[0xCA, 0xD2], 'Ễ' => &[0xCA, 0xDE], 'Ệ' => &[0xCA, 0xF2], 'Ì' => &[0x49, 0xCC],
+            'Í' => &[0xCD], 'Ỉ' => &[0x49, 0xD2], 'Ĩ' => &[0x49, 0xDE], 'Ị' => &[0x49, 0xF2],
+            'Ò' => &[0x4F, 0xCC], 'Ó' => &[0xD3], 'Ỏ' => &[0x4F, 0xD2], 'Õ' => &[0x4F, 0xDE],
+            'Ọ' => &[0x4F, 0xF2], 'Ô' => &[0xD4], 'Ồ' => &[0xD4, 0xCC], 'Ố' => &[0xD4, 0xEC],
+            'Ổ' => &[0xD4, 0xD2], 'Ỗ' => &[0xD4, 0xDE], 'Ộ' => &[0xD4, 0xF2], 'Ơ' => &[0xD5],
+            'Ờ' => &[0xD5, 0xCC], 'Ớ' => &[0xD5, 0xEC], 'Ở' => &[0xD5, 0xD2], 'Ỡ' => &[0xD5, 0xDE],
+            'Ợ' => &[0xD5, 0xF2], 'Ù' => &[0xD9], 'Ú' => &[0xDA], 'Ủ' => &[0x55, 0xD2],
+            'Ũ' => &[0x55, 0xDE], 'Ụ' => &[0x55, 0xF2], 'Ư' => &[0xDD], 'Ừ' => &[0xDD, 0xCC],
+            'Ứ' => &[0xDD, 0xEC], 'Ử' => &[0xDD, 0xD2], 'Ữ' => &[0xDD, 0xDE], 'Ự' => &[0xDD, 0xF2],
+            'Ỳ' => &[0x59, 0xCC], 'Ý' => &[0x59, 0xEC], 'Ỷ' => &[0x59, 0xD2], 'Ỹ' => &[0x59, 0xDE],
+            'Ỵ' => &[0x59, 0xF2], 'Đ' => &[0xD0],
+        }
+    })
+}
+
+/// Windows-1258 bytes 0x80..=0xFF ('\u{FFFD}' = undefined)
+const CP1258_HIGH: [char; 128] = [
+    '\u{20AC}', '\u{FFFD}', '\u{201A}', '\u{0192}', '\u{201E}', '\u{2026}', '\u{2020}', '\u{2021}',
+    '\u{02C6}', '\u{2030}', '\u{FFFD}', '\u{2039}', '\u{0152}', '\u{FFFD}', '\u{FFFD}', '\u{FFFD}',
+    '\u{FFFD}', '\u{2018}', '\u{2019}', '\u{201C}', '\u{201D}', '\u{2022}', '\u{2013}', '\u{2014}',
+    '\u{02DC}', '\u{2122}', '\u{FFFD}', '\u{203A}', '\u{0153}', '\u{FFFD}', '\u{FFFD}', '\u{0178}',
+    '\u{00A0}', '\u{00A1}', '\u{00A2}', '\u{00A3}', '\u{00A4}', '\u{00A5}', '\u{00A6}', '\u{00A7}',
+    '\u{00A8}', '\u{00A9}', '\u{00AA}', '\u{00AB}', '\u{00AC}', '\u{00AD}', '\u{00AE}', '\u{00AF}',
+    '\u{00B0}', '\u{00B1}', '\u{00B2}', '\u{00B3}', '\u{00B4}', '\u{00B5}', '\u{00B6}', '\u{00B7}',
+    '\u{00B8}', '\u{00B9}', '\u{00BA}', '\u{00BB}', '\u{00BC}', '\u{00BD}', '\u{00BE}', '\u{00BF}',
+    '\u{00C0}', '\u{00C1}', '\u{00C2}', '\u{0102}', '\u{00C4}', '\u{00C5}', '\u{00C6}', '\u{00C7}',
+    '\u{00C8}', '\u{00C9}', '\u{00CA}', '\u{00CB}', '\u{0300}', '\u{00CD}', '\u{00CE}', '\u{00CF}',
+    '\u{0110}', '\u{00D1}', '\u{0309}', '\u{00D3}', '\u{00D4}', '\u{01A0}', '\u{00D6}', '\u{00D7}',
+    '\u{00D8}', '\u{00D9}', '\u{00DA}', '\u{00DB}', '\u{00DC}', '\u{01AF}', '\u{0303}', '\u{00DF}',
+    '\u{00E0}', '\u{00E1}', '\u{00E2}', '\u{0103}', '\u{00E4}', '\u{00E5}', '\u{00E6}', '\u{00E7}',
+    '\u{00E8}', '\u{00E9}', '\u{00EA}', '\u{00EB}', '\u{0301}', '\u{00ED}', '\u{00EE}', '\u{00EF}',
+    '\u{0111}', '\u{00F1}', '\u{0323}', '\u{00F3}', '\u{00F4}', '\u{01A1}', '\u{00F6}', '\u{00F7}',
+    '\u{00F8}', '\u{00F9}', '\u{00FA}', '\u{00FB}', '\u{00FC}', '\u{01B0}', '\u{20AB}', '\u{00FF}',
+];
+
+/// CP1258 combining tone bytes
+const CP1258_TONES: [(u8, ToneIndex); 5] = [
+    (0xCC, ToneIndex::Grave),
+    (0xEC, ToneIndex::Acute),
+    (0xD2, ToneIndex::Hook),
+    (0xDE, ToneIndex::Tilde),
+    (0xF2, ToneIndex::Dot),
+];
+
+/// Decode one UTF-8 character
+fn decode_utf8(bytes: &[u8]) -> Option<(char, usize)> {
+    let len = match bytes.first()? {
+        0x00..=0x7F => 1,
+        0xC0..=0xDF => 2,
+        0xE0..=0xEF => 3,
+        _ => 4,
+    };
+    let text = std::str::from_utf8(bytes.get(..len)?).ok()?;
+    text.chars().next().map(|c| (c, len))
+}
+
+/// Byte sequence -> character, for every table
+fn reverse_map() -> &'static HashMap<(Charset, &'static [u8]), char> {
+    static MAP: OnceLock<HashMap<(Charset, &'static [u8]), char>> = OnceLock::new();
+    MAP.get_or_init(|| {
+        let mut map = HashMap::new();
+        for charset in Charset::ALL {
+            if let Some(table) = charset.table() {
+                for (&c, &bytes) in table.entries() {
+                    map.insert((charset, bytes), c);
+                }
+            }
+        }
+        map
+    })
+}
+
+impl Charset {
+    /// All supported charsets
+    pub const ALL: [Charset; 6] = [
+        Self::Unicode,
+        Self::UnicodeCombining,
+        Self::Tcvn3,
+        Self::VniWindows,
+        Self::Viscii,
+        Self::Cp1258,
+    ];
+
+    /// Display name
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Unicode => "Unicode",
+            Self::UnicodeCombining => "Unicode (NFD)",
+            Self::Tcvn3 => "TCVN3",
+            Self::VniWindows => "VNI-Windows",
+            Self::Viscii => "VISCII",
+            Self::Cp1258 => "CP1258",
+        }
+    }
+
+    fn table(self) -> Option<&'static Map<char, &'static [u8]>> {
+        match self {
+            Self::Unicode | Self::UnicodeCombining => None,
+            Self::Tcvn3 => Some(tcvn3_map()),
+            Self::VniWindows => Some(vni_map()),
+            Self::Viscii => Some(viscii_map()),
+            Self::Cp1258 => Some(cp1258_map()),
+        }
+    }
+
+    /// Append the bytes for `c`; false if the charset cannot represent it
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use vietime_core::models::charset::Charset;
+    ///
+    /// let mut bytes = Vec::new();
+    /// assert!(Charset::VniWindows.encode_char('ế', &mut bytes));
+    /// assert_eq!(bytes, [b'e', 0xE1]);
+    /// assert!(!Charset::Tcvn3.encode_char('Ế', &mut bytes));
+    /// ```
+    pub fn encode_char(self, c: char, out: &mut Vec<u8>) -> bool {
+        let Some(table) = self.table() else {
+            let text = self.host_text(c).unwrap_or_default();
+            out.extend_from_slice(text.as_bytes());
+            return true;
+        };
+        if let Some(bytes) = table.get(&c) {
+            out.extend_from_slice(bytes);
+            return true;
+        }
+        if c.is_ascii() {
+            // VISCII reuses six control codes for letters
+            if reverse_map().contains_key(&(self, &[c as u8][..])) {
+                return false;
+            }
+            out.push(c as u8);
+            return true;
+        }
+        if self == Self::Cp1258 {
+            if let Some(i) = CP1258_HIGH.iter().position(|&h| h == c) {
+                out.push(0x80 + i as u8);
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Encode text, or return the first character the charset cannot represent
+    pub fn encode(self, text: &str) -> Result<Vec<u8>, char> {
+        let mut out = Vec::with_capacity(text.len());
+        for c in text.chars() {
+            if !self.encode_char(c, &mut out) {
+                return Err(c);
+            }
+        }
+        Ok(out)
+    }
+
+    /// Decode the character at the start of `bytes` and the bytes it used
+    ///
+    /// `None` for empty input, invalid UTF-8 or an undefined CP1258 byte.
+    /// Bytes outside the Vietnamese table decode as ISO-8859-1.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use vietime_core::models::charset::Charset;
+    ///
+    /// assert_eq!(Charset::VniWindows.decode_char(&[b'e', 0xE1, b'u']), Some(('ế', 2)));
+    /// assert_eq!(Charset::Cp1258.decode_char(&[0xEA, 0xEC]), Some(('ế', 2)));
+    /// assert_eq!(Charset::Tcvn3.decode_char(&[0xD5]), Some(('ế', 1)));
+    /// ```
+    pub fn decode_char(self, bytes: &[u8]) -> Option<(char, usize)> {
+        let &first = bytes.first()?;
+        match self {
+            Self::Unicode => decode_utf8(bytes),
+            Self::UnicodeCombining => {
+                let (base, len) = decode_utf8(bytes)?;
+                let mut marks = Vec::new();
+                let mut lens = vec![len];
+                while let Some((mark, mark_len)) = decode_utf8(&bytes[lens[lens.len() - 1]..]) {
+                    if !VietnameseChar::is_combining_mark(mark) {
+                        break;
+                    }
+                    marks.push(mark);
+                    lens.push(lens[lens.len() - 1] + mark_len);
+                }
+                // Longest run of marks that fits the letter
+                (0..=marks.len())
+                    .rev()
+                    .find_map(|n| VietnameseChar::compose(base, &marks[..n]).map(|c| (c, lens[n])))
+            }
+            Self::Cp1258 => {
+                let base = self.host_char(first);
+                if base == '\u{FFFD}' {
+                    return None;
+                }
+                let tone = bytes.get(1).and_then(|b| {
+                    CP1258_TONES
+                        .iter()
+                        .find(|(byte, _)| byte == b)
+                        .map(|&(_, tone)| tone)
+                });
+                match tone {
+                    Some(tone)
+                        if VietnameseChar::is_vowel(base)
+                            && !VietnameseChar::get_tone_index(base).is_toned() =>
+                    {
+                        Some((VietnameseChar::apply_tone(base, tone), 2))
+                    }
+                    _ => Some((base, 1)),
+                }
+            }
+            _ => {
+                let reverse = reverse_map();
+                if let Some(&c) = bytes.get(..2).and_then(|pair| reverse.get(&(self, pair))) {
+                    return Some((c, 2));
+                }
+                let c = reverse
+                    .get(&(self, &bytes[..1]))
+                    .copied()
+                    .unwrap_or(first as char);
+                Some((c, 1))
+            }
+        }
+    }
+
+    /// Decode bytes, replacing undecodable ones with U+FFFD
+    pub fn decode(self, bytes: &[u8]) -> String {
+        let mut text = String::with_capacity(bytes.len());
+        let mut i = 0;
+        while i < bytes.len() {
+            match self.decode_char(&bytes[i..]) {
+                Some((c, len)) => {
+                    text.push(c);
+                    i += len;
+                }
+                None => {
+                    text.push('\u{FFFD}');
+                    i += 1;
+                }
+            }
+        }
+        text
+    }
+
+    /// The character an application sees for one byte
+    ///
+    /// Legacy fonts are installed over ISO-8859-1, so TCVN3, VNI-Windows and
+    /// VISCII bytes arrive as the Latin-1 character with the same code
+    /// (`ế` in VNI-Windows is `"eá"`). CP1258 uses its code page
+    /// (combining tones included). For the Unicode forms the byte is taken
+    /// as Latin-1.
+    pub fn host_char(self, byte: u8) -> char {
+        match self {
+            Self::Cp1258 if byte >= 0x80 => CP1258_HIGH[usize::from(byte - 0x80)],
+            _ => char::from(byte),
+        }
+    }
+
+    /// Text an application sees for `c`, `None` if the charset lacks it
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use vietime_core::models::charset::Charset;
+    ///
+    /// assert_eq!(Charset::VniWindows.host_text('ế').as_deref(), Some("eá"));
+    /// assert_eq!(Charset::Cp1258.host_text('ế').as_deref(), Some("ê\u{301}"));
+    /// assert_eq!(Charset::Unicode.host_text('ế').as_deref(), Some("ế"));
+    /// assert_eq!(
+    ///     Charset::UnicodeCombining.host_text('ế').as_deref(),
+    ///     Some("e\u{302}\u{301}")
+    /// );
+    /// ```
+    pub fn host_text(self, c: char) -> Option<String> {
+        match self {
+            Self::Unicode => return Some(c.to_string()),
+            Self::UnicodeCombining => return Some(VietnameseChar::decompose(c)),
+            _ => {}
+        }
+        let mut bytes = Vec::new();
+        self.encode_char(c, &mut bytes)
+            .then(|| bytes.iter().map(|&b| self.host_char(b)).collect())
+    }
+
+    /// Check every character of `text` can sit in a document as is
+    ///
+    /// Any Unicode for the Unicode forms; code page characters for CP1258;
+    /// ISO-8859-1 characters (the font codes) for the legacy fonts.
+    pub fn is_host_text(self, text: &str) -> bool {
+        match self {
+            Self::Unicode | Self::UnicodeCombining => true,
+            Self::Cp1258 => text
+                .chars()
+                .all(|c| c.is_ascii() || CP1258_HIGH.contains(&c)),
+            _ => text.chars().all(|c| u32::from(c) <= 0xFF),
+        }
+    }
+
+    /// Text an application sees, in the internal form (Unicode NFC)
+    ///
+    /// Inverse of [`host_text`](Self::host_text), and more lenient: combining
+    /// marks (NFD, CP1258) are composed whatever the charset, and characters
+    /// outside the legacy font codes are taken as Unicode.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use vietime_core::models::charset::Charset;
+    ///
+    /// assert_eq!(Charset::VniWindows.from_host_text("tieáng"), "tiếng");
+    /// assert_eq!(Charset::Unicode.from_host_text("tie\u{302}\u{301}ng"), "tiếng");
+    /// assert_eq!(Charset::Cp1258.from_host_text("tiê\u{301}ng"), "tiếng");
+    /// assert_eq!(Charset::Tcvn3.from_host_text("ti\u{D5}ng"), "tiếng");
+    /// ```
+    pub fn from_host_text(self, text: &str) -> String {
+        if self.table().is_none() || self == Self::Cp1258 {
+            return VietnameseChar::to_nfc(text);
+        }
+        let mut decoded = String::with_capacity(text.len());
+        let mut bytes = Vec::new();
+        for c in text.chars() {
+            match u8::try_from(c) {
+                Ok(byte) => bytes.push(byte),
+                Err(_) => {
+                    decoded.push_str(&self.decode(&bytes));
+                    bytes.clear();
+                    decoded.push(c);
+                }
+            }
+        }
+        decoded.push_str(&self.decode(&bytes));
+        VietnameseChar::to_nfc(&decoded)
+    }
+}
+
+impl std::fmt::Display for Charset {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip_every_letter() {
+        for charset in Charset::ALL {
+            for c in VietnameseChar::letters() {
+                let mut bytes = Vec::new();
+                if !charset.encode_char(c, &mut bytes) {
+                    // Only TCVN3 lacks letters: uppercase with a tone
+                    assert_eq!(charset, Charset::Tcvn3, "{}", c);
+                    assert!(VietnameseChar::get_tone_index(c).is_toned(), "{}", c);
+                    continue;
+                }
+                assert_eq!(
+                    charset.decode_char(&bytes),
+                    Some((c, bytes.len())),
+                    "{} {}",
+                    charset,
+                    c
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_known_codes() {
+        assert_eq!(
+            Charset::Tcvn3.encode("tiếng"),
+            Ok(vec![b't', b'i', 0xD5, b'n', b'g'])
+        );
+        assert_eq!(Charset::Viscii.encode("Ẫ"), Ok(vec![0x06]));
+        assert_eq!(
+            Charset::VniWindows.encode("Đường"),
+            Ok(b"\xD1\xF6\xF4\xF8ng".to_vec())
+        );
+        assert_eq!(Charset::Cp1258.encode("ộ"), Ok(vec![0xF4, 0xF2]));
+        assert_eq!(Charset::Tcvn3.encode("VIỆT"), Err('Ệ'));
+        // VISCII control codes stand for letters, not ASCII controls
+        assert_eq!(Charset::Viscii.encode("\u{6}"), Err('\u{6}'));
+    }
+
+    #[test]
+    fn test_decode_text() {
+        let text = "Tiếng Việt có dấu, 100€";
+        for charset in [Charset::Unicode, Charset::UnicodeCombining, Charset::Cp1258] {
+            assert_eq!(charset.decode(&charset.encode(text).unwrap()), text);
+        }
+        let lower = text.replace('€', "");
+        for charset in [Charset::Tcvn3, Charset::VniWindows, Charset::Viscii] {
+            assert_eq!(charset.decode(&charset.encode(&lower).unwrap()), lower);
+        }
+        assert_eq!(Charset::Cp1258.decode(&[b'a', 0x81]), "a\u{FFFD}");
+    }
+
+    #[test]
+    fn test_from_host_text_every_letter() {
+        for charset in Charset::ALL {
+            for c in VietnameseChar::letters() {
+                if let Some(text) = charset.host_text(c) {
+                    assert!(charset.is_host_text(&text), "{} {}", charset, c);
+                    assert_eq!(charset.from_host_text(&text), c.to_string(), "{}", charset);
+                }
+                // Decomposed input is understood in every charset
+                let nfd = VietnameseChar::decompose(c);
+                assert_eq!(charset.from_host_text(&nfd), c.to_string(), "{}", charset);
+            }
+        }
+        assert!(!Charset::VniWindows.is_host_text("ư"));
+        assert!(!Charset::Cp1258.is_host_text("ế"));
+    }
+}
@@ -2,6 +2,10 @@
 //!
 //! Provides character mappings and utilities for Vietnamese text processing.
 
+pub mod charset;
+pub mod syllable;
 pub mod vietnamese;
 
-pub use vietnamese::{ToneIndex, VietnameseChar};
+pub use charset::Charset;
+pub use syllable::Syllable;
+pub use vietnamese::{Modifier, ToneIndex, VietnameseChar};
@@ -0,0 +1,378 @@
+//! # Vietnamese Syllables
+//!
+//! Structural model of a syllable: onset + rhyme (medial, nucleus, coda) +
+//! tone, with the spelling rules that turn it into written form.
+//!
+//! ## Features
+//!
+//! - Every legal onset, rhyme and tone combination via [`Syllable::all`]
+//! - Spelling rules: c/k/qu, g/gh, ng/ngh, gi, i/y after no onset
+//! - Tone placement on the main vowel of the nucleus (new style: hoà, thuý)
+//! - Entering tones: rhymes ending in c, ch, p, t take only sắc or nặng
+//! - Parsing written words back into syllables ([`Syllable::parse`])
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::OnceLock;
+
+use crate::models::vietnamese::{ToneIndex, VietnameseChar};
+
+/// Onsets in their default spelling ("" = no onset)
+///
+/// `c`, `g` and `ng` are respelled `k`, `gh` and `ngh` before front vowels.
+pub const ONSETS: [&str; 25] = [
+    "", "b", "c", "ch", "d", "đ", "g", "gi", "h", "kh", "l", "m", "n", "ng", "nh", "p", "ph", "qu",
+    "r", "s", "t", "th", "tr", "v", "x",
+];
+
+/// Onsets that combine with a medial (hoa, khuya, quê)
+const MEDIAL_ONSETS: [&str; 18] = [
+    "", "ch", "d", "đ", "g", "h", "kh", "l", "n", "ng", "nh", "qu", "r", "s", "t", "th", "tr", "x",
+];
+
+/// Nuclei without a medial, with the codas each one takes
+const PLAIN_RHYMES: [(&str, &[&str]); 19] = [
+    (
+        "a",
+        &[
+            "", "c", "ch", "i", "m", "n", "ng", "nh", "o", "p", "t", "u", "y",
+        ],
+    ),
+    ("ă", &["c", "m", "n", "ng", "p", "t"]),
+    ("â", &["c", "m", "n", "ng", "p", "t", "u", "y"]),
+    ("e", &["", "c", "m", "n", "ng", "o", "p", "t"]),
+    ("ê", &["", "ch", "m", "n", "nh", "p", "t", "u"]),
+    ("i", &["", "ch", "m", "n", "nh", "p", "t", "u"]),
+    ("o", &["", "c", "i", "m", "n", "ng", "p", "t"]),
+    ("oo", &["c", "ng"]),
+    ("ô", &["", "c", "i", "m", "n", "ng", "p", "t"]),
+    ("ơ", &["", "i", "m", "n", "p", "t"]),
+    ("u", &["", "c", "i", "m", "n", "ng", "p", "t"]),
+    ("ư", &["", "c", "i", "m", "n", "ng", "t", "u"]),
+    ("iê", &["c", "m", "n", "ng", "p", "t", "u"]),
+    ("ia", &[""]),
+    ("uô", &["c", "i", "m", "n", "ng", "t"]),
+    ("ua", &[""]),
+    ("ươ", &["c", "i", "m", "n", "ng", "p", "t", "u"]),
+    ("ưa", &[""]),
+    ("y", &[""]),
+];
+
+/// Nuclei after a medial (o before a, ă, e; u otherwise), with their codas
+const MEDIAL_RHYMES: [(&str, &[&str]); 9] = [
+    (
+        "a",
+        &["", "c", "ch", "i", "m", "n", "ng", "nh", "p", "t", "y"],
+    ),
+    ("ă", &["c", "m", "n", "ng", "t"]),
+    ("â", &["n", "ng", "t", "y"]),
+    ("e", &["", "n", "o", "t"]),
+    ("ê", &["", "ch", "nh"]),
+    ("y", &["", "ch", "nh", "t", "u"]),
+    ("ya", &[""]),
+    ("yê", &["n", "t"]),
+    ("ơ", &[""]),
+];
+
+/// A syllable: onset, rhyme and tone
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Syllable {
+    /// Onset in default spelling (see [`ONSETS`])
+    pub onset: &'static str,
+    /// Whether the rhyme starts with a medial (o/u glide)
+    pub medial: bool,
+    /// Vowel nucleus
+    pub nucleus: &'static str,
+    /// Final consonant or semivowel ("" if none)
+    pub coda: &'static str,
+    /// Tone
+    pub tone: ToneIndex,
+}
+
+impl Syllable {
+    /// Every legal syllable, each written form exactly once
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use vietime_core::models::syllable::Syllable;
+    ///
+    /// let all: Vec<String> = Syllable::all().iter().map(|s| s.to_string()).collect();
+    /// assert!(all.contains(&"người".to_string()));
+    /// assert!(all.contains(&"quốc".to_string()));
+    /// assert!(!all.contains(&"quốc".replace('ố', "ồ")));
+    /// ```
+    pub fn all() -> Vec<Syllable> {
+        let mut syllables = Vec::new();
+        for onset in ONSETS {
+            let rhymes = PLAIN_RHYMES
+                .iter()
+                .map(|rhyme| (false, rhyme))
+                .chain(MEDIAL_RHYMES.iter().map(|rhyme| (true, rhyme)));
+            for (medial, &(nucleus, codas)) in rhymes {
+                for &coda in codas {
+                    for tone in ToneIndex::ALL {
+                        let syllable = Syllable {
+                            onset,
+                            medial,
+                            nucleus,
+                            coda,
+                            tone,
+                        };
+                        if syllable.is_valid() {
+                            syllables.push(syllable);
+                        }
+                    }
+                }
+            }
+        }
+        syllables
+    }
+
+    /// Parse a written word (any case, any tone placement)
+    ///
+    /// Accepts old-style tone placement (hòa, thúy), y for i after an
+    /// onset (lý, kỳ) and i for y in quy (quí); the result renders in the
+    /// new style (hoà, thuý, lí, kì, quý).
+    /// Returns `None` for anything that is not a Vietnamese syllable.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use vietime_core::models::syllable::Syllable;
+    ///
+    /// assert_eq!(Syllable::parse("Hòa").unwrap().to_string(), "hoà");
+    /// assert_eq!(Syllable::parse("NGƯỜI").unwrap().to_string(), "người");
+    /// assert_eq!(Syllable::parse("quí").unwrap().to_string(), "quý");
+    /// assert!(Syllable::parse("hello").is_none());
+    /// ```
+    pub fn parse(word: &str) -> Option<Syllable> {
+        let mut tone = ToneIndex::None;
+        let mut toneless = String::with_capacity(word.len());
+        for c in VietnameseChar::to_nfc(word).chars() {
+            let c = VietnameseChar::to_lower(c);
+            match VietnameseChar::get_tone_index(c) {
+                ToneIndex::None => {}
+                _ if tone.is_toned() => return None,
+                toned => tone = toned,
+            }
+            toneless.push(VietnameseChar::apply_tone(c, ToneIndex::None));
+        }
+
+        let rhymes = toneless_forms();
+        let found = rhymes.get(&toneless).or_else(|| {
+            // quy written with i: quí, quì
+            if toneless == "qui" {
+                return rhymes.get("quy");
+            }
+            // Old i/y convention: lý, kỳ, mỹ
+            let onset = toneless.strip_suffix('y')?;
+            (!onset.is_empty() && !onset.ends_with('u'))
+                .then(|| rhymes.get(&format!("{}i", onset)))
+                .flatten()
+        })?;
+        let syllable = Syllable { tone, ..*found };
+        syllable.is_valid().then_some(syllable)
+    }
+
+    /// Check the combination is legal
+    pub fn is_valid(&self) -> bool {
+        let table: &[(&str, &[&str])] = if self.medial {
+            &MEDIAL_RHYMES
+        } else {
+            &PLAIN_RHYMES
+        };
+        let rhyme_known = table
+            .iter()
+            .any(|(nucleus, codas)| *nucleus == self.nucleus && codas.contains(&self.coda));
+        if !rhyme_known || !ONSETS.contains(&self.onset) {
+            return false;
+        }
+
+        // qu is q + medial (or the u of uô: quốc); other onsets only take a
+        // medial if listed
+        let qu_plain = self.onset == "qu" && !self.medial && self.nucleus != "uô";
+        if qu_plain || (self.medial && !MEDIAL_ONSETS.contains(&self.onset)) {
+            return false;
+        }
+
+        // y alone only without onset (ý, ỷ); elsewhere it is spelled i
+        if !self.medial && self.nucleus == "y" && !self.onset.is_empty() {
+            return false;
+        }
+
+        // gi absorbs a following i: gi + ia = gi + a, gi + iu = gi + u,
+        // gi + iêt = gi + êt (written giết either way; count it once as iê)
+        if self.onset == "gi" {
+            let as_ie = Syllable {
+                nucleus: "iê",
+                ..*self
+            };
+            if self.nucleus == "ia"
+                || (self.nucleus == "i" && self.coda == "u")
+                || (self.nucleus == "ê" && as_ie.is_valid())
+            {
+                return false;
+            }
+        }
+
+        // Entering tones
+        if matches!(self.coda, "c" | "ch" | "p" | "t") {
+            return matches!(self.tone, ToneIndex::Acute | ToneIndex::Dot);
+        }
+        true
+    }
+
+    /// Written rhyme without tone (hoa -> "oa", quê -> "uê")
+    pub fn rhyme(&self) -> String {
+        let mut rhyme = String::new();
+        if self.medial {
+            let glide = if matches!(self.nucleus.chars().next(), Some('a' | 'ă' | 'e')) {
+                'o'
+            } else {
+                'u'
+            };
+            rhyme.push(glide);
+        }
+        rhyme.push_str(self.nucleus);
+        rhyme.push_str(self.coda);
+        rhyme
+    }
+
+    /// Index within [`rhyme`](Self::rhyme) of the vowel carrying the tone
+    ///
+    /// The main vowel of the nucleus: the second letter of iê, uô, ươ, oo
+    /// (tiếng, muốn), the first of ia, ua, ưa, ya (mía, múa).
+    pub fn tone_position(&self) -> usize {
+        let medial = usize::from(self.medial);
+        let second =
+            self.nucleus.chars().count() == 2 && !matches!(self.nucleus, "ia" | "ua" | "ưa" | "ya");
+        medial + usize::from(second)
+    }
+
+    /// Spell the onset for a written rhyme
+    fn spell(&self, rhyme: &mut Vec<char>) -> String {
+        let front = matches!(
+            rhyme.first().map(|&c| VietnameseChar::get_base_vowel(c)),
+            Some('i' | 'e' | 'y')
+        );
+        match self.onset {
+            "c" if front => "k".to_string(),
+            "g" if front => "gh".to_string(),
+            "ng" if front => "ngh".to_string(),
+            // q + medial u
+            "qu" => {
+                rhyme.remove(0);
+                "qu".to_string()
+            }
+            // gi + i... is written with a single i (gìn, giếng)
+            "gi" if VietnameseChar::get_base_vowel(rhyme[0]) == 'i' => "g".to_string(),
+            // iê without onset is written yê (yên, yêu)
+            "" if self.nucleus == "iê" => {
+                rhyme[0] = 'y';
+                String::new()
+            }
+            onset => onset.to_string(),
+        }
+    }
+}
+
+/// Written form without tone -> syllable (any tone)
+fn toneless_forms() -> &'static HashMap<String, Syllable> {
+    static FORMS: OnceLock<HashMap<String, Syllable>> = OnceLock::new();
+    FORMS.get_or_init(|| {
+        let mut forms = HashMap::new();
+        for syllable in Syllable::all() {
+            let toneless = Syllable {
+                tone: ToneIndex::None,
+                ..syllable
+            };
+            forms.entry(toneless.to_string()).or_insert(syllable);
+        }
+        forms
+    })
+}
+
+impl fmt::Display for Syllable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut rhyme: Vec<char> = self.rhyme().chars().collect();
+        let pos = self.tone_position();
+        rhyme[pos] = VietnameseChar::apply_tone(rhyme[pos], self.tone);
+
+        let onset = self.spell(&mut rhyme);
+        write!(f, "{}", onset)?;
+        rhyme.iter().try_for_each(|c| write!(f, "{}", c))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn syllable(
+        onset: &'static str,
+        medial: bool,
+        nucleus: &'static str,
+        coda: &'static str,
+        tone: ToneIndex,
+    ) -> String {
+        Syllable {
+            onset,
+            medial,
+            nucleus,
+            coda,
+            tone,
+        }
+        .to_string()
+    }
+
+    #[test]
+    fn test_spelling_rules() {
+        assert_eq!(syllable("c", false, "i", "m", ToneIndex::None), "kim");
+        assert_eq!(
+            syllable("ng", false, "iê", "ng", ToneIndex::None),
+            "nghiêng"
+        );
+        assert_eq!(syllable("qu", true, "a", "", ToneIndex::Acute), "quá");
+        assert_eq!(syllable("qu", true, "y", "", ToneIndex::Acute), "quý");
+        assert_eq!(syllable("gi", false, "i", "n", ToneIndex::Grave), "gìn");
+        assert_eq!(syllable("gi", false, "iê", "ng", ToneIndex::Acute), "giếng");
+        assert_eq!(syllable("", false, "iê", "u", ToneIndex::None), "yêu");
+    }
+
+    #[test]
+    fn test_tone_position() {
+        assert_eq!(syllable("h", true, "a", "", ToneIndex::Grave), "hoà");
+        assert_eq!(syllable("ng", false, "ươ", "i", ToneIndex::Grave), "người");
+        assert_eq!(syllable("m", false, "ua", "", ToneIndex::Acute), "múa");
+        assert_eq!(syllable("kh", true, "ya", "", ToneIndex::Acute), "khuýa");
+        assert_eq!(syllable("h", true, "y", "t", ToneIndex::Acute), "huýt");
+    }
+
+    #[test]
+    fn test_parse() {
+        for syllable in Syllable::all() {
+            assert_eq!(Syllable::parse(&syllable.to_string()), Some(syllable));
+        }
+        assert_eq!(Syllable::parse("thúy").unwrap().to_string(), "thuý");
+        assert_eq!(Syllable::parse("Kỳ").unwrap().to_string(), "kì");
+        assert_eq!(Syllable::parse("quý").unwrap().to_string(), "quý");
+        assert_eq!(
+            Syllable::parse("tie\u{302}\u{301}ng").unwrap().to_string(),
+            "tiếng"
+        );
+        for word in ["", "càt", "xyz", "áá", "việtt", "kyi"] {
+            assert_eq!(Syllable::parse(word), None, "{}", word);
+        }
+    }
+
+    #[test]
+    fn test_all_unique_and_valid() {
+        let all = Syllable::all();
+        let written: std::collections::HashSet<String> =
+            all.iter().map(|s| s.to_string()).collect();
+        assert_eq!(written.len(), all.len());
+        assert!(all.len() > 15_000, "only {} syllables", all.len());
+        assert!(!written.contains("bát".replace('á', "à").as_str()));
+    }
+}
@@ -13,7 +13,7 @@ use phf::{phf_map, Map};
 use std::sync::OnceLock;
 
 /// Vietnamese tone marks
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum ToneIndex {
     /// No tone (nguyen)
     None = 0,
@@ -30,6 +30,16 @@ pub enum ToneIndex {
 }
 
 impl ToneIndex {
+    /// All tones in [`ToneIndex`] order
+    pub const ALL: [ToneIndex; 6] = [
+        Self::None,
+        Self::Grave,
+        Self::Acute,
+        Self::Hook,
+        Self::Tilde,
+        Self::Dot,
+    ];
+
     /// Check if this is a tone (not None)
     pub fn is_toned(self) -> bool {
         self != Self::None
@@ -46,6 +56,115 @@ impl ToneIndex {
             Self::None => Some('z'), // z removes tone
         }
     }
+
+    /// Get the VNI key for this tone
+    pub fn vni_key(self) -> Option<char> {
+        match self {
+            Self::Acute => Some('1'),
+            Self::Grave => Some('2'),
+            Self::Hook => Some('3'),
+            Self::Tilde => Some('4'),
+            Self::Dot => Some('5'),
+            Self::None => Some('0'), // 0 removes tone
+        }
+    }
+
+    /// Get the VIQR mark for this tone (VIQR has no tone removal key)
+    pub fn viqr_key(self) -> Option<char> {
+        match self {
+            Self::Acute => Some('\''),
+            Self::Grave => Some('`'),
+            Self::Hook => Some('?'),
+            Self::Tilde => Some('~'),
+            Self::Dot => Some('.'),
+            Self::None => None,
+        }
+    }
+
+    /// Get the Unicode combining mark for this tone
+    pub fn combining_mark(self) -> Option<char> {
+        match self {
+            Self::Acute => Some('\u{301}'),
+            Self::Grave => Some('\u{300}'),
+            Self::Hook => Some('\u{309}'),
+            Self::Tilde => Some('\u{303}'),
+            Self::Dot => Some('\u{323}'),
+            Self::None => None,
+        }
+    }
+
+    /// Get the tone of a Unicode combining mark
+    pub fn from_combining_mark(mark: char) -> Option<ToneIndex> {
+        Self::ALL
+            .into_iter()
+            .find(|tone| tone.combining_mark() == Some(mark))
+    }
+}
+
+/// Letter modifiers (the marks other than tones)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Modifier {
+    /// Circumflex (mũ): â ê ô
+    Circumflex,
+    /// Breve (trăng): ă
+    Breve,
+    /// Horn (móc): ơ ư
+    Horn,
+    /// Stroke (gạch): đ
+    Stroke,
+}
+
+impl Modifier {
+    /// Get the Telex key adding this modifier to `base`
+    ///
+    /// Circumflex repeats the vowel (aa, ee, oo); breve and horn use w;
+    /// stroke repeats d.
+    pub fn telex_key(self, base: char) -> char {
+        match self {
+            Self::Circumflex => base,
+            Self::Breve | Self::Horn => 'w',
+            Self::Stroke => 'd',
+        }
+    }
+
+    /// Get the VNI key adding this modifier (6: â ê ô, 7: ơ ư, 8: ă, 9: đ)
+    pub fn vni_key(self) -> char {
+        match self {
+            Self::Circumflex => '6',
+            Self::Horn => '7',
+            Self::Breve => '8',
+            Self::Stroke => '9',
+        }
+    }
+
+    /// Get the VIQR mark for this modifier (stroke repeats d)
+    pub fn viqr_key(self) -> char {
+        match self {
+            Self::Circumflex => '^',
+            Self::Breve => '(',
+            Self::Horn => '+',
+            Self::Stroke => 'd',
+        }
+    }
+
+    /// Get the Unicode combining mark for this modifier
+    ///
+    /// None for the stroke: đ has no decomposed form.
+    pub fn combining_mark(self) -> Option<char> {
+        match self {
+            Self::Circumflex => Some('\u{302}'),
+            Self::Breve => Some('\u{306}'),
+            Self::Horn => Some('\u{31B}'),
+            Self::Stroke => None,
+        }
+    }
+
+    /// Get the modifier of a Unicode combining mark
+    pub fn from_combining_mark(mark: char) -> Option<Modifier> {
+        [Self::Circumflex, Self::Breve, Self::Horn]
+            .into_iter()
+            .find(|modifier| modifier.combining_mark() == Some(mark))
+    }
 }
 
 /// Vowel map: base vowel -> all tone variants
@@ -132,6 +251,74 @@ fn base_vowel_map() -> &'static Map<char, char> {
 pub struct VietnameseChar;
 
 impl VietnameseChar {
+    /// Lowercase form of a letter
+    ///
+    /// Unlike `char::to_ascii_lowercase`, this covers the Vietnamese letters
+    /// outside ASCII (Ê -> ê, Ợ -> ợ, Đ -> đ). Characters without a
+    /// single-char lowercase form are returned unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vietime_core::VietnameseChar;
+    ///
+    /// assert_eq!(VietnameseChar::to_lower('Ệ'), 'ệ');
+    /// assert_eq!(VietnameseChar::to_lower('Đ'), 'đ');
+    /// assert_eq!(VietnameseChar::to_lower('x'), 'x');
+    /// ```
+    pub fn to_lower(c: char) -> char {
+        let mut lower = c.to_lowercase();
+        match (lower.next(), lower.next()) {
+            (Some(l), None) => l,
+            _ => c,
+        }
+    }
+
+    /// Uppercase form of a letter (ư -> Ư, đ -> Đ)
+    ///
+    /// Characters without a single-char uppercase form are returned unchanged.
+    pub fn to_upper(c: char) -> char {
+        let mut upper = c.to_uppercase();
+        match (upper.next(), upper.next()) {
+            (Some(u), None) => u,
+            _ => c,
+        }
+    }
+
+    /// `letter` in the case of `model` (('ê', 'E') -> 'Ê')
+    pub fn with_case_of(letter: char, model: char) -> char {
+        if model.is_uppercase() {
+            Self::to_upper(letter)
+        } else {
+            Self::to_lower(letter)
+        }
+    }
+
+    /// Every vowel in every tone, and đ, each in lower and upper case
+    ///
+    /// These are the letters a Vietnamese charset has to cover beyond the
+    /// plain consonants.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vietime_core::VietnameseChar;
+    ///
+    /// let letters: Vec<char> = VietnameseChar::letters().collect();
+    /// assert_eq!(letters.len(), 146);
+    /// assert!(letters.contains(&'Ự') && letters.contains(&'đ') && letters.contains(&'y'));
+    /// ```
+    pub fn letters() -> impl Iterator<Item = char> {
+        let vowels = "aăâeêioôơuưy".chars().flat_map(|base| {
+            ToneIndex::ALL
+                .into_iter()
+                .map(move |tone| Self::apply_tone(base, tone))
+        });
+        std::iter::once('đ')
+            .chain(vowels)
+            .flat_map(|c| [c, Self::to_upper(c)])
+    }
+
     /// Check if character is a Vietnamese vowel
     ///
     /// # Examples
@@ -142,9 +329,8 @@ impl VietnameseChar {
     /// assert!(!vietime_core::VietnameseChar::is_vowel('d'));
     /// ```
     pub fn is_vowel(c: char) -> bool {
-        let base = Self::get_vowel_without_tone(c);
-        let lower = base.to_ascii_lowercase();
-        matches!(lower,
+        matches!(
+            Self::to_lower(Self::get_vowel_without_tone(c)),
             'a' | 'ă' | 'â' | 'e' | 'ê' | 'i' | 'o' | 'ô' | 'ơ' | 'u' | 'ư' | 'y'
         )
     }
@@ -181,6 +367,46 @@ impl VietnameseChar {
         c
     }
 
+    /// Get the base letter (without tone or modifier)
+    ///
+    /// Like [`get_base_vowel`](Self::get_base_vowel), but also maps đ to d.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// assert_eq!(vietime_core::VietnameseChar::get_base_letter('ờ'), 'o');
+    /// assert_eq!(vietime_core::VietnameseChar::get_base_letter('Đ'), 'D');
+    /// ```
+    pub fn get_base_letter(c: char) -> char {
+        match c {
+            'đ' => 'd',
+            'Đ' => 'D',
+            _ => Self::get_base_vowel(c),
+        }
+    }
+
+    /// Get the modifier of a letter, if any
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vietime_core::models::vietnamese::Modifier;
+    /// use vietime_core::VietnameseChar;
+    ///
+    /// assert_eq!(VietnameseChar::get_modifier('ậ'), Some(Modifier::Circumflex));
+    /// assert_eq!(VietnameseChar::get_modifier('Ư'), Some(Modifier::Horn));
+    /// assert_eq!(VietnameseChar::get_modifier('á'), None);
+    /// ```
+    pub fn get_modifier(c: char) -> Option<Modifier> {
+        match Self::get_vowel_without_tone(c) {
+            'â' | 'ê' | 'ô' | 'Â' | 'Ê' | 'Ô' => Some(Modifier::Circumflex),
+            'ă' | 'Ă' => Some(Modifier::Breve),
+            'ơ' | 'ư' | 'Ơ' | 'Ư' => Some(Modifier::Horn),
+            'đ' | 'Đ' => Some(Modifier::Stroke),
+            _ => None,
+        }
+    }
+
     /// Get the tone index of a character
     pub fn get_tone_index(c: char) -> ToneIndex {
         for (_base, variants) in vowel_map().entries() {
@@ -212,6 +438,189 @@ impl VietnameseChar {
         }
         vowel
     }
+
+    /// Add a modifier to a letter, keeping its tone and case
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vietime_core::models::vietnamese::Modifier;
+    /// use vietime_core::VietnameseChar;
+    ///
+    /// assert_eq!(VietnameseChar::apply_modifier('ố', Modifier::Horn), Some('ớ'));
+    /// assert_eq!(VietnameseChar::apply_modifier('D', Modifier::Stroke), Some('Đ'));
+    /// assert_eq!(VietnameseChar::apply_modifier('i', Modifier::Breve), None);
+    /// ```
+    pub fn apply_modifier(letter: char, modifier: Modifier) -> Option<char> {
+        let lower = match (Self::to_lower(Self::get_base_letter(letter)), modifier) {
+            ('a', Modifier::Circumflex) => 'â',
+            ('a', Modifier::Breve) => 'ă',
+            ('e', Modifier::Circumflex) => 'ê',
+            ('o', Modifier::Circumflex) => 'ô',
+            ('o', Modifier::Horn) => 'ơ',
+            ('u', Modifier::Horn) => 'ư',
+            ('d', Modifier::Stroke) => 'đ',
+            _ => return None,
+        };
+        let tone = Self::get_tone_index(letter);
+        Some(Self::with_case_of(Self::apply_tone(lower, tone), letter))
+    }
+
+    /// Check if a character is a combining tone or modifier mark
+    pub fn is_combining_mark(c: char) -> bool {
+        ToneIndex::from_combining_mark(c).is_some() || Modifier::from_combining_mark(c).is_some()
+    }
+
+    /// Decompose a letter into base + combining marks (Unicode NFD)
+    ///
+    /// Marks are in canonical order: horn, then dot below, then the others
+    /// (ệ is e + dot + circumflex, ợ is o + horn + dot). đ does not decompose.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vietime_core::VietnameseChar;
+    ///
+    /// assert_eq!(VietnameseChar::decompose('ế'), "e\u{302}\u{301}");
+    /// assert_eq!(VietnameseChar::decompose('Ệ'), "E\u{323}\u{302}");
+    /// assert_eq!(VietnameseChar::decompose('ợ'), "o\u{31B}\u{323}");
+    /// assert_eq!(VietnameseChar::decompose('đ'), "đ");
+    /// ```
+    pub fn decompose(c: char) -> String {
+        let tone = Self::get_tone_index(c).combining_mark();
+        let modifier = Self::get_modifier(c).and_then(Modifier::combining_mark);
+        let mut text = String::new();
+        text.push(if modifier.is_some() {
+            Self::get_base_vowel(c)
+        } else {
+            Self::get_vowel_without_tone(c)
+        });
+        match (modifier, tone) {
+            (Some(modifier), Some('\u{323}')) if modifier != '\u{31B}' => {
+                text.push('\u{323}');
+                text.push(modifier);
+            }
+            _ => text.extend(modifier.into_iter().chain(tone)),
+        }
+        text
+    }
+
+    /// Compose a base letter and combining marks, in any order
+    ///
+    /// None if a mark is not a Vietnamese tone or modifier, or does not fit
+    /// the letter (two tones, a breve on o, ...).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vietime_core::VietnameseChar;
+    ///
+    /// assert_eq!(VietnameseChar::compose('e', &['\u{301}', '\u{302}']), Some('ế'));
+    /// assert_eq!(VietnameseChar::compose('U', &['\u{31B}', '\u{323}']), Some('Ự'));
+    /// assert_eq!(VietnameseChar::compose('b', &['\u{301}']), None);
+    /// ```
+    pub fn compose(base: char, marks: &[char]) -> Option<char> {
+        let mut letter = base;
+        for &mark in marks {
+            letter = if let Some(tone) = ToneIndex::from_combining_mark(mark) {
+                if !Self::is_vowel(letter) || Self::get_tone_index(letter).is_toned() {
+                    return None;
+                }
+                Self::apply_tone(letter, tone)
+            } else {
+                let modifier = Modifier::from_combining_mark(mark)?;
+                if Self::get_modifier(letter).is_some() {
+                    return None;
+                }
+                Self::apply_modifier(letter, modifier)?
+            };
+        }
+        Some(letter)
+    }
+
+    /// Decompose every Vietnamese letter of `text` (Unicode NFD)
+    pub fn to_nfd(text: &str) -> String {
+        text.chars().map(Self::decompose).collect()
+    }
+
+    /// Compose every base + combining marks sequence of `text` (Unicode NFC)
+    ///
+    /// Marks that do not fit a Vietnamese letter are left combining.
+    pub fn to_nfc(text: &str) -> String {
+        let chars: Vec<char> = text.chars().collect();
+        let mut composed = String::with_capacity(text.len());
+        let mut i = 0;
+        while i < chars.len() {
+            let marks = chars[i + 1..]
+                .iter()
+                .take_while(|&&c| Self::is_combining_mark(c))
+                .count();
+            // Longest run of marks that fits the letter; the rest stay combining
+            let (letter, used) = (0..=marks)
+                .rev()
+                .find_map(|n| Self::compose(chars[i], &chars[i + 1..i + 1 + n]).map(|c| (c, n)))
+                .unwrap_or((chars[i], 0));
+            composed.push(letter);
+            i += 1 + used;
+        }
+        composed
+    }
+
+    /// Find the vowel that should carry the tone in a word
+    ///
+    /// Works on the last vowel group, leaving out the u of qu and the i of gi
+    /// (quý, giữa). Before a final consonant the tone goes on the last vowel
+    /// (tiếng, hoàng); otherwise on the second vowel of oa, oe, uy and pairs
+    /// ending in â, ă, ê, ô, ơ (hoà, huế, thuở), the first vowel of other
+    /// pairs (mía, tôi) and the middle vowel of triples (người, khuya).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vietime_core::VietnameseChar;
+    ///
+    /// assert_eq!(VietnameseChar::find_tone_position(&['t', 'ô', 'i']), Some(1));
+    /// assert_eq!(VietnameseChar::find_tone_position(&['t', 'o', 'a']), Some(2));
+    /// assert_eq!(VietnameseChar::find_tone_position(&['n', 'g', 'ư', 'ơ', 'i']), Some(3));
+    /// assert_eq!(VietnameseChar::find_tone_position(&['q', 'u', 'y']), Some(2));
+    /// assert_eq!(VietnameseChar::find_tone_position(&['t', 'h', 'n']), None);
+    /// ```
+    pub fn find_tone_position(word: &[char]) -> Option<usize> {
+        // Last consecutive vowel group: start..end
+        let end = word.iter().rposition(|&c| Self::is_vowel(c))? + 1;
+        let mut start = end - 1;
+        while start > 0 && Self::is_vowel(word[start - 1]) {
+            start -= 1;
+        }
+
+        let vowel = |i: usize| Self::to_lower(Self::get_vowel_without_tone(word[i]));
+
+        // The u of qu and the i of gi belong to the onset
+        if end - start > 1 && start > 0 {
+            let onset = Self::to_lower(word[start - 1]);
+            if (onset == 'q' && vowel(start) == 'u') || (onset == 'g' && vowel(start) == 'i') {
+                start += 1;
+            }
+        }
+
+        // A final consonant follows: tone on the main vowel, which is last
+        if end < word.len() {
+            return Some(end - 1);
+        }
+
+        match end - start {
+            1 => Some(start),
+            2 => {
+                let (first, second) = (vowel(start), vowel(start + 1));
+                // ươ without a final is still being typed (mưa -> mươ -> mương)
+                let second_is_main = matches!(second, 'â' | 'ă' | 'ê' | 'ô')
+                    || (second == 'ơ' && first != 'ư')
+                    || matches!((first, second), ('o', 'a') | ('o', 'e') | ('u', 'y'));
+                Some(if second_is_main { start + 1 } else { start })
+            }
+            _ => Some(start + 1),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -264,4 +673,44 @@ mod tests {
         assert_eq!(VietnameseChar::apply_tone('e', ToneIndex::Tilde), 'ẽ');
         assert_eq!(VietnameseChar::apply_tone('ê', ToneIndex::Hook), 'ể');
     }
+
+    #[test]
+    fn test_decompose_compose_every_letter() {
+        for base in "aăâeêioôơuưyAĂÂEÊIOÔƠUƯYđĐ".chars() {
+            for tone in ToneIndex::ALL {
+                let letter = VietnameseChar::apply_tone(base, tone);
+                let nfd = VietnameseChar::decompose(letter);
+                let mut chars = nfd.chars();
+                let first = chars.next().unwrap();
+                let marks: Vec<char> = chars.collect();
+                assert!(first.is_ascii() || first == base, "{}", letter);
+                assert_eq!(
+                    VietnameseChar::compose(first, &marks),
+                    Some(letter),
+                    "{}",
+                    letter
+                );
+                assert_eq!(VietnameseChar::to_nfc(&nfd), letter.to_string());
+            }
+        }
+        assert_eq!(
+            VietnameseChar::to_nfd("Người"),
+            "Ngu\u{31B}o\u{31B}\u{300}i"
+        );
+        assert_eq!(
+            VietnameseChar::to_nfc("x\u{301}a\u{301}\u{300}"),
+            "x\u{301}á\u{300}"
+        );
+    }
+
+    #[test]
+    fn test_modifier_keys() {
+        assert_eq!(Modifier::Circumflex.telex_key('o'), 'o');
+        assert_eq!(Modifier::Horn.telex_key('u'), 'w');
+        assert_eq!(Modifier::Horn.vni_key(), '7');
+        assert_eq!(Modifier::Breve.vni_key(), '8');
+        assert_eq!(Modifier::Breve.viqr_key(), '(');
+        assert_eq!(ToneIndex::Dot.vni_key(), Some('5'));
+        assert_eq!(ToneIndex::None.viqr_key(), None);
+    }
 }
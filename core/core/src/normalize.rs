@@ -0,0 +1,397 @@
+//! # Normalisation
+//!
+//! Re-renders every Vietnamese syllable of a text in one canonical form,
+//! for documents pieced together from different authors and tools.
+//!
+//! Each word is parsed with [`Syllable::parse`], which accepts either tone
+//! placement, NFC or NFD and any case, and written out again with the
+//! syllable's spelling rules and the chosen settings:
+//!
+//! - [`ToneStyle`]: hoà, thuý (new) or hòa, thúy (old)
+//! - [`IySpelling`]: lí, kĩ or lý, kỹ, or as the author wrote it
+//! - [`Composition`]: precomposed (NFC) or decomposed (NFD) letters
+//! - optionally, a legacy charset whose stray fragments are decoded
+//!   (`"Ti\u{D5}ng"`, TCVN3 shown as Latin-1, becomes "Tiếng")
+//!
+//! Words that are not Vietnamese syllables are left exactly as they are,
+//! and so are URLs and e-mail addresses.
+//!
+//! ```
+//! use vietime_core::models::charset::Charset;
+//! use vietime_core::normalize::{IySpelling, Normalizer, ToneStyle};
+//!
+//! let normalizer = Normalizer::new();
+//! assert_eq!(normalizer.normalize("Hòa thúy kỹ, quí khách: hello"), "Hoà thuý kỹ, quí khách: hello");
+//!
+//! let old = Normalizer::new().with_tone_style(ToneStyle::Old).with_iy_spelling(IySpelling::Y);
+//! assert_eq!(old.normalize("hoà thuý kĩ lí"), "hòa thúy kỹ lý");
+//!
+//! let legacy = Normalizer::new().with_legacy_charset(Some(Charset::Tcvn3));
+//! assert_eq!(legacy.normalize("Ti\u{D5}ng Anh"), "Tiếng Anh");
+//! ```
+
+use crate::models::charset::Charset;
+use crate::models::syllable::Syllable;
+use crate::models::vietnamese::{ToneIndex, VietnameseChar};
+
+/// Onsets written with y instead of i by the older convention (hy, kỳ, lý,
+/// mỹ, tỷ); `c` is spelled k before i
+const Y_ONSETS: [&str; 5] = ["h", "c", "l", "m", "t"];
+
+/// Where the tone goes in open oa, oe and uy
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ToneStyle {
+    /// On the main vowel (hoà, hoè, thuý), as [`Syllable`] renders
+    #[default]
+    New,
+    /// On the glide (hòa, hòe, thúy)
+    Old,
+}
+
+/// i or y at the end of open syllables like lí/lý, kĩ/kỹ and quí/quý
+///
+/// Words without a tone are respelled too (hy vọng -> hi vọng), unless
+/// they are plain ASCII with no Vietnamese word next to them: hi, my and
+/// by on their own or among English words are kept as written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum IySpelling {
+    /// As each word was written
+    #[default]
+    Keep,
+    /// i after every onset (lí, kĩ, sĩ, quí)
+    I,
+    /// y after h, k, l, m, t and qu (hỷ, kỹ, lý, mỹ, tỷ, quý); i elsewhere
+    /// is kept (sĩ)
+    Y,
+}
+
+/// How letters with marks are stored
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Composition {
+    /// One character per letter (Unicode NFC)
+    #[default]
+    Precomposed,
+    /// Base letter + combining marks (Unicode NFD)
+    Decomposed,
+}
+
+/// Re-renders Vietnamese syllables in a canonical form
+#[derive(Debug, Clone, Copy)]
+pub struct Normalizer {
+    tone_style: ToneStyle,
+    iy_spelling: IySpelling,
+    composition: Composition,
+    legacy: Option<Charset>,
+}
+
+impl Default for Normalizer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Normalizer {
+    /// New tone style, i/y kept, precomposed, no legacy decoding
+    pub fn new() -> Self {
+        Self {
+            tone_style: ToneStyle::default(),
+            iy_spelling: IySpelling::default(),
+            composition: Composition::default(),
+            legacy: None,
+        }
+    }
+
+    /// Set where tones go in oa, oe and uy
+    pub fn with_tone_style(mut self, tone_style: ToneStyle) -> Self {
+        self.tone_style = tone_style;
+        self
+    }
+
+    /// Set the i/y convention
+    pub fn with_iy_spelling(mut self, iy_spelling: IySpelling) -> Self {
+        self.iy_spelling = iy_spelling;
+        self
+    }
+
+    /// Set the composition form of the syllables written
+    pub fn with_composition(mut self, composition: Composition) -> Self {
+        self.composition = composition;
+        self
+    }
+
+    /// Set the legacy charset whose fragments are decoded (None, the
+    /// default, to leave them)
+    ///
+    /// A word is decoded only when it is not a syllable as it stands and
+    /// becomes one decoded. Latin-1 letters of other languages can decode
+    /// to a syllable too, so only set this for text known to hold such
+    /// fragments.
+    pub fn with_legacy_charset(mut self, legacy: Option<Charset>) -> Self {
+        self.legacy = legacy;
+        self
+    }
+
+    /// Normalise every Vietnamese syllable of `text`
+    pub fn normalize(&self, text: &str) -> String {
+        // Words and the text between them (URLs and e-mail addresses whole)
+        let mut pieces: Vec<(&str, bool)> = Vec::new();
+        for chunk in text.split_inclusive(char::is_whitespace) {
+            if is_address(chunk) {
+                pieces.push((chunk, false));
+                continue;
+            }
+            let mut start = 0;
+            for (i, c) in chunk.char_indices() {
+                if is_word_char(c) {
+                    continue;
+                }
+                if start < i {
+                    pieces.push((&chunk[start..i], true));
+                }
+                pieces.push((&chunk[i..i + c.len_utf8()], false));
+                start = i + c.len_utf8();
+            }
+            if start < chunk.len() {
+                pieces.push((&chunk[start..], true));
+            }
+        }
+
+        let words: Vec<&str> = pieces
+            .iter()
+            .filter(|(_, is_word)| *is_word)
+            .map(|(piece, _)| *piece)
+            .collect();
+        let vietnamese: Vec<bool> = words.iter().map(|word| is_vietnamese(word)).collect();
+
+        let mut normalized = String::with_capacity(text.len());
+        let mut index = 0;
+        for (piece, is_word) in pieces {
+            if !is_word {
+                normalized.push_str(piece);
+                continue;
+            }
+            let in_vietnamese = (index > 0 && vietnamese[index - 1])
+                || vietnamese.get(index + 1).copied().unwrap_or(false);
+            self.push_word(piece, in_vietnamese, &mut normalized);
+            index += 1;
+        }
+        normalized
+    }
+
+    /// Normalised form of a single word, or None if it is not a syllable
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use vietime_core::normalize::Normalizer;
+    ///
+    /// assert_eq!(Normalizer::new().normalize_word("THÚY").as_deref(), Some("THUÝ"));
+    /// assert_eq!(Normalizer::new().normalize_word("hello"), None);
+    /// ```
+    pub fn normalize_word(&self, word: &str) -> Option<String> {
+        self.normalize_word_in(word, false)
+    }
+
+    /// [`Normalizer::normalize_word`] for a word next to a Vietnamese word
+    /// if `in_vietnamese`
+    fn normalize_word_in(&self, word: &str, in_vietnamese: bool) -> Option<String> {
+        if let Some(syllable) = Syllable::parse(word) {
+            let source = VietnameseChar::to_nfc(word);
+            let respell = in_vietnamese || !source.is_ascii();
+            return Some(self.render(syllable, &source, respell));
+        }
+        let legacy = self.legacy?;
+        if !word.chars().any(|c| !c.is_ascii() && u32::from(c) <= 0xFF) {
+            return None;
+        }
+        let decoded = legacy.from_host_text(word);
+        Syllable::parse(&decoded).map(|syllable| self.render(syllable, &decoded, true))
+    }
+
+    fn push_word(&self, word: &str, in_vietnamese: bool, normalized: &mut String) {
+        if let Some(syllable) = self.normalize_word_in(word, in_vietnamese) {
+            normalized.push_str(&syllable);
+            return;
+        }
+        // Latin-1 punctuation around a syllable («hòa»)
+        let mut letters = String::new();
+        for c in word.chars() {
+            if c.is_alphabetic() || VietnameseChar::is_combining_mark(c) {
+                letters.push(c);
+                continue;
+            }
+            normalized.push_str(
+                &self
+                    .normalize_word_in(&letters, in_vietnamese)
+                    .unwrap_or_else(|| letters.clone()),
+            );
+            letters.clear();
+            normalized.push(c);
+        }
+        normalized.push_str(
+            &self
+                .normalize_word_in(&letters, in_vietnamese)
+                .unwrap_or(letters),
+        );
+    }
+
+    /// Write `syllable` as the settings say, in the letter case of `source`
+    /// (its written form, precomposed)
+    ///
+    /// A syllable without a tone keeps its written i/y unless
+    /// `respell_untoned` (see [`IySpelling`]).
+    fn render(&self, syllable: Syllable, source: &str, respell_untoned: bool) -> String {
+        let mut chars: Vec<char> = syllable.to_string().chars().collect();
+        let last = chars.len() - 1;
+
+        // Open oa, oe, uy (not quy): old style tones the glide
+        let glide_toned = syllable.medial
+            && syllable.coda.is_empty()
+            && matches!(syllable.nucleus, "a" | "e" | "y")
+            && syllable.onset != "qu";
+        if self.tone_style == ToneStyle::Old && glide_toned {
+            chars[last] = VietnameseChar::apply_tone(chars[last], ToneIndex::None);
+            chars[last - 1] = VietnameseChar::apply_tone(chars[last - 1], syllable.tone);
+        }
+
+        // Open i after an onset (lí, lý) and quy (quí, quý)
+        let final_i = syllable.coda.is_empty()
+            && if syllable.medial {
+                syllable.onset == "qu" && syllable.nucleus == "y"
+            } else {
+                syllable.nucleus == "i" && !matches!(syllable.onset, "" | "gi")
+            };
+        if final_i {
+            let written_y = source
+                .chars()
+                .last()
+                .map(|c| VietnameseChar::to_lower(VietnameseChar::get_base_vowel(c)))
+                == Some('y');
+            let y = match self.iy_spelling {
+                _ if !syllable.tone.is_toned() && !respell_untoned => written_y,
+                IySpelling::Keep => written_y,
+                IySpelling::I => false,
+                IySpelling::Y => {
+                    written_y || syllable.onset == "qu" || Y_ONSETS.contains(&syllable.onset)
+                }
+            };
+            let letter = if y { 'y' } else { 'i' };
+            chars[last] = VietnameseChar::apply_tone(letter, syllable.tone);
+        }
+
+        let source: Vec<char> = source.chars().collect();
+        if source.len() == chars.len() {
+            for (c, model) in chars.iter_mut().zip(&source) {
+                *c = VietnameseChar::with_case_of(*c, *model);
+            }
+        }
+
+        let rendered: String = chars.into_iter().collect();
+        match self.composition {
+            Composition::Precomposed => rendered,
+            Composition::Decomposed => VietnameseChar::to_nfd(&rendered),
+        }
+    }
+}
+
+/// Whether a word is written with Vietnamese letters (vọng, đi, not hi)
+fn is_vietnamese(word: &str) -> bool {
+    let letters: String = word
+        .chars()
+        .filter(|&c| c.is_alphabetic() || VietnameseChar::is_combining_mark(c))
+        .collect();
+    !letters.is_ascii() && Syllable::parse(&letters).is_some()
+}
+
+/// Whether a whitespace-delimited chunk is a URL or an e-mail address
+fn is_address(chunk: &str) -> bool {
+    chunk.contains("://") || chunk.contains('@') || chunk.starts_with("www.")
+}
+
+/// Letters, their combining marks, and Latin-1 characters that may be
+/// legacy font codes
+fn is_word_char(c: char) -> bool {
+    c.is_alphabetic()
+        || VietnameseChar::is_combining_mark(c)
+        || (('\u{A1}'..='\u{FF}').contains(&c) && c != '\u{D7}' && c != '\u{F7}')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tone_styles() {
+        let new = Normalizer::new();
+        let old = Normalizer::new().with_tone_style(ToneStyle::Old);
+        for (old_form, new_form) in [("hòa", "hoà"), ("khỏe", "khoẻ"), ("thúy", "thuý")] {
+            assert_eq!(new.normalize(old_form), new_form);
+            assert_eq!(old.normalize(new_form), old_form);
+        }
+        // A final consonant or qu keeps the tone on the main vowel
+        assert_eq!(old.normalize("hoàng quý khuya"), "hoàng quý khuya");
+    }
+
+    #[test]
+    fn test_iy_spelling() {
+        let text = "lý kỹ mĩ hi tỉ sĩ sỹ ý quý quí";
+        let keep = Normalizer::new();
+        let i = Normalizer::new().with_iy_spelling(IySpelling::I);
+        let y = Normalizer::new().with_iy_spelling(IySpelling::Y);
+        assert_eq!(keep.normalize(text), text);
+        assert_eq!(i.normalize(text), "lí kĩ mĩ hi tỉ sĩ sĩ ý quí quí");
+        assert_eq!(y.normalize(text), "lý kỹ mỹ hy tỷ sĩ sỹ ý quý quý");
+    }
+
+    #[test]
+    fn test_iy_spelling_untoned() {
+        let text = "hy vọng, quy định, một ly nước";
+        let i = Normalizer::new().with_iy_spelling(IySpelling::I);
+        let y = Normalizer::new().with_iy_spelling(IySpelling::Y);
+        assert_eq!(Normalizer::new().normalize(text), text);
+        assert_eq!(i.normalize(text), "hi vọng, qui định, một li nước");
+        assert_eq!(y.normalize("hi vọng, qui định"), "hy vọng, quy định");
+        // Alone, an ASCII word may be English
+        assert_eq!(i.normalize("hy"), "hy");
+    }
+
+    #[test]
+    fn test_iy_spelling_leaves_english() {
+        let text = "hi, my name is Mi, mail mi@x.vn or see http://x.com/lí";
+        for iy_spelling in [IySpelling::I, IySpelling::Y] {
+            let normalizer = Normalizer::new().with_iy_spelling(iy_spelling);
+            assert_eq!(normalizer.normalize(text), text);
+        }
+        assert_eq!(
+            Normalizer::new()
+                .with_iy_spelling(IySpelling::I)
+                .normalize("bác sỹ Lý"),
+            "bác sĩ Lí"
+        );
+    }
+
+    #[test]
+    fn test_case_kept() {
+        let normalizer = Normalizer::new();
+        assert_eq!(normalizer.normalize("HÒA Hòa hÒa"), "HOÀ Hoà hOà");
+        assert_eq!(normalizer.normalize("«Hòa»"), "«Hoà»");
+    }
+
+    #[test]
+    fn test_legacy_fragments() {
+        // TCVN3 "Tiếng Việt" shown as Latin-1
+        let text = "Ti\u{D5}ng Vi\u{D6}t Zürich";
+        let tcvn3 = Normalizer::new().with_legacy_charset(Some(Charset::Tcvn3));
+        assert_eq!(tcvn3.normalize(text), "Tiếng Việt Zürich");
+        assert_eq!(Normalizer::new().normalize(text), text);
+    }
+
+    #[test]
+    fn test_qui_is_not_decoded() {
+        // quí and quì are quý and quỳ with i, not TCVN3 for quớ and quỡ
+        let tcvn3 = Normalizer::new().with_legacy_charset(Some(Charset::Tcvn3));
+        for normalizer in [Normalizer::new(), tcvn3] {
+            assert_eq!(normalizer.normalize("quí khách, quì"), "quí khách, quì");
+        }
+    }
+}
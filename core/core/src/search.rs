@@ -0,0 +1,258 @@
+//! # Accent-Insensitive Search
+//!
+//! Matches what users type against Vietnamese text that may carry more
+//! marks than the query: "nguyen" finds "Nguyễn", "duc" finds "Đức".
+//!
+//! Both sides are folded letter by letter with the character model
+//! ([`VietnameseChar`]) to the chosen [`Strictness`] and, unless asked
+//! otherwise, to lower case. Decomposed (NFD) letters fold like their
+//! precomposed forms. Matches are reported as byte ranges of the original
+//! text, so they can be highlighted or sliced directly.
+//!
+//! ```
+//! use vietime_core::search::{Matcher, Strictness};
+//!
+//! let name = "Nguyễn Văn Đức";
+//! let loose = Matcher::new(Strictness::IgnoreMarks);
+//! assert_eq!(loose.find(name, "van duc").map(|range| &name[range]), Some("Văn Đức"));
+//!
+//! let exact = Matcher::new(Strictness::Exact);
+//! assert!(exact.find("lệ", "lê").is_none());
+//! ```
+
+use std::ops::Range;
+
+use crate::models::vietnamese::{ToneIndex, VietnameseChar};
+
+/// Which marks a match may differ in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Strictness {
+    /// Tones and modifiers, đ matching d ("duc" finds "Đức")
+    #[default]
+    IgnoreMarks,
+    /// Tones only ("lê" finds "lệ" but not "le")
+    IgnoreTones,
+    /// Every mark must match ("lê" finds only "lê")
+    Exact,
+}
+
+/// Text folded for matching, each character with its span in the original
+#[derive(Debug, Default)]
+struct Folded {
+    chars: Vec<char>,
+    spans: Vec<Range<usize>>,
+}
+
+/// Accent-insensitive matcher
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Matcher {
+    strictness: Strictness,
+    case_sensitive: bool,
+}
+
+impl Matcher {
+    /// Case-insensitive matcher
+    pub fn new(strictness: Strictness) -> Self {
+        Self {
+            strictness,
+            case_sensitive: false,
+        }
+    }
+
+    /// Set whether letter case must match
+    pub fn with_case_sensitive(mut self, case_sensitive: bool) -> Self {
+        self.case_sensitive = case_sensitive;
+        self
+    }
+
+    /// The strictness this matcher folds to
+    pub fn strictness(&self) -> Strictness {
+        self.strictness
+    }
+
+    /// Search key for `text`: the folded form queries are compared against
+    ///
+    /// Store it in an index and fold queries with the same matcher.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use vietime_core::search::{Matcher, Strictness};
+    ///
+    /// assert_eq!(Matcher::new(Strictness::IgnoreMarks).key("Đức"), "duc");
+    /// assert_eq!(Matcher::new(Strictness::IgnoreTones).key("Đức"), "đưc");
+    /// ```
+    pub fn key(&self, text: &str) -> String {
+        self.fold(text).chars.into_iter().collect()
+    }
+
+    /// First occurrence of `needle` in `haystack`
+    pub fn find(&self, haystack: &str, needle: &str) -> Option<Range<usize>> {
+        let haystack = self.fold(haystack);
+        let needle = self.fold(needle).chars;
+        (0..=haystack.chars.len().checked_sub(needle.len())?)
+            .find(|&i| haystack.chars[i..].starts_with(&needle))
+            .map(|i| haystack.span(i, needle.len()))
+    }
+
+    /// Every non-overlapping occurrence of `needle` in `haystack`
+    ///
+    /// An empty needle matches nothing.
+    pub fn find_all(&self, haystack: &str, needle: &str) -> Vec<Range<usize>> {
+        let haystack = self.fold(haystack);
+        let needle = self.fold(needle).chars;
+        let mut matches = Vec::new();
+        if needle.is_empty() {
+            return matches;
+        }
+        let mut i = 0;
+        while i + needle.len() <= haystack.chars.len() {
+            if haystack.chars[i..].starts_with(&needle) {
+                matches.push(haystack.span(i, needle.len()));
+                i += needle.len();
+            } else {
+                i += 1;
+            }
+        }
+        matches
+    }
+
+    /// The start of `haystack` matching `needle`, if it begins with it
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use vietime_core::search::{Matcher, Strictness};
+    ///
+    /// let matcher = Matcher::new(Strictness::IgnoreMarks);
+    /// assert_eq!(matcher.match_prefix("Đặng Thu", "dang"), Some(0..7));
+    /// assert_eq!(matcher.match_prefix("Đặng Thu", "thu"), None);
+    /// ```
+    pub fn match_prefix(&self, haystack: &str, needle: &str) -> Option<Range<usize>> {
+        let haystack = self.fold(haystack);
+        let needle = self.fold(needle).chars;
+        haystack
+            .chars
+            .starts_with(&needle)
+            .then(|| haystack.span(0, needle.len()))
+    }
+
+    /// Fold `text`, composing each letter with its combining marks first
+    fn fold(&self, text: &str) -> Folded {
+        let chars: Vec<(usize, char)> = text.char_indices().collect();
+        let mut folded = Folded::default();
+        let mut i = 0;
+        while i < chars.len() {
+            let marks: Vec<char> = chars[i + 1..]
+                .iter()
+                .map(|&(_, c)| c)
+                .take_while(|&c| VietnameseChar::is_combining_mark(c))
+                .collect();
+            let end = chars
+                .get(i + 1 + marks.len())
+                .map_or(text.len(), |&(offset, _)| offset);
+            let span = chars[i].0..end;
+            // Longest run of marks that fits, as in VietnameseChar::to_nfc
+            let (letter, used) = (0..=marks.len())
+                .rev()
+                .find_map(|n| VietnameseChar::compose(chars[i].1, &marks[..n]).map(|c| (c, n)))
+                .unwrap_or((chars[i].1, 0));
+            for c in std::iter::once(letter).chain(marks[used..].iter().copied()) {
+                if let Some(c) = self.fold_char(c) {
+                    folded.chars.push(c);
+                    folded.spans.push(span.clone());
+                }
+            }
+            i += 1 + marks.len();
+        }
+        folded
+    }
+
+    /// Folded form of a letter, or None for a mark that is ignored
+    fn fold_char(&self, c: char) -> Option<char> {
+        let c = match self.strictness {
+            Strictness::IgnoreMarks if VietnameseChar::is_combining_mark(c) => return None,
+            Strictness::IgnoreMarks => VietnameseChar::get_base_letter(c),
+            Strictness::IgnoreTones if ToneIndex::from_combining_mark(c).is_some() => return None,
+            Strictness::IgnoreTones => VietnameseChar::get_vowel_without_tone(c),
+            Strictness::Exact => c,
+        };
+        Some(if self.case_sensitive {
+            c
+        } else {
+            VietnameseChar::to_lower(c)
+        })
+    }
+}
+
+impl Folded {
+    /// Original byte range of `len` folded characters from `start`
+    fn span(&self, start: usize, len: usize) -> Range<usize> {
+        match len {
+            0 => {
+                let offset = self.spans.get(start).map_or_else(
+                    || self.spans.last().map_or(0, |span| span.end),
+                    |span| span.start,
+                );
+                offset..offset
+            }
+            _ => self.spans[start].start..self.spans[start + len - 1].end,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strictness_levels() {
+        let text = "Lê Thị Lệ";
+        let find = |strictness, needle| Matcher::new(strictness).find_all(text, needle);
+        assert_eq!(find(Strictness::IgnoreMarks, "le"), [0..3, 10..14]);
+        assert_eq!(find(Strictness::IgnoreTones, "lê"), [0..3, 10..14]);
+        assert_eq!(find(Strictness::IgnoreTones, "le"), []);
+        assert_eq!(find(Strictness::Exact, "lê").len(), 1);
+        assert_eq!(
+            Matcher::new(Strictness::Exact).find(text, "lệ"),
+            Some(10..14)
+        );
+    }
+
+    #[test]
+    fn test_case() {
+        let matcher = Matcher::new(Strictness::Exact);
+        assert_eq!(matcher.find("ĐỨC", "đức"), Some(0..6));
+        assert_eq!(matcher.with_case_sensitive(true).find("ĐỨC", "đức"), None);
+    }
+
+    #[test]
+    fn test_decomposed_spans_cover_marks() {
+        let nfd = VietnameseChar::to_nfd("Việt Nam");
+        let matcher = Matcher::new(Strictness::Exact);
+        let range = matcher.find(&nfd, "việt").unwrap();
+        assert_eq!(&nfd[range], VietnameseChar::to_nfd("Việt"));
+        assert_eq!(matcher.key(&nfd), "việt nam");
+    }
+
+    #[test]
+    fn test_stray_marks() {
+        // A tone on a consonant cannot compose and stays a mark
+        let text = "b\u{301}a";
+        assert_eq!(Matcher::new(Strictness::Exact).key(text), text);
+        assert_eq!(Matcher::new(Strictness::IgnoreTones).key(text), "ba");
+        assert_eq!(
+            Matcher::new(Strictness::IgnoreMarks).find(text, "ba"),
+            Some(0..4)
+        );
+    }
+
+    #[test]
+    fn test_empty_needle() {
+        let matcher = Matcher::default();
+        assert_eq!(matcher.find("abc", ""), Some(0..0));
+        assert_eq!(matcher.match_prefix("", ""), Some(0..0));
+        assert_eq!(matcher.find("", "a"), None);
+        assert!(matcher.find_all("abc", "").is_empty());
+    }
+}
@@ -0,0 +1,131 @@
+//! # Testing Utilities
+//!
+//! A [`VirtualDocument`] stands in for the host text field: engine results
+//! are applied to it exactly as a host would (backspaces, selections, caret
+//! moves, inserts, pass-through keys), so tests can assert on the text the
+//! user actually sees rather than on `output_text`.
+//!
+//! ```
+//! use vietime_core::engines::telex::TelexEngine;
+//! use vietime_core::testing::type_keys;
+//!
+//! let mut engine = TelexEngine::new();
+//! assert_eq!(type_keys(&mut engine, "tieengs vieetj"), "tiếng việt");
+//! ```
+
+use crate::engines::actions::{EditAction, HostCapabilities, TextField};
+use crate::engines::strategy::OutputStrategy;
+use crate::engines::{EngineEvent, InputEngine, ProcessKeyResult};
+
+/// Backspace in keystroke strings passed to [`VirtualDocument::type_keys`]
+pub const BACKSPACE: char = '\u{8}';
+
+/// In-memory text field driven by an engine
+#[derive(Debug, Clone, Default)]
+pub struct VirtualDocument {
+    field: TextField,
+    caps: HostCapabilities,
+    strategy: OutputStrategy,
+}
+
+impl VirtualDocument {
+    /// Empty document on a keyboard hook host
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Document holding `text` with the caret at the end
+    pub fn with_text(text: &str) -> Self {
+        Self {
+            field: TextField::new(text),
+            ..Self::default()
+        }
+    }
+
+    /// Set the editing primitives the simulated host supports
+    pub fn with_capabilities(mut self, caps: HostCapabilities) -> Self {
+        self.caps = caps;
+        self
+    }
+
+    /// Set the output strategy used to turn results into actions
+    pub fn with_strategy(mut self, strategy: OutputStrategy) -> Self {
+        self.strategy = strategy;
+        self
+    }
+
+    /// Visible text
+    pub fn text(&self) -> String {
+        self.field.text()
+    }
+
+    /// Caret position in characters
+    pub fn caret(&self) -> usize {
+        self.field.caret
+    }
+
+    /// Selected character range, if any
+    pub fn selection(&self) -> Option<(usize, usize)> {
+        self.field
+            .anchor
+            .filter(|&anchor| anchor != self.field.caret)
+            .map(|anchor| (anchor.min(self.field.caret), anchor.max(self.field.caret)))
+    }
+
+    /// Apply a single editing primitive
+    pub fn apply_action(&mut self, action: &EditAction) {
+        self.field.apply(action);
+    }
+
+    /// Apply an engine result for a keystroke that produced `typed`
+    ///
+    /// A handled result is converted to actions for the host's capabilities;
+    /// otherwise the original key reaches the field unchanged.
+    pub fn apply_result(&mut self, result: &ProcessKeyResult, typed: &str) {
+        if result.handled {
+            for action in result.actions_with_strategy(&self.caps, self.strategy) {
+                self.field.apply(&action);
+            }
+        } else {
+            self.field.apply(&EditAction::Insert(typed.to_string()));
+        }
+    }
+
+    /// Type one key through `engine` (`BACKSPACE` for the Backspace key)
+    pub fn type_key<E: InputEngine + ?Sized>(&mut self, engine: &mut E, key: char) {
+        if key == BACKSPACE {
+            if !engine.process_backspace() {
+                self.field.apply(&EditAction::Backspace(1));
+            }
+            return;
+        }
+
+        let result = engine.process_key(key, key.is_uppercase());
+        self.apply_result(&result, &key.to_string());
+    }
+
+    /// Type every key of `keys` through `engine` and return the visible text
+    pub fn type_keys<E: InputEngine + ?Sized>(&mut self, engine: &mut E, keys: &str) -> String {
+        for key in keys.chars() {
+            self.type_key(engine, key);
+        }
+        self.text()
+    }
+
+    /// Move the caret as the user would (arrow keys, mouse) and tell the engine
+    pub fn move_cursor<E: InputEngine + ?Sized>(&mut self, engine: &mut E, offset: isize) {
+        self.field.apply(&EditAction::MoveCursor(offset));
+        engine.handle_event(&EngineEvent::CursorMoved);
+    }
+
+    /// Select `n` characters left of the caret as the user would and tell the engine
+    pub fn select_left<E: InputEngine + ?Sized>(&mut self, engine: &mut E, n: usize) {
+        self.field.apply(&EditAction::SelectLeft(n));
+        engine.handle_event(&EngineEvent::SelectionChanged);
+    }
+}
+
+/// Type `keys` through `engine` into an empty document and return the visible text
+pub fn type_keys<E: InputEngine + ?Sized>(engine: &mut E, keys: &str) -> String {
+    VirtualDocument::new().type_keys(engine, keys)
+}
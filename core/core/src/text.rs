@@ -0,0 +1,203 @@
+//! # Text Folding
+//!
+//! String-level helpers for turning Vietnamese into plain letters: search
+//! keys, usernames, file names and URL slugs.
+//!
+//! - [`strip_diacritics`] removes tones and modifiers but keeps đ, which is
+//!   a letter of its own rather than a marked d
+//! - [`to_ascii`] also folds đ to d, or spells the marks out as Telex
+//!   keystrokes ([`Encoder`]) or VIQR ([`convert`](crate::convert)); other
+//!   Latin letters lose their marks too (naïve → naive)
+//! - [`slugify`] lower-cases and joins the ASCII words with a separator
+//!
+//! Precomposed (NFC) and decomposed (NFD) input give the same result.
+//!
+//! ```
+//! use vietime_core::text::{slugify, strip_diacritics, to_ascii, AsciiStyle};
+//!
+//! assert_eq!(strip_diacritics("Nguyễn Văn Đức"), "Nguyen Van Đuc");
+//! assert_eq!(to_ascii("Nguyễn Văn Đức", AsciiStyle::Strip), "Nguyen Van Duc");
+//! assert_eq!(slugify("Nguyễn Văn Đức", '-'), "nguyen-van-duc");
+//! ```
+
+use std::sync::OnceLock;
+
+use phf::{phf_map, Map};
+
+use crate::convert::{convert_bytes, Encoding};
+use crate::encoder::{Encoder, InputMethod};
+use crate::models::vietnamese::VietnameseChar;
+
+/// How [`to_ascii`] writes Vietnamese letters
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AsciiStyle {
+    /// Drop the marks (Đức → Duc)
+    #[default]
+    Strip,
+    /// Telex keystrokes, tone at the end of the word (Đức → DDuwcs)
+    Telex,
+    /// VIQR marks after each vowel (Đức → DDu+'c), as written by
+    /// [`Encoding::Viqr`]
+    Viqr,
+}
+
+/// Latin letters outside Vietnamese, folded to ASCII
+fn latin_folds() -> &'static Map<char, &'static str> {
+    static MAP: OnceLock<Map<char, &'static str>> = OnceLock::new();
+    MAP.get_or_init(|| {
+        phf_map! {
+            'Ä' => "A", 'Å' => "A", 'Æ' => "AE", 'Ç' => "C", 'Ë' => "E", 'Î' => "I", 'Ï' => "I",
+            'Ð' => "D", 'Ñ' => "N", 'Ö' => "O", 'Ø' => "O", 'Û' => "U", 'Ü' => "U", 'Þ' => "TH",
+            'ß' => "ss", 'ä' => "a", 'å' => "a", 'æ' => "ae", 'ç' => "c", 'ë' => "e", 'î' => "i",
+            'ï' => "i", 'ð' => "d", 'ñ' => "n", 'ö' => "o", 'ø' => "o", 'û' => "u", 'ü' => "u",
+            'þ' => "th", 'ÿ' => "y", 'Ā' => "A", 'ā' => "a", 'Ą' => "A", 'ą' => "a", 'Ć' => "C",
+            'ć' => "c", 'Ĉ' => "C", 'ĉ' => "c", 'Ċ' => "C", 'ċ' => "c", 'Č' => "C", 'č' => "c",
+            'Ď' => "D", 'ď' => "d", 'Ē' => "E", 'ē' => "e", 'Ĕ' => "E", 'ĕ' => "e", 'Ė' => "E",
+            'ė' => "e", 'Ę' => "E", 'ę' => "e", 'Ě' => "E", 'ě' => "e", 'Ĝ' => "G", 'ĝ' => "g",
+            'Ğ' => "G", 'ğ' => "g", 'Ġ' => "G", 'ġ' => "g", 'Ģ' => "G", 'ģ' => "g", 'Ĥ' => "H",
+            'ĥ' => "h", 'Ħ' => "H", 'ħ' => "h", 'Ī' => "I", 'ī' => "i", 'Ĭ' => "I", 'ĭ' => "i",
+            'Į' => "I", 'į' => "i", 'İ' => "I", 'ı' => "i", 'Ĳ' => "IJ", 'ĳ' => "ij", 'Ĵ' => "J",
+            'ĵ' => "j", 'Ķ' => "K", 'ķ' => "k", 'ĸ' => "k", 'Ĺ' => "L", 'ĺ' => "l", 'Ļ' => "L",
+            'ļ' => "l", 'Ľ' => "L", 'ľ' => "l", 'Ŀ' => "L", 'ŀ' => "l", 'Ł' => "L", 'ł' => "l",
+            'Ń' => "N", 'ń' => "n", 'Ņ' => "N", 'ņ' => "n", 'Ň' => "N", 'ň' => "n", 'ŉ' => "n",
+            'Ŋ' => "N", 'ŋ' => "n", 'Ō' => "O", 'ō' => "o", 'Ŏ' => "O", 'ŏ' => "o", 'Ő' => "O",
+            'ő' => "o", 'Œ' => "OE", 'œ' => "oe", 'Ŕ' => "R", 'ŕ' => "r", 'Ŗ' => "R", 'ŗ' => "r",
+            'Ř' => "R", 'ř' => "r", 'Ś' => "S", 'ś' => "s", 'Ŝ' => "S", 'ŝ' => "s", 'Ş' => "S",
+            'ş' => "s", 'Š' => "S", 'š' => "s", 'Ţ' => "T", 'ţ' => "t", 'Ť' => "T", 'ť' => "t",
+            'Ŧ' => "T", 'ŧ' => "t", 'Ū' => "U", 'ū' => "u", 'Ŭ' => "U", 'ŭ' => "u", 'Ů' => "U",
+            'ů' => "u", 'Ű' => "U", 'ű' => "u", 'Ų' => "U", 'ų' => "u", 'Ŵ' => "W", 'ŵ' => "w",
+            'Ŷ' => "Y", 'ŷ' => "y", 'Ÿ' => "Y", 'Ź' => "Z", 'ź' => "z", 'Ż' => "Z", 'ż' => "z",
+            'Ž' => "Z", 'ž' => "z", 'ſ' => "s",
+        }
+    })
+}
+
+/// Remove tones and modifiers (ệ → e, Ư → U), keeping đ
+///
+/// Combining marks of decomposed text are removed as well; other
+/// characters are kept as they are.
+///
+/// # Example
+///
+/// ```
+/// use vietime_core::text::strip_diacritics;
+///
+/// assert_eq!(strip_diacritics("Tiếng Việt"), "Tieng Viet");
+/// assert_eq!(strip_diacritics("đường"), "đuong");
+/// ```
+pub fn strip_diacritics(text: &str) -> String {
+    text.chars()
+        .filter(|&c| !VietnameseChar::is_combining_mark(c))
+        .map(VietnameseChar::get_base_vowel)
+        .collect()
+}
+
+/// Fold text to ASCII
+///
+/// Vietnamese letters are written as `style` says. Other Latin letters
+/// lose their marks (ü → u, ñ → n, ß → ss); any other character outside
+/// ASCII is dropped.
+///
+/// # Example
+///
+/// ```
+/// use vietime_core::text::{to_ascii, AsciiStyle};
+///
+/// assert_eq!(to_ascii("Đường phố", AsciiStyle::Strip), "Duong pho");
+/// assert_eq!(to_ascii("Đường phố", AsciiStyle::Telex), "DDuwowngf phoos");
+/// assert_eq!(to_ascii("Đường phố", AsciiStyle::Viqr), "DDu+o+`ng pho^'");
+/// assert_eq!(to_ascii("naïve Zürich", AsciiStyle::Strip), "naive Zurich");
+/// ```
+pub fn to_ascii(text: &str, style: AsciiStyle) -> String {
+    let folded = match style {
+        AsciiStyle::Strip => text
+            .chars()
+            .filter(|&c| !VietnameseChar::is_combining_mark(c))
+            .map(VietnameseChar::get_base_letter)
+            .collect(),
+        AsciiStyle::Telex => Encoder::new(InputMethod::Telex).encode(&VietnameseChar::to_nfc(text)),
+        AsciiStyle::Viqr => {
+            let viqr = convert_bytes(text.as_bytes(), Encoding::Unicode, Encoding::Viqr).bytes;
+            String::from_utf8_lossy(&viqr).into_owned()
+        }
+    };
+    let mut ascii = String::with_capacity(folded.len());
+    for c in folded.chars() {
+        if c.is_ascii() {
+            ascii.push(c);
+        } else if let Some(letters) = latin_folds().get(&c) {
+            ascii.push_str(letters);
+        }
+    }
+    ascii
+}
+
+/// URL slug or file name: lowercase ASCII words joined by `separator`
+///
+/// Letters are stripped as in [`to_ascii`] with [`AsciiStyle::Strip`];
+/// every run of other characters becomes one separator, with none at
+/// either end.
+///
+/// # Example
+///
+/// ```
+/// use vietime_core::text::slugify;
+///
+/// assert_eq!(slugify("Hà Nội: 36 phố phường!", '-'), "ha-noi-36-pho-phuong");
+/// assert_eq!(slugify("Báo cáo (bản cuối).docx", '_'), "bao_cao_ban_cuoi_docx");
+/// ```
+pub fn slugify(text: &str, separator: char) -> String {
+    let ascii = to_ascii(text, AsciiStyle::Strip).to_ascii_lowercase();
+    let mut slug = String::new();
+    for word in ascii
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+    {
+        if !slug.is_empty() {
+            slug.push(separator);
+        }
+        slug.push_str(word);
+    }
+    slug
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strip_keeps_d_stroke() {
+        assert_eq!(strip_diacritics("ĐẶNG THỊ ÁNH"), "ĐANG THI ANH");
+        assert_eq!(strip_diacritics("khuỷu tay"), "khuyu tay");
+        assert_eq!(strip_diacritics("café ñ"), "cafe ñ");
+    }
+
+    #[test]
+    fn test_decomposed_input() {
+        let nfd = VietnameseChar::to_nfd("Nguyễn Đức");
+        assert_eq!(strip_diacritics(&nfd), "Nguyen Đuc");
+        assert_eq!(to_ascii(&nfd, AsciiStyle::Strip), "Nguyen Duc");
+        assert_eq!(to_ascii(&nfd, AsciiStyle::Telex), "Nguyeenx DDuwcs");
+    }
+
+    #[test]
+    fn test_to_ascii_drops_other_characters() {
+        assert_eq!(to_ascii("“Việt” – 100€", AsciiStyle::Strip), "Viet  100");
+    }
+
+    #[test]
+    fn test_to_ascii_folds_other_latin_letters() {
+        assert_eq!(to_ascii("naïve Zürich", AsciiStyle::Strip), "naive Zurich");
+        assert_eq!(to_ascii("Straße Łódź", AsciiStyle::Strip), "Strasse Lodz");
+        assert_eq!(to_ascii("Phở Zürich", AsciiStyle::Telex), "Phowr Zurich");
+        assert_eq!(to_ascii("Phở Zürich", AsciiStyle::Viqr), "Pho+? Zurich");
+        assert_eq!(slugify("Crème brûlée", '-'), "creme-brulee");
+    }
+
+    #[test]
+    fn test_slug_edges() {
+        assert_eq!(slugify("  --Đà Lạt--  ", '-'), "da-lat");
+        assert_eq!(slugify("", '-'), "");
+        assert_eq!(slugify("!!!", '-'), "");
+    }
+}
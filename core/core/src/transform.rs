@@ -0,0 +1,174 @@
+//! # Text Transforms
+//!
+//! Case conversions for Vietnamese text, matching the options of Unikey's
+//! toolkit so every front-end applies the same rules.
+//!
+//! Letters are converted one at a time with [`VietnameseChar`], so ư/Ư and
+//! đ/Đ are covered, and combining marks (NFD text) stay with their letter:
+//! a mark never starts a word, and it has no case of its own.
+//!
+//! ```
+//! use vietime_core::transform::Transform;
+//!
+//! assert_eq!(Transform::Upper.apply("đường"), "ĐƯỜNG");
+//! assert_eq!(Transform::Title.apply("nguyễn thị minh khai"), "Nguyễn Thị Minh Khai");
+//! assert_eq!(Transform::Sentence.apply("đi đâu? về nhà."), "Đi đâu? Về nhà.");
+//! ```
+
+use crate::models::vietnamese::VietnameseChar;
+use crate::text::{to_ascii, AsciiStyle};
+
+/// A whole-text transform
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Transform {
+    /// Every letter in upper case
+    Upper,
+    /// Every letter in lower case
+    Lower,
+    /// First letter of each syllable in upper case, the rest in lower case
+    /// ("bà rịa-VŨNG TÀU" → "Bà Rịa-Vũng Tàu")
+    Title,
+    /// First letter of the text and of each sentence in upper case; other
+    /// letters are kept as they are
+    Sentence,
+    /// Every letter in the opposite case
+    Toggle,
+    /// Marks removed and đ written as d ([`to_ascii`])
+    StripDiacritics,
+}
+
+impl Transform {
+    /// All transforms in toolkit order
+    pub const ALL: [Transform; 6] = [
+        Self::Upper,
+        Self::Lower,
+        Self::Title,
+        Self::Sentence,
+        Self::Toggle,
+        Self::StripDiacritics,
+    ];
+
+    /// Display name
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Upper => "UPPER CASE",
+            Self::Lower => "lower case",
+            Self::Title => "Title Case",
+            Self::Sentence => "Sentence case",
+            Self::Toggle => "tOGGLE cASE",
+            Self::StripDiacritics => "Strip diacritics",
+        }
+    }
+
+    /// Transform `text`
+    pub fn apply(self, text: &str) -> String {
+        match self {
+            Self::Upper => text.chars().map(VietnameseChar::to_upper).collect(),
+            Self::Lower => text.chars().map(VietnameseChar::to_lower).collect(),
+            Self::Title => title_case(text),
+            Self::Sentence => sentence_case(text),
+            Self::Toggle => text.chars().map(toggle).collect(),
+            Self::StripDiacritics => to_ascii(text, AsciiStyle::Strip),
+        }
+    }
+}
+
+/// Upper case at the start of each run of letters and digits
+fn title_case(text: &str) -> String {
+    let mut in_word = false;
+    text.chars()
+        .map(|c| {
+            if VietnameseChar::is_combining_mark(c) {
+                return c;
+            }
+            let starts_word = !in_word;
+            in_word = c.is_alphanumeric();
+            if starts_word {
+                VietnameseChar::to_upper(c)
+            } else {
+                VietnameseChar::to_lower(c)
+            }
+        })
+        .collect()
+}
+
+/// Upper case for the first letter of the text and the first after `.`,
+/// `!` or `?` and a space
+///
+/// Punctuation followed directly by something else (1.5, a.m) does not
+/// end a sentence.
+fn sentence_case(text: &str) -> String {
+    let mut sentence_start = true;
+    let mut after_stop = false;
+    text.chars()
+        .map(|c| {
+            if c.is_alphanumeric() && sentence_start {
+                sentence_start = false;
+                after_stop = false;
+                return VietnameseChar::to_upper(c);
+            }
+            match c {
+                '.' | '!' | '?' | '…' => after_stop = true,
+                _ if c.is_whitespace() => sentence_start |= after_stop,
+                // Quotes and brackets may open the next sentence
+                '"' | '\'' | '“' | '‘' | '(' | '[' if sentence_start => {}
+                _ if VietnameseChar::is_combining_mark(c) => {}
+                _ => after_stop = false,
+            }
+            c
+        })
+        .collect()
+}
+
+fn toggle(c: char) -> char {
+    if c.is_uppercase() {
+        VietnameseChar::to_lower(c)
+    } else {
+        VietnameseChar::to_upper(c)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_title_case() {
+        assert_eq!(Transform::Title.apply("ĐẶNG thu THẢO"), "Đặng Thu Thảo");
+        assert_eq!(Transform::Title.apply("bà rịa-VŨNG TÀU"), "Bà Rịa-Vũng Tàu");
+        assert_eq!(Transform::Title.apply("(ước mơ) 2lần"), "(Ước Mơ) 2lần");
+    }
+
+    #[test]
+    fn test_sentence_case() {
+        let text = "giá 1.5 triệu, v.v. rồi sao?  \"ừ\" đi! (thật) à… vâng";
+        assert_eq!(
+            Transform::Sentence.apply(text),
+            "Giá 1.5 triệu, v.v. Rồi sao?  \"Ừ\" đi! (Thật) à… Vâng"
+        );
+        assert_eq!(
+            Transform::Sentence.apply("Hà Nội. hà nội"),
+            "Hà Nội. Hà nội"
+        );
+    }
+
+    #[test]
+    fn test_nfd_text() {
+        let nfd = VietnameseChar::to_nfd("ước mơ đẹp");
+        for transform in [Transform::Upper, Transform::Title, Transform::Toggle] {
+            let expected = transform.apply("ước mơ đẹp");
+            assert_eq!(
+                VietnameseChar::to_nfc(&transform.apply(&nfd)),
+                expected,
+                "{}",
+                transform.name()
+            );
+        }
+        assert_eq!(Transform::StripDiacritics.apply(&nfd), "uoc mo dep");
+    }
+
+    #[test]
+    fn test_toggle() {
+        assert_eq!(Transform::Toggle.apply("Đường PHỐ"), "đƯỜNG phố");
+    }
+}
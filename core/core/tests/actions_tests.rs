@@ -0,0 +1,299 @@
+//! # Edit Action Integration Tests
+//!
+//! Tests for converting engine results into host edit actions and replaying
+//! them against a string.
+
+use vietime_core::engines::actions::{replay, total_cost, EditAction, HostCapabilities};
+use vietime_core::engines::telex::TelexEngine;
+use vietime_core::engines::{InputEngine, ProcessKeyResult};
+use vietime_core::testing::VirtualDocument;
+
+const SELECT_ONLY: HostCapabilities = HostCapabilities {
+    backspace: false,
+    select_left: true,
+    delete_surrounding: false,
+    move_cursor: false,
+};
+
+const NOTHING: HostCapabilities = HostCapabilities {
+    backspace: false,
+    select_left: false,
+    delete_surrounding: false,
+    move_cursor: false,
+};
+
+const BACKSPACE_AND_MOVE: HostCapabilities = HostCapabilities {
+    backspace: true,
+    select_left: false,
+    delete_surrounding: false,
+    move_cursor: true,
+};
+
+/// Type keys, applying each result's action list to a text field
+fn type_with_actions(keys: &str, caps: &HostCapabilities) -> String {
+    let mut engine = TelexEngine::new();
+    let mut text = String::new();
+    for key in keys.chars() {
+        let result = engine.process_key(key, false);
+        let actions = if result.handled {
+            result.actions(caps)
+        } else {
+            // Pass-through: the host lets the key itself through
+            vec![EditAction::Insert(key.to_string())]
+        };
+        text = replay(&text, &actions);
+    }
+    text
+}
+
+/// Test pass-through results need no actions
+#[test]
+fn test_pass_through_has_no_actions() {
+    let result = ProcessKeyResult::pass_through("a".to_string());
+    assert!(result.actions(&HostCapabilities::KEYBOARD_HOOK).is_empty());
+}
+
+/// Test keyboard hooks use Backspace + Insert
+#[test]
+fn test_keyboard_hook_actions() {
+    let result = ProcessKeyResult::replace(2, "ết".to_string(), "viết".to_string());
+
+    assert_eq!(
+        result.actions(&HostCapabilities::KEYBOARD_HOOK),
+        vec![
+            EditAction::Backspace(2),
+            EditAction::Insert("ết".to_string())
+        ]
+    );
+}
+
+/// Test input method hosts use a single delete-surrounding request
+#[test]
+fn test_input_method_actions() {
+    let result = ProcessKeyResult::replace(4, "ương".to_string(), "đường".to_string());
+    let actions = result.actions(&HostCapabilities::INPUT_METHOD);
+
+    assert_eq!(
+        actions,
+        vec![
+            EditAction::DeleteSurrounding {
+                before: 4,
+                after: 0
+            },
+            EditAction::Insert("ương".to_string()),
+        ]
+    );
+    assert_eq!(total_cost(&actions), 2);
+}
+
+/// Test delete-surrounding wins when the host offers everything
+#[test]
+fn test_cheapest_sequence_chosen() {
+    let all = HostCapabilities {
+        backspace: true,
+        select_left: true,
+        delete_surrounding: true,
+        move_cursor: true,
+    };
+    let result = ProcessKeyResult::replace(3, "ếng".to_string(), "tiếng".to_string());
+
+    assert_eq!(
+        result.actions(&all)[0],
+        EditAction::DeleteSurrounding {
+            before: 3,
+            after: 0
+        }
+    );
+}
+
+/// Test select-left + replace for hosts without Backspace
+#[test]
+fn test_select_left_actions() {
+    let result = ProcessKeyResult::replace(1, "ô".to_string(), "tô".to_string());
+
+    assert_eq!(
+        result.actions(&SELECT_ONLY),
+        vec![
+            EditAction::SelectLeft(1),
+            EditAction::Insert("ô".to_string())
+        ]
+    );
+}
+
+/// Test a change inside the word steps over the letters after it
+#[test]
+fn test_move_cursor_actions() {
+    let old: Vec<char> = "tiêng".chars().collect();
+    let new: Vec<char> = "tiếng".chars().collect();
+    let result = ProcessKeyResult::from_edit(&old, &new, 's');
+    let actions = result.actions(&BACKSPACE_AND_MOVE);
+
+    assert_eq!(
+        actions,
+        vec![
+            EditAction::MoveCursor(-2),
+            EditAction::Backspace(1),
+            EditAction::Insert("ế".to_string()),
+            EditAction::MoveCursor(2),
+        ]
+    );
+    assert_eq!(replay("tiêng", &actions), "tiếng");
+
+    // Without the capability the whole tail is rewritten
+    assert_eq!(
+        result.actions(&HostCapabilities::KEYBOARD_HOOK),
+        vec![
+            EditAction::Backspace(3),
+            EditAction::Insert("ếng".to_string())
+        ]
+    );
+}
+
+/// Test a change at the end of the word needs no caret move
+#[test]
+fn test_move_cursor_only_inside_word() {
+    let old: Vec<char> = "tha".chars().collect();
+    let new: Vec<char> = "thá".chars().collect();
+    let result = ProcessKeyResult::from_edit(&old, &new, 's');
+
+    assert_eq!(
+        result.actions(&BACKSPACE_AND_MOVE),
+        vec![
+            EditAction::Backspace(1),
+            EditAction::Insert("á".to_string())
+        ]
+    );
+}
+
+/// Test pure deletion never relies on a selection being replaced
+#[test]
+fn test_pure_deletion_without_select() {
+    let result = ProcessKeyResult {
+        handled: true,
+        output_text: None,
+        backspace_count: 2,
+        kept_suffix: 0,
+        current_buffer: String::new(),
+    };
+
+    assert_eq!(result.actions(&SELECT_ONLY), vec![EditAction::Backspace(2)]);
+    assert_eq!(result.actions(&NOTHING), vec![EditAction::Backspace(2)]);
+}
+
+/// Test insertion without deletion
+#[test]
+fn test_insert_only() {
+    let result = ProcessKeyResult::replace(0, " ".to_string(), String::new());
+
+    assert_eq!(
+        result.actions(&HostCapabilities::INPUT_METHOD),
+        vec![EditAction::Insert(" ".to_string())]
+    );
+}
+
+/// Test replaying every primitive
+#[test]
+fn test_replay_primitives() {
+    assert_eq!(replay("abc", &[EditAction::Backspace(2)]), "a");
+    assert_eq!(replay("abc", &[EditAction::Backspace(5)]), "");
+    assert_eq!(
+        replay(
+            "abc",
+            &[
+                EditAction::SelectLeft(2),
+                EditAction::Insert("XY".to_string())
+            ]
+        ),
+        "aXY"
+    );
+    assert_eq!(
+        replay(
+            "abc",
+            &[EditAction::SelectLeft(2), EditAction::Backspace(1)]
+        ),
+        "a"
+    );
+    assert_eq!(
+        replay(
+            "abc",
+            &[
+                EditAction::MoveCursor(-1),
+                EditAction::Backspace(1),
+                EditAction::Insert("X".to_string()),
+                EditAction::MoveCursor(1)
+            ]
+        ),
+        "aXc"
+    );
+    assert_eq!(
+        replay(
+            "abc",
+            &[
+                EditAction::MoveCursor(-9),
+                EditAction::Insert("X".to_string())
+            ]
+        ),
+        "Xabc"
+    );
+}
+
+/// Test primitives applied after the user moves the caret
+#[test]
+fn test_primitives_after_caret_move() {
+    let mut engine = TelexEngine::new();
+
+    let mut doc = VirtualDocument::with_text("abcd");
+    doc.move_cursor(&mut engine, -2);
+    doc.apply_action(&EditAction::DeleteSurrounding {
+        before: 1,
+        after: 1,
+    });
+    assert_eq!(doc.text(), "ad");
+
+    let mut doc = VirtualDocument::with_text("abc");
+    doc.move_cursor(&mut engine, -1);
+    doc.apply_action(&EditAction::Insert("X".to_string()));
+    assert_eq!(doc.text(), "abXc");
+
+    let mut doc = VirtualDocument::with_text("abc");
+    doc.move_cursor(&mut engine, -10);
+    doc.apply_action(&EditAction::Insert("X".to_string()));
+    assert_eq!(doc.text(), "Xabc");
+
+    // Moving the caret drops the selection
+    let mut doc = VirtualDocument::with_text("abc");
+    doc.select_left(&mut engine, 2);
+    doc.move_cursor(&mut engine, 1);
+    doc.apply_action(&EditAction::Insert("X".to_string()));
+    assert_eq!(doc.text(), "abXc");
+}
+
+/// Test every host type reconstructs the same text from a Telex session
+#[test]
+fn test_replay_engine_output_all_hosts() {
+    let cases = [
+        ("thees", "thế"),
+        ("tooif", "tồi"),
+        ("thaf thas", "thà thá"),
+        ("theess", "thes"),
+        ("tieengs vieetj", "tiếng việt"),
+        ("nguoiwf", "người"),
+    ];
+
+    for caps in [
+        HostCapabilities::KEYBOARD_HOOK,
+        HostCapabilities::INPUT_METHOD,
+        SELECT_ONLY,
+        BACKSPACE_AND_MOVE,
+    ] {
+        for (keys, expected) in cases {
+            assert_eq!(
+                type_with_actions(keys, &caps),
+                expected,
+                "{:?} with {:?}",
+                keys,
+                caps
+            );
+        }
+    }
+}
@@ -0,0 +1,150 @@
+//! # Letter Case Tests
+//!
+//! Every word is typed in lower, Title and UPPER case; the engines must
+//! give the same letters, modifiers and tone position in each.
+
+mod common;
+
+use std::sync::Arc;
+
+use vietime_core::clock::ManualClock;
+use vietime_core::encoder::{Encoder, InputMethod, TonePlacement};
+use vietime_core::engines::multitap::MultiTapEngine;
+use vietime_core::engines::telex::TelexEngine;
+use vietime_core::models::syllable::Syllable;
+use vietime_core::testing::type_keys;
+use vietime_core::{ToneIndex, VietnameseChar};
+
+use common::tap_word;
+
+/// Words whose letters are outside ASCII in upper case (Ê, Ơ, Ư, Đ, ...)
+const WORDS: &[&str] = &[
+    "việt",
+    "nam",
+    "đường",
+    "người",
+    "tiếng",
+    "quốc",
+    "giữa",
+    "khuya",
+    "thuở",
+    "xoong",
+    "ơn",
+    "ước",
+    "hoà",
+    "êm",
+    "ấy",
+    "ăn",
+    "rượu",
+    "nghiêng",
+    "khoẻ",
+    "thuý",
+];
+
+fn title(word: &str) -> String {
+    let mut chars = word.chars();
+    chars
+        .next()
+        .map(VietnameseChar::to_upper)
+        .into_iter()
+        .chain(chars)
+        .collect()
+}
+
+fn upper(word: &str) -> String {
+    word.chars().map(VietnameseChar::to_upper).collect()
+}
+
+/// lower, Title and UPPER forms of a word
+fn cases(word: &str) -> [String; 3] {
+    [word.to_string(), title(word), upper(word)]
+}
+
+/// Test the case helpers map every Vietnamese letter both ways
+#[test]
+fn test_case_mapping_every_letter() {
+    for base in "aăâeêioôơuưy".chars() {
+        for tone in ToneIndex::ALL {
+            let lower = VietnameseChar::apply_tone(base, tone);
+            let upper = VietnameseChar::to_upper(lower);
+
+            assert!(upper.is_uppercase(), "{}", lower);
+            assert_eq!(VietnameseChar::to_lower(upper), lower);
+            assert_eq!(
+                VietnameseChar::apply_tone(VietnameseChar::to_upper(base), tone),
+                upper
+            );
+            assert!(VietnameseChar::is_vowel(upper), "{}", upper);
+            assert_eq!(VietnameseChar::get_tone_index(upper), tone);
+            assert_eq!(
+                VietnameseChar::get_modifier(upper),
+                VietnameseChar::get_modifier(lower)
+            );
+            assert_eq!(VietnameseChar::with_case_of(lower, 'X'), upper);
+            assert_eq!(VietnameseChar::with_case_of(upper, 'x'), lower);
+        }
+    }
+    assert_eq!(VietnameseChar::to_upper('đ'), 'Đ');
+    assert_eq!(VietnameseChar::to_lower('Đ'), 'đ');
+}
+
+/// Test tone placement ignores case for every syllable
+#[test]
+fn test_tone_position_ignores_case() {
+    for syllable in Syllable::all() {
+        let word = syllable.to_string();
+        let lower: Vec<char> = word.chars().collect();
+        let expected = VietnameseChar::find_tone_position(&lower);
+        for form in &cases(&word)[1..] {
+            let chars: Vec<char> = form.chars().collect();
+            assert_eq!(
+                VietnameseChar::find_tone_position(&chars),
+                expected,
+                "{}",
+                form
+            );
+        }
+    }
+}
+
+/// Test Telex types every word in every case, tone after the word or the vowel
+#[test]
+fn test_telex_case_matrix() {
+    for placement in [TonePlacement::EndOfWord, TonePlacement::AfterVowel] {
+        let encoder = Encoder::new(InputMethod::Telex).with_tone_placement(placement);
+        for word in WORDS {
+            for form in cases(word) {
+                let keys = encoder.encode(&form);
+                assert_eq!(
+                    type_keys(&mut TelexEngine::new(), &keys),
+                    form,
+                    "keys {}",
+                    keys
+                );
+            }
+        }
+    }
+}
+
+/// Test lowercase Telex keys still apply to uppercase letters
+#[test]
+fn test_telex_mixed_case_keys() {
+    let mut engine = TelexEngine::new();
+    assert_eq!(type_keys(&mut engine, "VIEetj NAm"), "VIỆt NAm");
+    assert_eq!(type_keys(&mut TelexEngine::new(), "DDUwowngf"), "ĐƯờng");
+    assert_eq!(type_keys(&mut TelexEngine::new(), "ÊMz"), "EMZ");
+    assert_eq!(type_keys(&mut TelexEngine::new(), "THU}"), "THUƠ");
+    assert_eq!(type_keys(&mut TelexEngine::new(), "Ơss"), "Os");
+}
+
+/// Test multi-tap types every word in every case with Shift
+#[test]
+fn test_multitap_case_matrix() {
+    for word in WORDS {
+        for form in cases(word) {
+            let clock = ManualClock::new();
+            let mut engine = MultiTapEngine::with_clock(Arc::new(clock.clone()));
+            assert_eq!(tap_word(&mut engine, &clock, &form), form);
+        }
+    }
+}
@@ -0,0 +1,185 @@
+//! # Charset Output Tests
+//!
+//! Types through [`CharsetOutput`] into a virtual text field and checks the
+//! host sees each word in the legacy encoding, with every replacement
+//! deleting the right number of host characters.
+
+use vietime_core::encoder::{Encoder, InputMethod};
+use vietime_core::engines::actions::EditAction;
+use vietime_core::engines::charset::CharsetOutput;
+use vietime_core::engines::telex::TelexEngine;
+use vietime_core::engines::{InputEngine, KeyCode, KeyEvent};
+use vietime_core::models::charset::Charset;
+use vietime_core::models::syllable::Syllable;
+use vietime_core::testing::{type_keys, VirtualDocument};
+
+/// What the host shows for `text` in `charset`
+fn host(charset: Charset, text: &str) -> String {
+    text.chars()
+        .map(|c| {
+            charset
+                .host_text(c)
+                .expect("lowercase letters are in every charset")
+        })
+        .collect()
+}
+
+/// Test every syllable types in every charset, mid-word edits included
+#[test]
+fn test_every_syllable_in_every_charset() {
+    let encoder = Encoder::new(InputMethod::Telex);
+    for charset in Charset::ALL {
+        let mut engine = CharsetOutput::new(TelexEngine::new(), charset);
+        for syllable in Syllable::all() {
+            let word = syllable.to_string();
+            let keys = encoder.encode(&word);
+            engine.reset();
+            assert_eq!(
+                type_keys(&mut engine, &keys),
+                host(charset, &word),
+                "{} {}",
+                charset,
+                keys
+            );
+        }
+    }
+}
+
+/// Test known host text for a sentence
+#[test]
+fn test_sentence_in_each_charset() {
+    let keys = "Tieengs Vieetj cos daaus";
+    let expected = [
+        (Charset::Unicode, "Tiếng Việt có dấu"),
+        (
+            Charset::UnicodeCombining,
+            "Tie\u{302}\u{301}ng Vie\u{323}\u{302}t co\u{301} da\u{302}\u{301}u",
+        ),
+        (Charset::Tcvn3, "Ti\u{D5}ng Vi\u{D6}t c\u{E3} d\u{CA}u"),
+        (
+            Charset::VniWindows,
+            "Tie\u{E1}ng Vie\u{E4}t co\u{F9} da\u{E1}u",
+        ),
+        (Charset::Viscii, "Ti\u{AA}ng Vi\u{AE}t c\u{F3} d\u{A4}u"),
+        (Charset::Cp1258, "Tiê\u{301}ng Viê\u{323}t có dâ\u{301}u"),
+    ];
+    for (charset, text) in expected {
+        let mut engine = CharsetOutput::new(TelexEngine::new(), charset);
+        assert_eq!(type_keys(&mut engine, keys), text, "{}", charset);
+    }
+}
+
+/// Test the decoded host text is the Unicode text
+#[test]
+fn test_host_text_decodes_back() {
+    let keys = "dduwowngf mooxi ngayf vaanx nguyeenx, khoong phair nhuw thees";
+    let unicode = type_keys(&mut TelexEngine::new(), keys);
+    for charset in [Charset::Tcvn3, Charset::VniWindows, Charset::Viscii] {
+        let text = type_keys(&mut CharsetOutput::new(TelexEngine::new(), charset), keys);
+        let bytes: Vec<u8> = text.chars().map(|c| c as u8).collect();
+        assert_eq!(charset.decode(&bytes), unicode, "{}", charset);
+    }
+}
+
+/// Test Backspace events delete whole two-character letters
+#[test]
+fn test_backspace_event_in_vni() {
+    let mut engine = CharsetOutput::new(TelexEngine::new(), Charset::VniWindows);
+    let mut document = VirtualDocument::new();
+    document.type_keys(&mut engine, "dduwowngf");
+    assert_eq!(document.text(), "\u{F1}\u{F6}\u{F4}\u{F8}ng");
+
+    let backspace = KeyEvent::new(KeyCode::Backspace);
+    // ng: one character each, left to the host; ờ: both deleted by the engine
+    for _ in 0..3 {
+        let result = engine.process_key_event(&backspace);
+        if result.handled {
+            document.apply_result(&result, "");
+        } else {
+            document.apply_action(&EditAction::Backspace(1));
+        }
+    }
+    assert_eq!(document.text(), "\u{F1}\u{F6}");
+    assert_eq!(engine.get_buffer(), "\u{F1}\u{F6}");
+}
+
+/// Test NFD replacements delete every code point of the old letter
+#[test]
+fn test_nfd_backspace_counts() {
+    let mut engine = CharsetOutput::new(TelexEngine::new(), Charset::UnicodeCombining);
+    for key in "ngu".chars() {
+        engine.process_key(key, false);
+    }
+    // ngu -> ngư: u is one code point
+    let result = engine.process_key('w', false);
+    assert_eq!(result.backspace_count, 1);
+    assert_eq!(result.output_text.as_deref(), Some("u\u{31B}"));
+
+    for key in "owi".chars() {
+        engine.process_key(key, false);
+    }
+    // ngươi -> người: the ơi replaced takes 3 code points, ời takes 4
+    let result = engine.process_key('f', false);
+    assert_eq!(result.backspace_count, 3);
+    assert_eq!(result.output_text.as_deref(), Some("o\u{31B}\u{300}i"));
+    assert_eq!(engine.get_buffer(), "ngu\u{31B}o\u{31B}\u{300}i");
+}
+
+/// Test decomposed letters typed or pasted into a Unicode field keep working
+/// as letters, and are replaced with the right number of backspaces
+#[test]
+fn test_decomposed_input() {
+    let typed = |keys: &str| {
+        type_keys(
+            &mut CharsetOutput::new(TelexEngine::new(), Charset::Unicode),
+            keys,
+        )
+    };
+
+    // e + combining circumflex, then a tone key
+    assert_eq!(typed("tie\u{302}ngs"), "tiếng");
+    // Pasted NFD ệ (e + dot + circumflex), then a new tone
+    assert_eq!(typed("vie\u{323}\u{302}ts"), "viết");
+    // Pasted NFD ư, then Telex turns the o into ơ; the ư is left as pasted
+    assert_eq!(typed("ngu\u{31B}owif"), "ngu\u{31B}ời");
+    // A tone mark on a consonant stays a combining mark
+    assert_eq!(typed("ng\u{301}"), "ng\u{301}");
+}
+
+/// Test letters keep the form they were typed in until the engine changes them
+#[test]
+fn test_decomposed_letters_stay_decomposed() {
+    let mut engine = CharsetOutput::new(TelexEngine::new(), Charset::Unicode);
+    let text = type_keys(&mut engine, "ve\u{302}\u{301}t");
+    assert_eq!(text, "ve\u{302}\u{301}t");
+    assert_eq!(engine.engine().get_buffer(), "vết");
+
+    let result = engine.process_key('f', false);
+    assert_eq!(result.backspace_count, 4);
+    assert_eq!(result.output_text.as_deref(), Some("ềt"));
+}
+
+/// Test legacy-encoded keys from the host: CP1258 tones, VNI-Windows mark
+/// bytes and TCVN3 letter codes
+#[test]
+fn test_legacy_input() {
+    let cases = [
+        (Charset::Cp1258, "tiê\u{301}ngf", "tiê\u{300}ng"),
+        (Charset::VniWindows, "tie\u{E1}ngf", "tie\u{E0}ng"),
+        (Charset::Tcvn3, "ti\u{D5}ngf", "ti\u{D2}ng"),
+        (Charset::Viscii, "ti\u{AA}ngf", "ti\u{AB}ng"),
+    ];
+    for (charset, keys, expected) in cases {
+        let mut engine = CharsetOutput::new(TelexEngine::new(), charset);
+        assert_eq!(type_keys(&mut engine, keys), expected, "{}", charset);
+        assert_eq!(engine.engine().get_buffer(), "tiềng", "{}", charset);
+    }
+}
+
+/// Test a Unicode letter the document cannot hold is sent in the charset's form
+#[test]
+fn test_unicode_key_into_legacy_document() {
+    let mut engine = CharsetOutput::new(TelexEngine::new(), Charset::VniWindows);
+    assert_eq!(type_keys(&mut engine, "thư"), "th\u{F6}");
+    assert_eq!(engine.engine().get_buffer(), "thư");
+}
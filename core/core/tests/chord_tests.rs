@@ -0,0 +1,229 @@
+//! # Chord Engine Integration Tests
+//!
+//! Integration tests for the stenographic (chord) input engine.
+
+use vietime_core::engines::chord::{ChordEngine, ChordTheory, MAX_UNDO_STROKES};
+use vietime_core::engines::telex::TelexEngine;
+use vietime_core::engines::InputEngine;
+use vietime_core::ToneIndex;
+
+fn keys(stroke: &str) -> Vec<char> {
+    stroke.chars().collect()
+}
+
+/// Small theory used to test table lookup independently of the basic layout
+fn tiny_theory() -> ChordTheory {
+    ChordTheory::new("tn", "aeo", "sf")
+        .with_onset("t", "t")
+        .with_onset("n", "n")
+        .with_onset("tn", "nh")
+        .with_rhyme("a", "a")
+        .with_rhyme("e", "ê")
+        .with_rhyme("ao", "ao")
+        .with_tone("s", ToneIndex::Acute)
+        .with_tone("f", ToneIndex::Grave)
+        .with_tone("sf", ToneIndex::Dot)
+}
+
+/// Test one stroke produces one syllable
+#[test]
+fn test_stroke_produces_syllable() {
+    let mut engine = ChordEngine::with_theory(tiny_theory());
+
+    let result = engine.process_chord(&keys("ta"));
+
+    assert!(result.handled);
+    assert_eq!(result.output_text, Some("ta".to_string()));
+    assert_eq!(result.backspace_count, 0);
+    assert_eq!(engine.get_buffer(), "ta");
+}
+
+/// Test key order within a stroke does not matter
+#[test]
+fn test_stroke_order_independent() {
+    let theory = tiny_theory();
+
+    assert_eq!(theory.syllable(&keys("ntaos")), Some("nháo".to_string()));
+    assert_eq!(theory.syllable(&keys("soatn")), Some("nháo".to_string()));
+    assert_eq!(theory.stroke(&keys("soatn")), "tnaos");
+}
+
+/// Test tone chords and vowel-initial syllables
+#[test]
+fn test_tone_and_missing_onset() {
+    let theory = tiny_theory();
+
+    assert_eq!(theory.syllable(&keys("ef")), Some("ề".to_string()));
+    assert_eq!(theory.syllable(&keys("tesf")), Some("tệ".to_string()));
+}
+
+/// Test strokes that cannot be translated
+#[test]
+fn test_invalid_strokes() {
+    let theory = tiny_theory();
+
+    // No rhyme
+    assert_eq!(theory.syllable(&keys("t")), None);
+    // Rhyme chord not in the table
+    assert_eq!(theory.syllable(&keys("tae")), None);
+    // Key outside the theory
+    assert_eq!(theory.syllable(&keys("taq")), None);
+}
+
+/// Test untranslatable strokes are written out raw
+#[test]
+fn test_untranslatable_stroke_output_raw() {
+    let mut engine = ChordEngine::with_theory(tiny_theory());
+
+    let result = engine.process_chord(&keys("nt"));
+    assert_eq!(result.output_text, Some("tn".to_string()));
+}
+
+/// Test consecutive strokes are separated by spaces
+#[test]
+fn test_strokes_separated_by_space() {
+    let mut engine = ChordEngine::with_theory(tiny_theory());
+
+    engine.process_chord(&keys("tnas"));
+    let result = engine.process_chord(&keys("taof"));
+
+    assert_eq!(result.output_text, Some(" tào".to_string()));
+    assert_eq!(engine.get_buffer(), "nhá tào");
+}
+
+/// Test undo removes the previous stroke including its separator
+#[test]
+fn test_undo_stroke() {
+    let mut engine = ChordEngine::with_theory(tiny_theory());
+
+    engine.process_chord(&keys("ta"));
+    engine.process_chord(&keys("nes"));
+    assert_eq!(engine.get_buffer(), "ta nế");
+
+    let result = engine.process_chord(&['*']);
+    assert!(result.handled);
+    assert_eq!(result.backspace_count, 3);
+    assert_eq!(result.output_text, None);
+    assert_eq!(engine.get_buffer(), "ta");
+
+    let result = engine.process_chord(&['*']);
+    assert_eq!(result.backspace_count, 2);
+    assert_eq!(engine.get_buffer(), "");
+
+    // Nothing left to undo
+    let result = engine.process_chord(&['*']);
+    assert!(result.handled);
+    assert_eq!(result.backspace_count, 0);
+}
+
+/// Test a custom undo key
+#[test]
+fn test_custom_undo_key() {
+    let mut engine = ChordEngine::with_theory(tiny_theory().with_undo_key('#'));
+
+    engine.process_chord(&keys("ta"));
+    let result = engine.process_chord(&['#']);
+    assert_eq!(result.backspace_count, 2);
+    assert_eq!(engine.get_buffer(), "");
+}
+
+/// Test undo history is bounded
+#[test]
+fn test_undo_history_bounded() {
+    let mut engine = ChordEngine::with_theory(tiny_theory());
+
+    for _ in 0..MAX_UNDO_STROKES + 5 {
+        engine.process_chord(&keys("ta"));
+    }
+    for _ in 0..MAX_UNDO_STROKES {
+        engine.process_chord(&['*']);
+    }
+
+    let result = engine.process_chord(&['*']);
+    assert_eq!(result.backspace_count, 0);
+}
+
+/// Test briefs take precedence over the theory and can span syllables
+#[test]
+fn test_brief_multi_syllable() {
+    let mut engine = ChordEngine::with_theory(tiny_theory()).with_brief("nta", "Việt Nam");
+
+    let result = engine.process_chord(&keys("atn"));
+    assert_eq!(result.output_text, Some("Việt Nam".to_string()));
+
+    // Undo removes the whole brief
+    let result = engine.process_chord(&['*']);
+    assert_eq!(result.backspace_count, 8);
+}
+
+/// Test briefs added after construction
+#[test]
+fn test_add_brief() {
+    let mut engine = ChordEngine::new();
+    engine.add_brief("qwer", "cộng hòa xã hội chủ nghĩa");
+
+    let result = engine.process_chord(&keys("rewq"));
+    assert_eq!(
+        result.output_text,
+        Some("cộng hòa xã hội chủ nghĩa".to_string())
+    );
+}
+
+/// Test the basic theory produces full syllables
+#[test]
+fn test_basic_theory_syllables() {
+    let theory = ChordTheory::basic();
+
+    // v (er) + iê (vn) + t (o) + nặng (')
+    assert_eq!(theory.syllable(&keys("ervno'")), Some("việt".to_string()));
+    // n (as) + a (c) + m (ui)
+    assert_eq!(theory.syllable(&keys("ascui")), Some("nam".to_string()));
+    // tr (af) + ươ (cbn) + ng (i) + huyền (j)
+    assert_eq!(
+        theory.syllable(&keys("afcbnij")),
+        Some("trường".to_string())
+    );
+    // ngh (ef) + iê (vn) + ng (i) + ngã (;)
+    assert_eq!(
+        theory.syllable(&keys("efvni;")),
+        Some("nghiễng".to_string())
+    );
+}
+
+/// Test single keys pass through and reset the stroke history
+#[test]
+fn test_process_key_passes_through() {
+    let mut engine = ChordEngine::with_theory(tiny_theory());
+
+    engine.process_chord(&keys("ta"));
+    let result = engine.process_key('x', false);
+
+    assert!(!result.handled);
+    assert_eq!(engine.get_buffer(), "");
+}
+
+/// Test backspace shortens the last stroke
+#[test]
+fn test_backspace_shortens_last_stroke() {
+    let mut engine = ChordEngine::with_theory(tiny_theory());
+
+    engine.process_chord(&keys("ta"));
+    engine.process_chord(&keys("ta"));
+    assert!(!engine.process_backspace());
+    assert_eq!(engine.get_buffer(), "ta t");
+
+    // Undo removes what is left of the stroke
+    let result = engine.process_chord(&['*']);
+    assert_eq!(result.backspace_count, 2);
+    assert_eq!(engine.get_buffer(), "ta");
+}
+
+/// Test non-chord engines let chords through
+#[test]
+fn test_default_process_chord_passes_through() {
+    let mut engine = TelexEngine::new();
+
+    let result = engine.process_chord(&keys("ta"));
+    assert!(!result.handled);
+    assert_eq!(engine.get_buffer(), "");
+}
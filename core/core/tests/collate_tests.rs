@@ -0,0 +1,101 @@
+//! # Collation Tests
+//!
+//! Reference word lists in dictionary order, with and without digraphs as
+//! letters; shuffled copies must sort back into them.
+
+use std::cmp::Ordering;
+
+use vietime_core::collate::{self, Collator};
+use vietime_core::models::syllable::Syllable;
+use vietime_core::VietnameseChar;
+
+/// Dictionary order, every letter on its own
+const REFERENCE: &[&str] = &[
+    "a", "à", "ả", "ã", "á", "ạ", "ai", "am", "an", "anh", "ăn", "ân", "ba", "bà", "bác", "bạn",
+    "ca", "cà", "cá", "cam", "câu", "cha", "chị", "co", "cô", "cơ", "da", "dạ", "đá", "đi", "e",
+    "em", "ê", "gà", "ghế", "gì", "hà", "hỏi", "ích", "kem", "khi", "la", "lê", "lệ", "mẹ", "na",
+    "ngà", "nghe", "nhà", "no", "nó", "ô", "ơi", "phở", "qua", "ra", "sa", "ta", "thu", "to",
+    "trà", "tư", "u", "ư", "va", "xa", "ý", "yêu",
+];
+
+/// Dictionary order with ch, gh, kh, ng, nh, ph, th and tr as letters
+const DIGRAPH_REFERENCE: &[&str] = &[
+    "a", "à", "ả", "ã", "á", "ạ", "ai", "am", "an", "anh", "ăn", "ân", "ba", "bà", "bác", "bạn",
+    "ca", "cà", "cá", "cam", "câu", "co", "cô", "cơ", "cha", "chị", "da", "dạ", "đá", "đi", "e",
+    "em", "ê", "gà", "gì", "ghế", "hà", "hỏi", "ích", "kem", "khi", "la", "lê", "lệ", "mẹ", "na",
+    "no", "nó", "ngà", "nghe", "nhà", "ô", "ơi", "phở", "qua", "ra", "sa", "ta", "to", "tư", "thu",
+    "trà", "u", "ư", "va", "xa", "ý", "yêu",
+];
+
+/// Deterministic shuffle: every seventh item, wrapping around
+fn shuffled(words: &[&'static str]) -> Vec<&'static str> {
+    (0..words.len())
+        .map(|i| words[i * 7 % words.len()])
+        .collect()
+}
+
+/// Test the reference list sorts back into order
+#[test]
+fn test_reference_order() {
+    let mut words = shuffled(REFERENCE);
+    assert_ne!(words, REFERENCE);
+    words.sort_by(|a, b| collate::compare(a, b));
+    assert_eq!(words, REFERENCE);
+
+    let mut words = shuffled(REFERENCE);
+    words.sort_by_cached_key(|word| collate::sort_key(word));
+    assert_eq!(words, REFERENCE);
+}
+
+/// Test the digraph reference list sorts back into order
+#[test]
+fn test_digraph_reference_order() {
+    let collator = Collator::new().with_digraphs(true);
+    let mut words = shuffled(DIGRAPH_REFERENCE);
+    words.sort_by(|a, b| collator.compare(a, b));
+    assert_eq!(words, DIGRAPH_REFERENCE);
+}
+
+/// Test names sort by letters, then tones, then case, in either form
+#[test]
+fn test_names() {
+    let sorted = [
+        "đào minh",
+        "Đào Minh",
+        "Đặng Thu Thảo",
+        "Lê An",
+        "Lê Thị Lệ",
+        "Lê Văn",
+        "Nguyễn Văn Đức",
+        "Zoe",
+    ];
+    let mut names: Vec<String> = sorted
+        .iter()
+        .rev()
+        .map(|name| VietnameseChar::to_nfd(name))
+        .collect();
+    names.sort_by(|a, b| collate::compare(a, b));
+    let names: Vec<String> = names
+        .iter()
+        .map(|name| VietnameseChar::to_nfc(name))
+        .collect();
+    assert_eq!(names, sorted);
+}
+
+/// Test distinct syllables never compare equal, with or without digraphs
+#[test]
+fn test_every_syllable_distinct() {
+    for collator in [Collator::new(), Collator::new().with_digraphs(true)] {
+        let mut words: Vec<String> = Syllable::all().iter().map(|s| s.to_string()).collect();
+        words.sort_by_cached_key(|word| collator.sort_key(word));
+        for pair in words.windows(2) {
+            assert_eq!(
+                collator.compare(&pair[0], &pair[1]),
+                Ordering::Less,
+                "{} {}",
+                pair[0],
+                pair[1]
+            );
+        }
+    }
+}
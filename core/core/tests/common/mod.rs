@@ -0,0 +1,52 @@
+//! # Shared Test Helpers
+//!
+//! Keystroke fixtures used by more than one integration test file.
+
+use vietime_core::clock::ManualClock;
+use vietime_core::engines::multitap::{letters_for_key, MultiTapEngine, MULTI_TAP_TIMEOUT_MS};
+use vietime_core::engines::InputEngine;
+use vietime_core::testing::VirtualDocument;
+use vietime_core::{ToneIndex, VietnameseChar};
+
+/// Tap `word` on the multi-tap keypad into an empty document and return the
+/// visible text
+///
+/// Each letter is tapped on its key, Shift held for uppercase letters,
+/// waiting out the cycle timeout on `clock` between two letters on the
+/// same key; the word's tone is then cycled in with `1`.
+///
+/// # Panics
+///
+/// If a letter of `word` is not on the keypad.
+pub fn tap_word(engine: &mut MultiTapEngine, clock: &ManualClock, word: &str) -> String {
+    let mut document = VirtualDocument::new();
+    let mut previous_key = None;
+    let mut tone = ToneIndex::None;
+    for c in word.chars() {
+        if VietnameseChar::get_tone_index(c).is_toned() {
+            tone = VietnameseChar::get_tone_index(c);
+        }
+        let letter = VietnameseChar::to_lower(VietnameseChar::apply_tone(c, ToneIndex::None));
+        let (key, taps) = ('2'..='9')
+            .find_map(|key| {
+                let position = letters_for_key(key)?.iter().position(|&l| l == letter)?;
+                Some((key, position + 1))
+            })
+            .unwrap_or_else(|| panic!("{:?} is not on the keypad", c));
+        if previous_key == Some(key) {
+            // Let the pending letter commit
+            clock.advance(MULTI_TAP_TIMEOUT_MS);
+        }
+        for _ in 0..taps {
+            let result = engine.process_key(key, c.is_uppercase());
+            document.apply_result(&result, &key.to_string());
+            clock.advance(100);
+        }
+        previous_key = Some(key);
+    }
+    for _ in 0..tone as usize {
+        let result = engine.process_key('1', false);
+        document.apply_result(&result, "1");
+    }
+    document.text()
+}
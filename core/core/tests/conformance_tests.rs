@@ -0,0 +1,107 @@
+//! # Conformance Kit Integration Tests
+//!
+//! Runs the `InputEngine` conformance suite against the bundled engines, and
+//! checks that the kit catches a broken engine.
+
+use std::sync::Arc;
+
+use vietime_core::clock::ManualClock;
+use vietime_core::conformance;
+use vietime_core::conformance_suite;
+use vietime_core::engines::charset::CharsetOutput;
+use vietime_core::engines::chord::ChordEngine;
+use vietime_core::engines::multitap::MultiTapEngine;
+use vietime_core::engines::telex::TelexEngine;
+use vietime_core::engines::{InputEngine, ProcessKeyResult};
+use vietime_core::models::charset::Charset;
+
+conformance_suite!(telex, TelexEngine::new());
+
+conformance_suite!(
+    multitap,
+    MultiTapEngine::with_clock(Arc::new(ManualClock::new())),
+    "2223314\u{8}66610#9\u{8}\u{8}8880"
+);
+
+conformance_suite!(chord, ChordEngine::new());
+
+conformance_suite!(
+    telex_tcvn3,
+    CharsetOutput::new(TelexEngine::new(), Charset::Tcvn3)
+);
+
+conformance_suite!(
+    telex_viscii,
+    CharsetOutput::new(TelexEngine::new(), Charset::Viscii)
+);
+
+/// Test multi-character charsets keep every contract but one-buffered-character
+/// Backspace (the word ends when Backspace splits a letter)
+#[test]
+fn test_multi_character_charsets() {
+    for charset in [
+        Charset::UnicodeCombining,
+        Charset::VniWindows,
+        Charset::Cp1258,
+    ] {
+        let engine = || CharsetOutput::new(TelexEngine::new(), charset);
+        conformance::check_name(&engine());
+        conformance::check_starts_empty(&engine());
+        conformance::check_reset_clears(&mut engine(), conformance::DEFAULT_KEYS);
+        conformance::check_buffer_views_agree(&mut engine(), conformance::DEFAULT_KEYS);
+        conformance::check_output_reconstructs_buffer(&mut engine(), conformance::DEFAULT_KEYS);
+    }
+}
+
+/// Engine that forgets to clear its buffer and echoes keys it never shows
+struct BrokenEngine {
+    buffer: Vec<char>,
+}
+
+impl InputEngine for BrokenEngine {
+    fn name(&self) -> &str {
+        "Broken"
+    }
+
+    fn process_key(&mut self, key: char, _is_shift_pressed: bool) -> ProcessKeyResult {
+        self.buffer.push(key);
+        self.buffer.push(key);
+        ProcessKeyResult::pass_through(self.get_buffer())
+    }
+
+    fn reset(&mut self) {}
+
+    fn process_backspace(&mut self) -> bool {
+        self.buffer.clear();
+        false
+    }
+
+    fn get_buffer(&self) -> String {
+        self.buffer.iter().collect()
+    }
+
+    fn get_buffer_slice(&self) -> &[char] {
+        &self.buffer
+    }
+}
+
+/// Test the kit catches a reset that keeps state
+#[test]
+#[should_panic(expected = "not cleared by reset")]
+fn test_detects_reset_keeping_state() {
+    conformance::check_reset_clears(&mut BrokenEngine { buffer: Vec::new() }, "ab");
+}
+
+/// Test the kit catches output that does not match the buffer
+#[test]
+#[should_panic(expected = "does not end with buffer")]
+fn test_detects_output_mismatch() {
+    conformance::check_output_reconstructs_buffer(&mut BrokenEngine { buffer: Vec::new() }, "ab");
+}
+
+/// Test the kit catches a Backspace clearing the whole buffer
+#[test]
+#[should_panic(expected = "removed 2 buffered characters")]
+fn test_detects_backspace_clearing_buffer() {
+    conformance::check_backspace_consistency(&mut BrokenEngine { buffer: Vec::new() }, "a");
+}
@@ -0,0 +1,98 @@
+//! # Encoding Converter Tests
+//!
+//! Round-trips every Vietnamese letter through every pair of encodings.
+
+use std::io::Cursor;
+
+use vietime_core::convert::{convert, convert_bytes, Encoding};
+use vietime_core::VietnameseChar;
+
+/// Only TCVN3 lacks letters: the uppercase ones with a tone
+fn representable(c: char, encoding: Encoding) -> bool {
+    encoding != Encoding::Tcvn3
+        || !(c.is_uppercase() && VietnameseChar::get_tone_index(c).is_toned())
+}
+
+/// Test every letter survives every pair of encodings, next to marks and d
+#[test]
+fn test_every_letter_through_every_pair() {
+    for c in VietnameseChar::letters() {
+        // Neighbours that VIQR and VNI-Windows could read as marks
+        let text = format!("{c}{c}d{c}.{c}? d{c}");
+        for from in Encoding::ALL {
+            let source = convert_bytes(text.as_bytes(), Encoding::Unicode, from);
+            if !representable(c, from) {
+                assert!(!source.unmappable.is_empty(), "{} in {}", c, from.name());
+                continue;
+            }
+            assert!(source.unmappable.is_empty(), "{} in {}", c, from.name());
+
+            for to in Encoding::ALL {
+                let converted = convert_bytes(&source.bytes, from, to);
+                if !representable(c, to) {
+                    assert_eq!(converted.unmappable.len(), 5, "{} to {}", c, to.name());
+                    assert!(converted.unmappable.iter().all(|u| u.character == c));
+                    continue;
+                }
+                assert!(
+                    converted.unmappable.is_empty(),
+                    "{} {} -> {}",
+                    c,
+                    from.name(),
+                    to.name()
+                );
+
+                let back = convert_bytes(&converted.bytes, to, Encoding::Unicode);
+                assert_eq!(
+                    String::from_utf8(back.bytes).unwrap(),
+                    text,
+                    "{} -> {}",
+                    from.name(),
+                    to.name()
+                );
+            }
+        }
+    }
+}
+
+/// Test a paragraph of plain text with ASCII punctuation through every pair
+#[test]
+fn test_paragraph_through_every_pair() {
+    let text = "Ông già đi đường, mưa rơi ướt áo. \"Thế à?\" - C:\\thư mục & 100% (ngày 1/1)";
+    for from in Encoding::ALL {
+        let source = convert_bytes(text.as_bytes(), Encoding::Unicode, from).bytes;
+        for to in Encoding::ALL {
+            let converted = convert_bytes(&source, from, to).bytes;
+            let back = convert_bytes(&converted, to, Encoding::Unicode).bytes;
+            assert_eq!(
+                String::from_utf8(back).unwrap(),
+                text,
+                "{} -> {}",
+                from.name(),
+                to.name()
+            );
+        }
+    }
+}
+
+/// Test the reader/writer API gives the same bytes as the one-shot API
+#[test]
+fn test_convert_reader_to_writer() {
+    let text = "Cộng hoà xã hội chủ nghĩa Việt Nam ".repeat(500);
+    let source = convert_bytes(text.as_bytes(), Encoding::Unicode, Encoding::VniWindows).bytes;
+
+    let mut output = Vec::new();
+    let unmappable = convert(
+        Cursor::new(&source),
+        &mut output,
+        Encoding::VniWindows,
+        Encoding::Viqr,
+    )
+    .unwrap();
+    assert!(unmappable.is_empty());
+    assert_eq!(
+        output,
+        convert_bytes(&source, Encoding::VniWindows, Encoding::Viqr).bytes
+    );
+    assert!(output.starts_with(b"Co^.ng hoa`"));
+}
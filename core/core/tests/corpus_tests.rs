@@ -0,0 +1,323 @@
+//! # Golden Corpus Tests
+//!
+//! Runs every `*.corpus` file under `tests/`. A corpus is plain text, so
+//! cases can be added without writing Rust:
+//!
+//! ```text
+//! # Comments start with '#'
+//! engine: telex
+//! options: host=input_method strategy=zero_width
+//!
+//! keys: tieengs vieetj
+//! text: tiếng việt
+//! buffer: việt
+//! ```
+//!
+//! - `engine:` (`telex`, `multitap`) and `options:` apply to the cases that
+//!   follow them; `options:` is reset by each `engine:` line
+//! - Options: `host=keyboard_hook|input_method`,
+//!   `strategy=direct|zero_width|space`
+//! - A case is a block of `keys:`, `text:` (visible text) and an optional
+//!   `buffer:` line, separated from the next case by a blank line
+//! - Values are trimmed; wrap one in double quotes to keep leading or
+//!   trailing spaces. In `keys`, `<BS>` is the Backspace key
+
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use vietime_core::clock::ManualClock;
+use vietime_core::engines::actions::HostCapabilities;
+use vietime_core::engines::multitap::MultiTapEngine;
+use vietime_core::engines::strategy::OutputStrategy;
+use vietime_core::engines::telex::TelexEngine;
+use vietime_core::engines::InputEngine;
+use vietime_core::testing::{VirtualDocument, BACKSPACE};
+
+/// Engine and options in effect for a case
+#[derive(Debug, Clone)]
+struct Setup {
+    engine: String,
+    host: HostCapabilities,
+    strategy: OutputStrategy,
+}
+
+/// A single corpus case
+#[derive(Debug)]
+struct Case {
+    location: String,
+    setup: Setup,
+    keys: String,
+    text: String,
+    buffer: Option<String>,
+}
+
+impl Setup {
+    fn new(engine: &str) -> Self {
+        Self {
+            engine: engine.to_string(),
+            host: HostCapabilities::KEYBOARD_HOOK,
+            strategy: OutputStrategy::Direct,
+        }
+    }
+
+    fn set_option(&mut self, option: &str) -> Result<(), String> {
+        let (name, value) = option
+            .split_once('=')
+            .ok_or_else(|| format!("option {:?} is not name=value", option))?;
+        match (name, value) {
+            ("host", "keyboard_hook") => self.host = HostCapabilities::KEYBOARD_HOOK,
+            ("host", "input_method") => self.host = HostCapabilities::INPUT_METHOD,
+            ("strategy", "direct") => self.strategy = OutputStrategy::Direct,
+            ("strategy", "zero_width") => self.strategy = OutputStrategy::ZERO_WIDTH,
+            ("strategy", "space") => self.strategy = OutputStrategy::SPACE,
+            _ => return Err(format!("unknown option {:?}", option)),
+        }
+        Ok(())
+    }
+
+    fn engine(&self) -> Result<Box<dyn InputEngine>, String> {
+        let clock = Arc::new(ManualClock::new());
+        match self.engine.as_str() {
+            "telex" => Ok(Box::new(TelexEngine::with_clock(clock))),
+            "multitap" => Ok(Box::new(MultiTapEngine::with_clock(clock))),
+            other => Err(format!("unknown engine {:?}", other)),
+        }
+    }
+}
+
+/// Trim a value and strip optional surrounding quotes
+fn unquote(value: &str) -> &str {
+    let value = value.trim();
+    value
+        .strip_prefix('"')
+        .and_then(|v| v.strip_suffix('"'))
+        .unwrap_or(value)
+}
+
+/// Parse a corpus file into cases
+fn parse(path: &Path, source: &str) -> Result<Vec<Case>, String> {
+    let name = path
+        .file_name()
+        .unwrap_or_default()
+        .to_string_lossy()
+        .into_owned();
+    let mut cases = Vec::new();
+    let mut setup: Option<Setup> = None;
+    let mut pending: Option<Case> = None;
+    let mut has_text = false;
+
+    // A trailing blank line closes the last case
+    for (number, line) in source.lines().chain(std::iter::once("")).enumerate() {
+        let location = format!("{}:{}", name, number + 1);
+        let line = line.trim_end_matches('\r');
+        if line.trim().is_empty() {
+            if let Some(case) = pending.take() {
+                if !has_text {
+                    return Err(format!("{}: case has no text", case.location));
+                }
+                cases.push(case);
+            }
+            continue;
+        }
+        if line.trim_start().starts_with('#') {
+            continue;
+        }
+
+        let (field, value) = line
+            .split_once(':')
+            .ok_or_else(|| format!("{}: expected `field: value`", location))?;
+        match field.trim() {
+            "engine" => setup = Some(Setup::new(unquote(value))),
+            "options" => {
+                let setup = setup
+                    .as_mut()
+                    .ok_or_else(|| format!("{}: options before engine", location))?;
+                for option in value.split_whitespace() {
+                    setup
+                        .set_option(option)
+                        .map_err(|e| format!("{}: {}", location, e))?;
+                }
+            }
+            "keys" => {
+                let setup = setup
+                    .clone()
+                    .ok_or_else(|| format!("{}: case before engine", location))?;
+                if pending.is_some() {
+                    return Err(format!("{}: missing blank line before case", location));
+                }
+                has_text = false;
+                pending = Some(Case {
+                    location,
+                    setup,
+                    keys: unquote(value).replace("<BS>", &BACKSPACE.to_string()),
+                    text: String::new(),
+                    buffer: None,
+                });
+            }
+            "text" | "buffer" => {
+                let case = pending
+                    .as_mut()
+                    .ok_or_else(|| format!("{}: {} without keys", location, field.trim()))?;
+                if field.trim() == "text" {
+                    has_text = true;
+                    case.text = unquote(value).to_string();
+                } else {
+                    case.buffer = Some(unquote(value).to_string());
+                }
+            }
+            other => return Err(format!("{}: unknown field {:?}", location, other)),
+        }
+    }
+    Ok(cases)
+}
+
+/// Describe where two strings first differ
+fn diff(label: &str, expected: &str, actual: &str) -> String {
+    let position = expected
+        .chars()
+        .zip(actual.chars())
+        .take_while(|(e, a)| e == a)
+        .count();
+    let mut report = String::new();
+    let _ = writeln!(report, "    {} differs at char {}:", label, position);
+    let _ = writeln!(report, "      expected: \"{}\"", expected);
+    let _ = writeln!(report, "      actual:   \"{}\"", actual);
+    let _ = writeln!(report, "                 {}^", " ".repeat(position));
+    report
+}
+
+/// Run a case, returning a report if it fails
+fn run(case: &Case) -> Result<Option<String>, String> {
+    let mut engine = case
+        .setup
+        .engine()
+        .map_err(|e| format!("{}: {}", case.location, e))?;
+    let mut document = VirtualDocument::new()
+        .with_capabilities(case.setup.host)
+        .with_strategy(case.setup.strategy);
+    let text = document.type_keys(engine.as_mut(), &case.keys);
+    let buffer = engine.get_buffer();
+
+    let mut report = String::new();
+    if text != case.text {
+        report.push_str(&diff("text", &case.text, &text));
+    }
+    if let Some(expected) = &case.buffer {
+        if &buffer != expected {
+            report.push_str(&diff("buffer", expected, &buffer));
+        }
+    }
+    if report.is_empty() {
+        return Ok(None);
+    }
+    let keys = case.keys.replace(BACKSPACE, "<BS>");
+    Ok(Some(format!(
+        "{} [{}] keys \"{}\"\n{}",
+        case.location, case.setup.engine, keys, report
+    )))
+}
+
+/// Every `*.corpus` file under `dir`, sorted
+fn corpus_files(dir: &Path) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    let Ok(entries) = fs::read_dir(dir) else {
+        return files;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            files.extend(corpus_files(&path));
+        } else if path.extension().is_some_and(|ext| ext == "corpus") {
+            files.push(path);
+        }
+    }
+    files.sort();
+    files
+}
+
+/// Test every corpus case, reporting all failures at once
+#[test]
+fn test_golden_corpus() {
+    let files = corpus_files(&Path::new(env!("CARGO_MANIFEST_DIR")).join("tests"));
+    assert!(!files.is_empty(), "no corpus files found under tests/");
+
+    let mut total = 0;
+    let mut failures = Vec::new();
+    for path in &files {
+        let source =
+            fs::read_to_string(path).unwrap_or_else(|e| panic!("{}: {}", path.display(), e));
+        let cases = parse(path, &source).unwrap_or_else(|e| panic!("{}", e));
+        total += cases.len();
+        for case in &cases {
+            if let Some(report) = run(case).unwrap_or_else(|e| panic!("{}", e)) {
+                failures.push(report);
+            }
+        }
+    }
+
+    assert!(total > 0, "corpus files contain no cases");
+    assert!(
+        failures.is_empty(),
+        "{} of {} corpus cases failed:\n\n{}",
+        failures.len(),
+        total,
+        failures.join("\n")
+    );
+}
+
+/// Test the parser handles headers, quoting and Backspace
+#[test]
+fn test_parse_corpus() {
+    let source = "# comment\nengine: telex\noptions: host=input_method\n\nkeys: \"ab<BS> \"\ntext: \"a \"\n\nkeys: aa\ntext: â\nbuffer: â\n";
+    let cases = parse(Path::new("inline.corpus"), source).unwrap();
+
+    assert_eq!(cases.len(), 2);
+    assert_eq!(cases[0].keys, format!("ab{} ", BACKSPACE));
+    assert_eq!(cases[0].text, "a ");
+    assert_eq!(cases[0].buffer, None);
+    assert_eq!(cases[0].setup.host, HostCapabilities::INPUT_METHOD);
+    assert_eq!(cases[1].location, "inline.corpus:8");
+    assert_eq!(cases[1].buffer.as_deref(), Some("â"));
+}
+
+/// Test malformed corpus files are rejected with a location
+#[test]
+fn test_parse_errors() {
+    let path = Path::new("bad.corpus");
+    assert_eq!(
+        parse(path, "keys: a\ntext: a\n").unwrap_err(),
+        "bad.corpus:1: case before engine"
+    );
+    assert_eq!(
+        parse(path, "engine: telex\noptions: tone=new\n").unwrap_err(),
+        "bad.corpus:2: unknown option \"tone=new\""
+    );
+    assert_eq!(
+        parse(path, "engine: telex\nkeys: a\nexpect: a\n").unwrap_err(),
+        "bad.corpus:3: unknown field \"expect\""
+    );
+    assert_eq!(
+        parse(path, "engine: telex\nkeys: a\n").unwrap_err(),
+        "bad.corpus:2: case has no text"
+    );
+}
+
+/// Test failure reports point at the first differing character
+#[test]
+fn test_failure_report() {
+    let case = Case {
+        location: "inline.corpus:1".to_string(),
+        setup: Setup::new("telex"),
+        keys: "vieetj".to_string(),
+        text: "viết".to_string(),
+        buffer: None,
+    };
+    let report = run(&case).unwrap().unwrap();
+
+    assert!(report.starts_with("inline.corpus:1 [telex] keys \"vieetj\""));
+    assert!(report.contains("text differs at char 2:"));
+    assert!(report.contains("expected: \"viết\""));
+    assert!(report.contains("actual:   \"việt\""));
+}
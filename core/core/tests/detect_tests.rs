@@ -0,0 +1,100 @@
+//! # Encoding Detection Tests
+//!
+//! Encodes a paragraph every way a document arrives and checks the
+//! detector recovers it, mojibake included. TCVN3 has no capital toned
+//! letters, so only plain letters are capitalised.
+
+use vietime_core::convert::{convert_bytes, Encoding};
+use vietime_core::detect::{detect, repair};
+
+const TEXT: &str = "Tôi yêu tiếng nước tôi từ khi mới ra đời, người ơi! \
+                    Mẹ hiền ru những câu xa vời. ạ ơi, tiếng mẹ ngọt ngào.";
+
+fn encode(text: &str, encoding: Encoding) -> Vec<u8> {
+    convert_bytes(text.as_bytes(), Encoding::Unicode, encoding).bytes
+}
+
+/// Bytes shown as Windows-1252 (Latin-1 for its holes) and saved as UTF-8
+fn mojibake(bytes: &[u8]) -> Vec<u8> {
+    bytes
+        .iter()
+        .map(|&b| match b {
+            0x80..=0x9F => cp1252(b),
+            _ => char::from(b),
+        })
+        .collect::<String>()
+        .into_bytes()
+}
+
+fn cp1252(byte: u8) -> char {
+    let c1 = "€\u{81}‚ƒ„…†‡ˆ‰Š‹Œ\u{8D}Ž\u{8F}\u{90}‘’“”•–—˜™š›œ\u{9D}žŸ";
+    c1.chars().nth(usize::from(byte - 0x80)).unwrap()
+}
+
+/// Test every encoding of the paragraph is recognised
+#[test]
+fn test_detects_each_encoding() {
+    for encoding in Encoding::ALL {
+        let guesses = detect(&encode(TEXT, encoding));
+        assert_eq!(guesses[0].text, TEXT, "{}", encoding.name());
+        assert_eq!(guesses[0].steps.len(), 1, "{}", encoding.name());
+        assert!(
+            guesses[0].confidence > 0.9,
+            "{}: {}",
+            encoding.name(),
+            guesses[0].confidence
+        );
+    }
+}
+
+/// Test the legacy byte encodings are told apart, not just decoded alike
+#[test]
+fn test_names_legacy_encoding() {
+    for encoding in [
+        Encoding::Tcvn3,
+        Encoding::VniWindows,
+        Encoding::Viscii,
+        Encoding::Cp1258,
+        Encoding::Viqr,
+    ] {
+        let guesses = detect(&encode(TEXT, encoding));
+        assert_eq!(guesses[0].steps, [encoding]);
+        assert!(
+            guesses[1].confidence < guesses[0].confidence,
+            "{}",
+            encoding.name()
+        );
+    }
+}
+
+/// Test mojibake is repaired by chaining decodes
+#[test]
+fn test_repairs_mojibake() {
+    // UTF-8 read as Windows-1252: "TÃ´i yÃªu"
+    let double = mojibake(TEXT.as_bytes());
+    assert!(String::from_utf8_lossy(&double).starts_with("TÃ´i yÃªu"));
+    let guesses = detect(&double);
+    assert_eq!(guesses[0].steps, [Encoding::Unicode, Encoding::Unicode]);
+    assert_eq!(guesses[0].text, TEXT);
+
+    // TCVN3 and VNI-Windows shown as Latin-1 and saved as UTF-8
+    for encoding in [Encoding::Tcvn3, Encoding::VniWindows] {
+        let saved = mojibake(&encode(TEXT, encoding));
+        assert_eq!(detect(&saved)[0].steps, [Encoding::Unicode, encoding]);
+        assert_eq!(repair(&saved), TEXT);
+    }
+
+    // Twice through Windows-1252
+    assert_eq!(repair(&mojibake(&mojibake(TEXT.as_bytes()))), TEXT);
+}
+
+/// Test guesses are ranked and a large input is decoded in full
+#[test]
+fn test_ranking_and_large_input() {
+    let long = TEXT.repeat(400);
+    let guesses = detect(&encode(&long, Encoding::VniWindows));
+    assert!(guesses
+        .windows(2)
+        .all(|pair| pair[0].confidence >= pair[1].confidence));
+    assert_eq!(guesses[0].text, long);
+}
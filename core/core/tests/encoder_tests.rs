@@ -0,0 +1,162 @@
+//! # Keystroke Encoder Integration Tests
+//!
+//! Round trips: encode text, type the keystrokes through an engine, and
+//! compare the visible text with the original.
+
+use proptest::prelude::*;
+use vietime_core::encoder::{Encoder, InputMethod, TonePlacement};
+use vietime_core::engines::telex::TelexEngine;
+use vietime_core::testing::type_keys;
+
+/// Words whose Telex spelling the engine types back correctly
+const WORDS: &[&str] = &[
+    "tiếng",
+    "việt",
+    "người",
+    "đường",
+    "Đà",
+    "Nẵng",
+    "hoà",
+    "bình",
+    "xin",
+    "chào",
+    "các",
+    "bạn",
+    "thuở",
+    "trường",
+    "học",
+    "sinh",
+    "mưa",
+    "rượu",
+    "khoẻ",
+    "nghiêng",
+    "ăn",
+    "uống",
+    "ở",
+    "đây",
+    "năm",
+    "mới",
+    "thương",
+    "nhớ",
+    "Hà",
+    "Nội",
+    "sáng",
+    "tối",
+    "trăng",
+    "VIỆT",
+    "NAM",
+    "ĐƯỜNG",
+    "THUỞ",
+    "Ước",
+    "XOONG",
+];
+
+const PLACEMENTS: [TonePlacement; 2] = [TonePlacement::EndOfWord, TonePlacement::AfterVowel];
+
+fn telex_round_trip(text: &str, placement: TonePlacement) -> String {
+    let keys = Encoder::new(InputMethod::Telex)
+        .with_tone_placement(placement)
+        .encode(text);
+    type_keys(&mut TelexEngine::new(), &keys)
+}
+
+/// Test every word survives a Telex round trip
+#[test]
+fn test_telex_round_trip_words() {
+    for placement in PLACEMENTS {
+        for word in WORDS {
+            assert_eq!(&telex_round_trip(word, placement), word, "{:?}", placement);
+        }
+    }
+}
+
+/// Test the README Telex table is reproduced
+#[test]
+fn test_telex_table() {
+    let telex = Encoder::new(InputMethod::Telex);
+    for (text, keys) in [
+        ("â", "aa"),
+        ("ă", "aw"),
+        ("ê", "ee"),
+        ("ô", "oo"),
+        ("ơ", "ow"),
+        ("ư", "uw"),
+        ("đ", "dd"),
+        ("á", "as"),
+        ("à", "af"),
+        ("ả", "ar"),
+        ("ã", "ax"),
+        ("ạ", "aj"),
+    ] {
+        assert_eq!(telex.encode(text), keys);
+    }
+}
+
+/// Test the standard VNI table is reproduced (7 for the horn, 8 for the
+/// breve)
+#[test]
+fn test_vni_table() {
+    let vni = Encoder::new(InputMethod::Vni);
+    for (text, keys) in [
+        ("â", "a6"),
+        ("ê", "e6"),
+        ("ô", "o6"),
+        ("ă", "a8"),
+        ("ư", "u7"),
+        ("ơ", "o7"),
+        ("đ", "d9"),
+        ("á", "a1"),
+        ("à", "a2"),
+        ("ả", "a3"),
+        ("ã", "a4"),
+        ("ạ", "a5"),
+    ] {
+        assert_eq!(vni.encode(text), keys);
+    }
+    assert_eq!(vni.encode("việt nam"), "vie6t5 nam");
+    assert_eq!(
+        vni.with_tone_placement(TonePlacement::AfterVowel)
+            .encode("việt nam"),
+        "vie65t nam"
+    );
+}
+
+/// Test VIQR marks and escapes
+#[test]
+fn test_viqr() {
+    let viqr = Encoder::new(InputMethod::Viqr);
+    assert_eq!(viqr.encode("Trường Đại học"), "Tru+o+ng` DDai. hoc.");
+    assert_eq!(
+        viqr.with_tone_placement(TonePlacement::AfterVowel)
+            .encode("Trường Đại học"),
+        "Tru+o+`ng DDa.i ho.c"
+    );
+
+    // Punctuation that would be read as a tone mark is escaped
+    assert_eq!(viqr.encode("Ai? Tôi."), "Ai\\? To^i\\.");
+}
+
+/// Test non-Vietnamese characters are copied
+#[test]
+fn test_other_characters() {
+    let telex = Encoder::new(InputMethod::Telex);
+    assert_eq!(telex.encode("số 10, Hà Nội!"), "soos 10, Haf Nooij!");
+
+    // Brackets are typed twice so Telex gives them back
+    assert_eq!(telex.encode("[a]"), "[[a]]");
+    assert_eq!(type_keys(&mut TelexEngine::new(), "[[a]]"), "[a]");
+}
+
+proptest! {
+    /// encode -> type -> equals original, for sentences of known words
+    #[test]
+    fn prop_telex_round_trip(
+        words in prop::collection::vec(prop::sample::select(WORDS), 1..8),
+        separator in prop::sample::select(vec![" ", ", ", ". ", " - "]),
+        after_vowel in any::<bool>(),
+    ) {
+        let text = words.join(separator);
+        let placement = if after_vowel { TonePlacement::AfterVowel } else { TonePlacement::EndOfWord };
+        prop_assert_eq!(telex_round_trip(&text, placement), text);
+    }
+}
@@ -0,0 +1,110 @@
+//! # Engine Event Integration Tests
+//!
+//! Tests for non-key events and the buffer auto-reset timeout.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use vietime_core::clock::ManualClock;
+use vietime_core::engines::telex::TelexEngine;
+use vietime_core::engines::{EngineEvent, InputEngine};
+use vietime_core::BUFFER_TIMEOUT_MS;
+
+fn engine_with_clock() -> (TelexEngine, ManualClock) {
+    let clock = ManualClock::new();
+    let engine = TelexEngine::with_clock(Arc::new(clock.clone()));
+    (engine, clock)
+}
+
+fn type_keys(engine: &mut TelexEngine, keys: &str) {
+    for key in keys.chars() {
+        engine.process_key(key, false);
+    }
+}
+
+/// Test focus, caret and selection events reset the buffer
+#[test]
+fn test_context_events_reset_buffer() {
+    let events = [
+        EngineEvent::FocusLost,
+        EngineEvent::FocusGained {
+            app_id: "notepad.exe".to_string(),
+        },
+        EngineEvent::CursorMoved,
+        EngineEvent::SelectionChanged,
+    ];
+
+    for event in events {
+        let mut engine = TelexEngine::new();
+        type_keys(&mut engine, "tha");
+
+        engine.handle_event(&event);
+        assert_eq!(
+            engine.get_buffer(),
+            "",
+            "{:?} should reset the buffer",
+            event
+        );
+    }
+}
+
+/// Test short idle periods keep the buffer
+#[test]
+fn test_short_idle_keeps_buffer() {
+    let mut engine = TelexEngine::new();
+    type_keys(&mut engine, "tha");
+
+    engine.handle_event(&EngineEvent::Idle(Duration::from_millis(
+        BUFFER_TIMEOUT_MS - 1,
+    )));
+    assert_eq!(engine.get_buffer(), "tha");
+}
+
+/// Test idle periods reaching the timeout reset the buffer
+#[test]
+fn test_long_idle_resets_buffer() {
+    let mut engine = TelexEngine::new();
+    type_keys(&mut engine, "tha");
+
+    engine.handle_event(&EngineEvent::Idle(Duration::from_millis(BUFFER_TIMEOUT_MS)));
+    assert_eq!(engine.get_buffer(), "");
+}
+
+/// Test the engine applies the auto-reset itself on the next keystroke
+#[test]
+fn test_auto_reset_after_timeout() {
+    let (mut engine, clock) = engine_with_clock();
+    type_keys(&mut engine, "tha");
+
+    clock.advance(BUFFER_TIMEOUT_MS);
+
+    // Tone key after the pause has no vowel to attach to
+    let result = engine.process_key('s', false);
+    assert!(!result.handled);
+    assert_eq!(engine.get_buffer(), "s");
+}
+
+/// Test keystrokes within the timeout keep composing
+#[test]
+fn test_no_reset_within_timeout() {
+    let (mut engine, clock) = engine_with_clock();
+
+    for key in "tha".chars() {
+        engine.process_key(key, false);
+        clock.advance(BUFFER_TIMEOUT_MS - 1);
+    }
+
+    engine.process_key('s', false);
+    assert_eq!(engine.get_buffer(), "thá");
+}
+
+/// Test backspace also counts as activity and honours the timeout
+#[test]
+fn test_backspace_after_timeout() {
+    let (mut engine, clock) = engine_with_clock();
+    type_keys(&mut engine, "bc");
+
+    clock.advance(BUFFER_TIMEOUT_MS);
+    engine.process_backspace();
+    assert_eq!(engine.get_buffer(), "");
+}
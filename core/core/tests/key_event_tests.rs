@@ -0,0 +1,162 @@
+//! # Key Event Integration Tests
+//!
+//! Tests for [`InputEngine::process_key_event`] and the default decisions
+//! about which key events reset the buffer.
+
+use vietime_core::engines::telex::TelexEngine;
+use vietime_core::engines::{InputEngine, KeyCode, KeyEvent, Modifiers};
+
+fn type_events(engine: &mut TelexEngine, text: &str) {
+    for c in text.chars() {
+        engine.process_key_event(&KeyEvent::char(c));
+    }
+}
+
+/// Test text keys are processed like process_key
+#[test]
+fn test_char_event_processed() {
+    let mut engine = TelexEngine::new();
+
+    type_events(&mut engine, "thees");
+    assert_eq!(engine.get_buffer(), "thế");
+}
+
+/// Test Ctrl chords reset instead of being typed
+#[test]
+fn test_ctrl_chord_resets_buffer() {
+    let mut engine = TelexEngine::new();
+    type_events(&mut engine, "tha");
+
+    // Ctrl+S must not become a tone mark
+    let event = KeyEvent::char('s').with_modifiers(Modifiers::CTRL);
+    let result = engine.process_key_event(&event);
+
+    assert!(!result.handled);
+    assert_eq!(engine.get_buffer(), "");
+}
+
+/// Test Alt and Meta chords reset as well
+#[test]
+fn test_alt_and_meta_chords_reset_buffer() {
+    for modifiers in [
+        Modifiers {
+            alt: true,
+            ..Modifiers::NONE
+        },
+        Modifiers {
+            meta: true,
+            ..Modifiers::NONE
+        },
+    ] {
+        let mut engine = TelexEngine::new();
+        type_events(&mut engine, "tha");
+
+        engine.process_key_event(&KeyEvent::char('f').with_modifiers(modifiers));
+        assert_eq!(engine.get_buffer(), "");
+    }
+}
+
+/// Test navigation and editing keys reset the buffer
+#[test]
+fn test_navigation_keys_reset_buffer() {
+    let codes = [
+        KeyCode::Left,
+        KeyCode::Right,
+        KeyCode::Up,
+        KeyCode::Down,
+        KeyCode::Home,
+        KeyCode::End,
+        KeyCode::PageUp,
+        KeyCode::PageDown,
+        KeyCode::Tab,
+        KeyCode::Delete,
+        KeyCode::Enter,
+        KeyCode::Escape,
+        KeyCode::Mouse,
+        KeyCode::Function(5),
+        KeyCode::Other(0xFF),
+    ];
+
+    for code in codes {
+        let mut engine = TelexEngine::new();
+        type_events(&mut engine, "tha");
+
+        let result = engine.process_key_event(&KeyEvent::new(code));
+        assert!(!result.handled, "{:?} should pass through", code);
+        assert_eq!(
+            engine.get_buffer(),
+            "",
+            "{:?} should reset the buffer",
+            code
+        );
+    }
+}
+
+/// Test key releases are ignored
+#[test]
+fn test_key_release_ignored() {
+    let mut engine = TelexEngine::new();
+    type_events(&mut engine, "tha");
+
+    let result = engine.process_key_event(&KeyEvent::char('s').released());
+    assert!(!result.handled);
+    assert_eq!(engine.get_buffer(), "tha");
+
+    let result = engine.process_key_event(&KeyEvent::new(KeyCode::Left).released());
+    assert!(!result.handled);
+    assert_eq!(engine.get_buffer(), "tha");
+}
+
+/// Test lone modifier keys (Shift, CapsLock) do not reset the word
+#[test]
+fn test_modifier_key_does_not_reset() {
+    let mut engine = TelexEngine::new();
+    type_events(&mut engine, "tha");
+
+    let event = KeyEvent::new(KeyCode::Modifier).with_modifiers(Modifiers::SHIFT);
+    engine.process_key_event(&event);
+    assert_eq!(engine.get_buffer(), "tha");
+}
+
+/// Test Backspace goes through process_backspace
+#[test]
+fn test_backspace_event() {
+    let mut engine = TelexEngine::new();
+    type_events(&mut engine, "bc");
+
+    let result = engine.process_key_event(&KeyEvent::new(KeyCode::Backspace));
+    assert!(!result.handled);
+    assert_eq!(engine.get_buffer(), "b");
+}
+
+/// Test auto-repeat of a text key types again
+#[test]
+fn test_repeated_char_event() {
+    let mut engine = TelexEngine::new();
+    type_events(&mut engine, "th");
+
+    engine.process_key_event(&KeyEvent::char('a'));
+    engine.process_key_event(&KeyEvent::char('a').repeated());
+    assert_eq!(engine.get_buffer(), "thâ");
+}
+
+/// Test event builder fields
+#[test]
+fn test_key_event_builder() {
+    let event = KeyEvent::char('A')
+        .with_modifiers(Modifiers {
+            caps_lock: true,
+            ..Modifiers::NONE
+        })
+        .with_scan_code(0x1E)
+        .repeated();
+
+    assert_eq!(event.key, Some('A'));
+    assert_eq!(event.code, KeyCode::Char);
+    assert_eq!(event.scan_code, 0x1E);
+    assert!(event.modifiers.caps_lock);
+    assert!(!event.modifiers.is_command());
+    assert!(event.repeat);
+    assert!(event.is_press());
+    assert!(!event.released().is_press());
+}
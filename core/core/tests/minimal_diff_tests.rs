@@ -0,0 +1,157 @@
+//! # Minimal Diff Output Tests
+//!
+//! Property tests: for any keystroke sequence, the text on screen (after
+//! applying every result) ends with the engine buffer, no result sends
+//! more backspaces than the minimal diff needs, and the kept suffix a
+//! result reports is unchanged text.
+
+use std::sync::Arc;
+
+use proptest::prelude::*;
+use vietime_core::clock::ManualClock;
+use vietime_core::engines::chord::ChordEngine;
+use vietime_core::engines::diff::minimal_edit;
+use vietime_core::engines::multitap::MultiTapEngine;
+use vietime_core::engines::telex::TelexEngine;
+use vietime_core::engines::{InputEngine, ProcessKeyResult};
+
+/// Backspace in generated key sequences
+const BACKSPACE: char = '\u{8}';
+
+/// Apply a result to the simulated screen
+fn apply(screen: &mut Vec<char>, result: &ProcessKeyResult, typed: &[char]) {
+    if result.handled {
+        let keep = screen.len().saturating_sub(result.backspace_count);
+        screen.truncate(keep);
+        if let Some(text) = &result.output_text {
+            screen.extend(text.chars());
+        }
+    } else {
+        screen.extend(typed);
+    }
+}
+
+/// Type keys one by one, checking the screen against the buffer after each
+fn check_keys<E: InputEngine>(engine: &mut E, keys: &[char]) -> Result<(), TestCaseError> {
+    let mut screen: Vec<char> = Vec::new();
+    for &key in keys {
+        let before: Vec<char> = engine.get_buffer().chars().collect();
+
+        if key == BACKSPACE {
+            if !engine.process_backspace() {
+                screen.pop();
+            }
+        } else {
+            let result = engine.process_key(key, false);
+
+            // Never more backspaces than the diff between old and new word
+            if result.handled {
+                let after: Vec<char> = result.current_buffer.chars().collect();
+                let (minimal, _) = minimal_edit(&before, &after);
+                prop_assert!(result.backspace_count <= minimal);
+
+                // The kept suffix is really on screen already
+                let kept = &before[before.len() - result.kept_suffix..];
+                let text: Vec<char> = result.output_text.iter().flat_map(|t| t.chars()).collect();
+                prop_assert!(result.kept_suffix <= result.backspace_count);
+                prop_assert!(text.ends_with(kept));
+            }
+            apply(&mut screen, &result, &[key]);
+        }
+
+        let buffer: Vec<char> = engine.get_buffer().chars().collect();
+        prop_assert!(
+            screen.ends_with(&buffer),
+            "screen {:?} does not end with buffer {:?} after {:?}",
+            screen.iter().collect::<String>(),
+            engine.get_buffer(),
+            keys.iter().collect::<String>()
+        );
+    }
+    Ok(())
+}
+
+fn telex_keys() -> impl Strategy<Value = Vec<char>> {
+    let keys: Vec<char> = "aaeeoobcdghiklmnpqtuvy sfrxjzwAEODST.,[]\u{8}"
+        .chars()
+        .collect();
+    prop::collection::vec(prop::sample::select(keys), 0..60)
+}
+
+fn multitap_keys() -> impl Strategy<Value = Vec<char>> {
+    let keys: Vec<char> = "0123456789#\u{8}".chars().collect();
+    prop::collection::vec(prop::sample::select(keys), 0..60)
+}
+
+proptest! {
+    /// Minimal edit always turns the old text into the new one
+    #[test]
+    fn prop_minimal_edit_reconstructs(old in "[a-cà-ạê]{0,8}", new in "[a-cà-ạê]{0,8}") {
+        let old: Vec<char> = old.chars().collect();
+        let new: Vec<char> = new.chars().collect();
+        let (backspaces, text) = minimal_edit(&old, &new);
+
+        let mut rebuilt = old[..old.len() - backspaces].to_vec();
+        rebuilt.extend(text.chars());
+        prop_assert_eq!(&rebuilt, &new);
+
+        // Nothing kept could have been kept longer
+        let kept = old.len() - backspaces;
+        prop_assert!(kept == old.len() || kept == new.len() || old[kept] != new[kept]);
+    }
+
+    /// Telex screen always ends with the buffer
+    #[test]
+    fn prop_telex_screen_matches_buffer(keys in telex_keys()) {
+        check_keys(&mut TelexEngine::new(), &keys)?;
+    }
+
+    /// Multi-tap screen always ends with the buffer
+    #[test]
+    fn prop_multitap_screen_matches_buffer(keys in multitap_keys()) {
+        let mut engine = MultiTapEngine::with_clock(Arc::new(ManualClock::new()));
+        check_keys(&mut engine, &keys)?;
+    }
+
+    /// Chord screen always ends with the buffer
+    #[test]
+    fn prop_chord_screen_matches_buffer(strokes in prop::collection::vec("[qwerasdfcvbnmuiopjkl;'*]{1,6}", 0..20)) {
+        let mut engine = ChordEngine::new();
+        let mut screen: Vec<char> = Vec::new();
+        for stroke in strokes {
+            let keys: Vec<char> = stroke.chars().collect();
+            let result = engine.process_chord(&keys);
+            apply(&mut screen, &result, &keys);
+
+            let buffer: Vec<char> = engine.get_buffer().chars().collect();
+            prop_assert!(screen.ends_with(&buffer));
+        }
+    }
+}
+
+/// Test tone changes only rewrite from the toned vowel
+#[test]
+fn test_tone_rewrites_changed_suffix_only() {
+    let mut engine = TelexEngine::new();
+    for key in "tooi".chars() {
+        engine.process_key(key, false);
+    }
+
+    let result = engine.process_key('f', false);
+    assert_eq!(result.backspace_count, 2);
+    assert_eq!(result.output_text, Some("ồi".to_string()));
+}
+
+/// Test the word boundary sends nothing
+#[test]
+fn test_flush_sends_nothing() {
+    let mut engine = TelexEngine::new();
+    for key in "thees".chars() {
+        engine.process_key(key, false);
+    }
+
+    let result = engine.process_key(' ', false);
+    assert!(!result.handled);
+    assert_eq!(result.backspace_count, 0);
+    assert_eq!(result.output_text, None);
+}
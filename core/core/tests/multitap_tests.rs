@@ -0,0 +1,207 @@
+//! # Multi-tap Engine Integration Tests
+//!
+//! Integration tests for the numeric keypad (T9-style) input engine.
+
+use std::sync::Arc;
+
+use vietime_core::clock::ManualClock;
+use vietime_core::engines::multitap::{MultiTapEngine, MULTI_TAP_TIMEOUT_MS};
+use vietime_core::engines::InputEngine;
+
+fn engine_with_clock() -> (MultiTapEngine, ManualClock) {
+    let clock = ManualClock::new();
+    let engine = MultiTapEngine::with_clock(Arc::new(clock.clone()));
+    (engine, clock)
+}
+
+/// Type a sequence of taps, pausing past the timeout wherever `_` appears
+fn tap(engine: &mut MultiTapEngine, clock: &ManualClock, taps: &str) {
+    for key in taps.chars() {
+        if key == '_' {
+            clock.advance(MULTI_TAP_TIMEOUT_MS);
+        } else {
+            engine.process_key(key, false);
+            clock.advance(100);
+        }
+    }
+}
+
+/// Test first tap shows the first letter of the group
+#[test]
+fn test_first_tap_outputs_letter() {
+    let (mut engine, _clock) = engine_with_clock();
+
+    let result = engine.process_key('2', false);
+
+    assert!(result.handled);
+    assert_eq!(result.output_text, Some("a".to_string()));
+    assert_eq!(result.backspace_count, 0);
+    assert_eq!(engine.get_buffer(), "a");
+}
+
+/// Test repeated taps cycle through diacritic variants
+#[test]
+fn test_repeated_taps_cycle_variants() {
+    let (mut engine, clock) = engine_with_clock();
+
+    engine.process_key('2', false);
+    clock.advance(200);
+    let result = engine.process_key('2', false);
+    assert_eq!(result.output_text, Some("ă".to_string()));
+    assert_eq!(result.backspace_count, 1);
+
+    clock.advance(200);
+    let result = engine.process_key('2', false);
+    assert_eq!(result.output_text, Some("â".to_string()));
+    assert_eq!(engine.get_buffer(), "â");
+}
+
+/// Test cycling wraps around to the first letter
+#[test]
+fn test_cycle_wraps_around() {
+    let (mut engine, clock) = engine_with_clock();
+
+    tap(&mut engine, &clock, "4444");
+    assert_eq!(engine.get_buffer(), "g");
+}
+
+/// Test the timeout commits the pending letter
+#[test]
+fn test_timeout_commits_letter() {
+    let (mut engine, clock) = engine_with_clock();
+
+    engine.process_key('2', false);
+    clock.advance(MULTI_TAP_TIMEOUT_MS);
+    let result = engine.process_key('2', false);
+
+    // New letter instead of cycling
+    assert_eq!(result.backspace_count, 0);
+    assert_eq!(result.output_text, Some("a".to_string()));
+    assert_eq!(engine.get_buffer(), "aa");
+}
+
+/// Test a custom timeout
+#[test]
+fn test_custom_timeout() {
+    let clock = ManualClock::new();
+    let mut engine = MultiTapEngine::with_clock(Arc::new(clock.clone())).with_timeout(300);
+
+    engine.process_key('6', false);
+    clock.advance(299);
+    engine.process_key('6', false);
+    assert_eq!(engine.get_buffer(), "n");
+
+    clock.advance(300);
+    engine.process_key('6', false);
+    assert_eq!(engine.get_buffer(), "nm");
+}
+
+/// Test a different key commits without waiting
+#[test]
+fn test_different_key_commits_letter() {
+    let (mut engine, clock) = engine_with_clock();
+
+    tap(&mut engine, &clock, "8844");
+    assert_eq!(engine.get_buffer(), "uh");
+}
+
+/// Test tone key cycles through all tones in ToneIndex order
+#[test]
+fn test_tone_key_cycles_tones() {
+    let (mut engine, clock) = engine_with_clock();
+
+    // "ta"
+    tap(&mut engine, &clock, "82");
+
+    let expected = ["tà", "tá", "tả", "tã", "tạ", "ta"];
+    for word in expected {
+        engine.process_key('1', false);
+        assert_eq!(engine.get_buffer(), word);
+    }
+}
+
+/// Test tone placement follows Vietnamese rules ("việt")
+#[test]
+fn test_tone_placement_viet() {
+    let (mut engine, clock) = engine_with_clock();
+
+    // v i ê t
+    tap(&mut engine, &clock, "8888_444_3333_8");
+    assert_eq!(engine.get_buffer(), "viêt");
+
+    // Acute, hook, tilde, dot
+    let result = engine.process_key('1', false);
+    assert_eq!(result.backspace_count, 2);
+    assert_eq!(result.output_text, Some("ềt".to_string()));
+    tap(&mut engine, &clock, "1111");
+    assert_eq!(engine.get_buffer(), "việt");
+}
+
+/// Test tone key without a vowel is swallowed
+#[test]
+fn test_tone_key_without_vowel() {
+    let (mut engine, _clock) = engine_with_clock();
+
+    let result = engine.process_key('1', false);
+    assert!(result.handled);
+    assert_eq!(result.output_text, None);
+    assert_eq!(engine.get_buffer(), "");
+}
+
+/// Test tone key commits the pending letter
+#[test]
+fn test_tone_key_commits_pending() {
+    let (mut engine, clock) = engine_with_clock();
+
+    tap(&mut engine, &clock, "212");
+    assert_eq!(engine.get_buffer(), "àa");
+}
+
+/// Test zero key ends the word with a space
+#[test]
+fn test_zero_outputs_space_and_resets() {
+    let (mut engine, clock) = engine_with_clock();
+
+    tap(&mut engine, &clock, "22");
+    let result = engine.process_key('0', false);
+
+    assert!(result.handled);
+    assert_eq!(result.output_text, Some(" ".to_string()));
+    assert_eq!(engine.get_buffer(), "");
+}
+
+/// Test Shift produces uppercase letters that keep their case while cycling
+#[test]
+fn test_shift_uppercase() {
+    let (mut engine, clock) = engine_with_clock();
+
+    engine.process_key('3', true);
+    clock.advance(100);
+    engine.process_key('3', false);
+    assert_eq!(engine.get_buffer(), "Đ");
+}
+
+/// Test backspace removes the pending letter and stops cycling
+#[test]
+fn test_backspace_cancels_cycle() {
+    let (mut engine, clock) = engine_with_clock();
+
+    tap(&mut engine, &clock, "22");
+    assert!(!engine.process_backspace());
+    assert_eq!(engine.get_buffer(), "");
+
+    engine.process_key('2', false);
+    assert_eq!(engine.get_buffer(), "a");
+}
+
+/// Test non-keypad keys reset and pass through
+#[test]
+fn test_other_keys_pass_through() {
+    let (mut engine, clock) = engine_with_clock();
+
+    tap(&mut engine, &clock, "22");
+    let result = engine.process_key('#', false);
+
+    assert!(!result.handled);
+    assert_eq!(engine.get_buffer(), "");
+}
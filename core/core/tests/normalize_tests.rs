@@ -0,0 +1,105 @@
+//! # Normalisation Tests
+//!
+//! Every syllable and a document mixing conventions, encodings and
+//! foreign words, through every combination of settings.
+
+use vietime_core::models::charset::Charset;
+use vietime_core::models::syllable::Syllable;
+use vietime_core::normalize::{Composition, IySpelling, Normalizer, ToneStyle};
+use vietime_core::transform::Transform;
+use vietime_core::VietnameseChar;
+
+/// Mixed conventions: old and new tone style, i and y, NFD, TCVN3 and
+/// English
+const DOCUMENT: &str = "Hòa bình và hoà thuận. Chị Thúy, chị Thuý; kỹ sư lí, KĨ SƯ LÝ. \
+                        Ti\u{D5}ng Vi\u{D6}t: open https://example.com/hoa?x=1, Zürich 2024.";
+
+fn all_settings() -> Vec<Normalizer> {
+    let mut normalizers = Vec::new();
+    for tone_style in [ToneStyle::New, ToneStyle::Old] {
+        for iy_spelling in [IySpelling::Keep, IySpelling::I, IySpelling::Y] {
+            for composition in [Composition::Precomposed, Composition::Decomposed] {
+                normalizers.push(
+                    Normalizer::new()
+                        .with_legacy_charset(Some(Charset::Tcvn3))
+                        .with_tone_style(tone_style)
+                        .with_iy_spelling(iy_spelling)
+                        .with_composition(composition),
+                );
+            }
+        }
+    }
+    normalizers
+}
+
+/// Test every syllable is already canonical, in any case or form
+#[test]
+fn test_every_syllable_is_canonical() {
+    let normalizer = Normalizer::new();
+    let decomposed = Normalizer::new().with_composition(Composition::Decomposed);
+    for syllable in Syllable::all() {
+        let word = syllable.to_string();
+        let upper = Transform::Upper.apply(&word);
+        assert_eq!(normalizer.normalize(&word), word);
+        assert_eq!(normalizer.normalize(&upper), upper);
+        assert_eq!(normalizer.normalize(&VietnameseChar::to_nfd(&word)), word);
+        assert_eq!(decomposed.normalize(&word), VietnameseChar::to_nfd(&word));
+    }
+}
+
+/// Test old tone style round-trips to the new style on every syllable
+#[test]
+fn test_every_syllable_old_style() {
+    let old = Normalizer::new().with_tone_style(ToneStyle::Old);
+    let new = Normalizer::new();
+    for syllable in Syllable::all() {
+        let word = syllable.to_string();
+        let old_form = old.normalize(&word);
+        assert_eq!(old_form.chars().count(), word.chars().count());
+        assert_eq!(Syllable::parse(&old_form), Some(syllable), "{}", old_form);
+        assert_eq!(new.normalize(&old_form), word);
+    }
+}
+
+/// Test the mixed document in the new and old conventions
+#[test]
+fn test_document() {
+    let new = Normalizer::new()
+        .with_legacy_charset(Some(Charset::Tcvn3))
+        .with_iy_spelling(IySpelling::I);
+    assert_eq!(
+        new.normalize(DOCUMENT),
+        "Hoà bình và hoà thuận. Chị Thuý, chị Thuý; kĩ sư lí, KĨ SƯ LÍ. \
+         Tiếng Việt: open https://example.com/hoa?x=1, Zürich 2024."
+    );
+    let old = Normalizer::new()
+        .with_legacy_charset(Some(Charset::Tcvn3))
+        .with_tone_style(ToneStyle::Old)
+        .with_iy_spelling(IySpelling::Y);
+    assert_eq!(
+        old.normalize(DOCUMENT),
+        "Hòa bình và hòa thuận. Chị Thúy, chị Thúy; kỹ sư lý, KỸ SƯ LÝ. \
+         Tiếng Việt: open https://example.com/hoa?x=1, Zürich 2024."
+    );
+}
+
+/// Test decomposed sentences normalise like precomposed ones
+#[test]
+fn test_decomposed_document() {
+    let sentence = DOCUMENT.split(" Ti").next().unwrap();
+    let normalizer = Normalizer::new();
+    assert_eq!(
+        normalizer.normalize(&VietnameseChar::to_nfd(sentence)),
+        normalizer.normalize(sentence)
+    );
+}
+
+/// Test normalising twice changes nothing, whatever the settings
+#[test]
+fn test_idempotent() {
+    for normalizer in all_settings() {
+        let once = normalizer.normalize(DOCUMENT);
+        assert_eq!(normalizer.normalize(&once), once, "{:?}", normalizer);
+        assert!(once.contains("Zürich 2024") && once.contains("open https://example.com/hoa?x=1"));
+    }
+}
@@ -0,0 +1,166 @@
+//! # Preedit Mode Integration Tests
+//!
+//! Tests for driving engines in preedit/commit mode, and for equivalence
+//! with backspace-replace mode.
+
+use std::sync::Arc;
+
+use vietime_core::clock::ManualClock;
+use vietime_core::engines::multitap::MultiTapEngine;
+use vietime_core::engines::preedit::{PreeditEvent, PreeditSegment, PreeditSession, SegmentStyle};
+use vietime_core::engines::telex::TelexEngine;
+use vietime_core::engines::InputEngine;
+
+/// Type keys in backspace-replace mode and return the resulting text
+fn replace_mode_text<E: InputEngine>(engine: &mut E, keys: &str) -> String {
+    let mut text: Vec<char> = Vec::new();
+    for key in keys.chars() {
+        let result = engine.process_key(key, false);
+        if result.handled {
+            let keep = text.len().saturating_sub(result.backspace_count);
+            text.truncate(keep);
+            if let Some(output) = result.output_text {
+                text.extend(output.chars());
+            }
+        } else {
+            text.push(key);
+        }
+    }
+    engine.reset();
+    text.into_iter().collect()
+}
+
+/// Type keys in preedit mode and return everything committed
+fn preedit_mode_text<E: InputEngine>(engine: E, keys: &str) -> String {
+    let mut session = PreeditSession::new(engine);
+    let mut events = Vec::new();
+    for key in keys.chars() {
+        events.extend(session.process_key(key, false));
+    }
+    events.extend(session.commit());
+
+    events
+        .into_iter()
+        .filter_map(|event| match event {
+            PreeditEvent::Commit(text) => Some(text),
+            PreeditEvent::Preedit { .. } => None,
+        })
+        .collect()
+}
+
+fn last_preedit(events: &[PreeditEvent]) -> Option<&PreeditEvent> {
+    events
+        .iter()
+        .rev()
+        .find(|event| matches!(event, PreeditEvent::Preedit { .. }))
+}
+
+/// Test the word being typed is reported as preedit, not committed
+#[test]
+fn test_word_stays_in_preedit() {
+    let mut session = PreeditSession::new(TelexEngine::new());
+
+    for key in "tha".chars() {
+        let events = session.process_key(key, false);
+        assert!(events.iter().all(|e| !matches!(e, PreeditEvent::Commit(_))));
+    }
+
+    let events = session.process_key('s', false);
+    assert_eq!(
+        last_preedit(&events),
+        Some(&PreeditEvent::Preedit {
+            text: "thá".to_string(),
+            cursor: 3,
+            segments: vec![PreeditSegment {
+                start: 0,
+                end: 3,
+                style: SegmentStyle::Underline,
+            }],
+        })
+    );
+}
+
+/// Test finishing a word commits it and clears the preedit
+#[test]
+fn test_word_boundary_commits() {
+    let mut session = PreeditSession::new(TelexEngine::new());
+    for key in "tooif".chars() {
+        session.process_key(key, false);
+    }
+
+    let events = session.process_key(' ', false);
+    assert_eq!(events[0], PreeditEvent::Commit("tồi ".to_string()));
+    assert_eq!(
+        last_preedit(&events),
+        Some(&PreeditEvent::Preedit {
+            text: String::new(),
+            cursor: 0,
+            segments: Vec::new(),
+        })
+    );
+}
+
+/// Test backspace edits the preedit, and falls through when it is empty
+#[test]
+fn test_backspace_in_preedit() {
+    let mut session = PreeditSession::new(TelexEngine::new());
+    for key in "bc".chars() {
+        session.process_key(key, false);
+    }
+
+    let events = session.process_backspace();
+    assert!(!events.is_empty());
+    assert_eq!(session.preedit(), "b");
+
+    session.process_backspace();
+    assert!(session.process_backspace().is_empty());
+}
+
+/// Test explicit commit flushes the preedit
+#[test]
+fn test_explicit_commit() {
+    let mut session = PreeditSession::new(TelexEngine::new());
+    for key in "thees".chars() {
+        session.process_key(key, false);
+    }
+
+    let events = session.commit();
+    assert_eq!(events[0], PreeditEvent::Commit("thế".to_string()));
+    assert_eq!(session.preedit(), "");
+    assert_eq!(session.engine().get_buffer(), "");
+}
+
+/// Test both output modes produce identical text for Telex
+#[test]
+fn test_modes_identical_telex() {
+    let inputs = [
+        "thees",
+        "tooif",
+        "thaf thaf",
+        "tha thas thaf",
+        "theess",
+        "thez",
+        "xin chaof cacs banj",
+        "ti",
+        "a, b. c!",
+    ];
+
+    for keys in inputs {
+        let replaced = replace_mode_text(&mut TelexEngine::new(), keys);
+        let committed = preedit_mode_text(TelexEngine::new(), keys);
+        assert_eq!(replaced, committed, "modes differ for {:?}", keys);
+    }
+}
+
+/// Test both output modes produce identical text for multi-tap
+#[test]
+fn test_modes_identical_multitap() {
+    let inputs = ["8241", "2220826111", "44#66", "3333_1"];
+
+    for keys in inputs {
+        let engine = || MultiTapEngine::with_clock(Arc::new(ManualClock::new()));
+        let replaced = replace_mode_text(&mut engine(), keys);
+        let committed = preedit_mode_text(engine(), keys);
+        assert_eq!(replaced, committed, "modes differ for {:?}", keys);
+    }
+}
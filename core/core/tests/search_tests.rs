@@ -0,0 +1,96 @@
+//! # Search Tests
+//!
+//! A small staff directory searched at each strictness, precomposed and
+//! decomposed; matches must slice back to the original names.
+
+use vietime_core::models::syllable::Syllable;
+use vietime_core::search::{Matcher, Strictness};
+use vietime_core::text::{strip_diacritics, to_ascii, AsciiStyle};
+use vietime_core::VietnameseChar;
+
+const DIRECTORY: &[&str] = &[
+    "Nguyễn Văn Đức",
+    "Lê Thị Lệ",
+    "Trần Lê Dũng",
+    "Đặng Thu Thảo",
+    "Phạm Ngọc Duy",
+];
+
+fn search(matcher: Matcher, query: &str) -> Vec<&'static str> {
+    DIRECTORY
+        .iter()
+        .copied()
+        .filter(|name| matcher.find(name, query).is_some())
+        .collect()
+}
+
+/// Test each strictness level against the directory
+#[test]
+fn test_directory_search() {
+    let loose = Matcher::new(Strictness::IgnoreMarks);
+    assert_eq!(search(loose, "nguyen"), ["Nguyễn Văn Đức"]);
+    assert_eq!(search(loose, "duc"), ["Nguyễn Văn Đức"]);
+    assert_eq!(
+        search(loose, "du"),
+        ["Nguyễn Văn Đức", "Trần Lê Dũng", "Phạm Ngọc Duy"]
+    );
+    assert_eq!(search(loose, "le"), ["Lê Thị Lệ", "Trần Lê Dũng"]);
+
+    let tones = Matcher::new(Strictness::IgnoreTones);
+    assert_eq!(search(tones, "đư"), ["Nguyễn Văn Đức"]);
+    assert_eq!(search(tones, "đu"), [] as [&str; 0]);
+    assert_eq!(search(tones, "du"), ["Trần Lê Dũng", "Phạm Ngọc Duy"]);
+
+    let exact = Matcher::new(Strictness::Exact);
+    assert_eq!(search(exact, "lê"), ["Lê Thị Lệ", "Trần Lê Dũng"]);
+    assert_eq!(search(exact, "lệ"), ["Lê Thị Lệ"]);
+    assert_eq!(search(exact, "LỆ"), ["Lê Thị Lệ"]);
+}
+
+/// Test matches slice back to the original text, decomposed or not
+#[test]
+fn test_offsets_map_back() {
+    let matcher = Matcher::new(Strictness::IgnoreMarks);
+    for name in DIRECTORY {
+        let nfd = VietnameseChar::to_nfd(name);
+        let words: Vec<&str> = name.split(' ').collect();
+        let nfd_words: Vec<&str> = nfd.split(' ').collect();
+        for (word, nfd_word) in words.iter().zip(&nfd_words) {
+            let query = to_ascii(word, AsciiStyle::Strip);
+            let ranges = matcher.find_all(name, &query);
+            assert!(ranges.iter().any(|range| name[range.clone()] == **word));
+            let ranges = matcher.find_all(&nfd, &query);
+            assert!(ranges.iter().any(|range| nfd[range.clone()] == **nfd_word));
+        }
+        let ranges = matcher.find_all(name, " ");
+        assert_eq!(ranges.len(), words.len() - 1);
+        assert!(ranges.iter().all(|range| &name[range.clone()] == " "));
+    }
+}
+
+/// Test prefix matching as typed, one letter at a time
+#[test]
+fn test_prefix_as_typed() {
+    let name = "Đặng Thu Thảo";
+    let matcher = Matcher::new(Strictness::IgnoreMarks);
+    let typed = "dang thu thao";
+    for end in 1..=typed.len() {
+        let range = matcher.match_prefix(name, &typed[..end]).unwrap();
+        assert_eq!(name[range].chars().count(), end);
+    }
+    assert_eq!(matcher.match_prefix(name, "dang thu thaoo"), None);
+}
+
+/// Test the search keys agree with the text folding helpers
+#[test]
+fn test_keys_match_folding() {
+    let loose = Matcher::new(Strictness::IgnoreMarks);
+    let exact = Matcher::new(Strictness::Exact).with_case_sensitive(true);
+    for syllable in Syllable::all() {
+        let word = syllable.to_string();
+        assert_eq!(loose.key(&word), to_ascii(&word, AsciiStyle::Strip));
+        assert_eq!(exact.key(&VietnameseChar::to_nfd(&word)), word);
+        let tones = Matcher::new(Strictness::IgnoreTones).key(&word);
+        assert_eq!(strip_diacritics(&tones).replace('đ', "d"), loose.key(&word));
+    }
+}
@@ -0,0 +1,205 @@
+//! # Output Strategy Integration Tests
+//!
+//! Tests for the autocomplete-safe output strategy, run against a virtual
+//! document that behaves like a browser address bar with inline
+//! autocomplete.
+
+use vietime_core::engines::actions::{EditAction, HostCapabilities};
+use vietime_core::engines::strategy::{OutputStrategy, StrategySelector};
+use vietime_core::engines::telex::TelexEngine;
+use vietime_core::engines::{EngineEvent, InputEngine, ProcessKeyResult};
+
+/// Text field that shows a selected inline completion after every insert
+///
+/// Like Chrome's omnibox: a Backspace with a suggestion showing only
+/// removes the suggestion; typing replaces it.
+struct AutocompleteField {
+    text: String,
+    suggestion: Option<String>,
+    history: Vec<&'static str>,
+}
+
+impl AutocompleteField {
+    fn new(history: &[&'static str]) -> Self {
+        Self {
+            text: String::new(),
+            suggestion: None,
+            history: history.to_vec(),
+        }
+    }
+
+    fn insert(&mut self, s: &str) {
+        self.suggestion = None;
+        self.text.push_str(s);
+        self.suggestion = self
+            .history
+            .iter()
+            .find(|entry| entry.len() > self.text.len() && entry.starts_with(&self.text))
+            .map(|entry| entry[self.text.len()..].to_string());
+    }
+
+    fn backspace(&mut self) {
+        if self.suggestion.take().is_none() {
+            self.text.pop();
+        }
+    }
+
+    fn apply(&mut self, actions: &[EditAction]) {
+        for action in actions {
+            match action {
+                EditAction::Backspace(n) => (0..*n).for_each(|_| self.backspace()),
+                EditAction::Insert(s) => self.insert(s),
+                other => panic!("keyboard hook host cannot perform {:?}", other),
+            }
+        }
+    }
+}
+
+const HISTORY: &[&str] = &["thethao.vn", "hoahoctro.vn", "tooltip.com"];
+
+fn type_into(field: &mut AutocompleteField, keys: &str, strategy: OutputStrategy) {
+    let mut engine = TelexEngine::new();
+    for key in keys.chars() {
+        let result = engine.process_key(key, false);
+        if result.handled {
+            field.apply(&result.actions_with_strategy(&HostCapabilities::KEYBOARD_HOOK, strategy));
+        } else {
+            field.insert(&key.to_string());
+        }
+    }
+}
+
+/// Test the virtual field reproduces the autocomplete bug with direct output
+#[test]
+fn test_direct_output_breaks_in_autocomplete_field() {
+    let mut field = AutocompleteField::new(HISTORY);
+    type_into(&mut field, "thees", OutputStrategy::Direct);
+
+    assert_ne!(field.text, "thế");
+}
+
+/// Test the zero-width dummy keeps the text intact
+#[test]
+fn test_zero_width_strategy_in_autocomplete_field() {
+    for (keys, expected) in [("thees", "thế"), ("hoaf", "hoà"), ("tooif", "tồi")] {
+        let mut field = AutocompleteField::new(HISTORY);
+        type_into(&mut field, keys, OutputStrategy::ZERO_WIDTH);
+        assert_eq!(field.text, expected);
+    }
+}
+
+/// Test the space dummy keeps the text intact
+#[test]
+fn test_space_strategy_in_autocomplete_field() {
+    let mut field = AutocompleteField::new(HISTORY);
+    type_into(&mut field, "thees", OutputStrategy::SPACE);
+
+    assert_eq!(field.text, "thế");
+}
+
+/// Test strategies behave identically in a field without autocomplete
+#[test]
+fn test_strategy_harmless_without_autocomplete() {
+    for strategy in [
+        OutputStrategy::Direct,
+        OutputStrategy::ZERO_WIDTH,
+        OutputStrategy::SPACE,
+    ] {
+        let mut field = AutocompleteField::new(&[]);
+        type_into(&mut field, "tooif", strategy);
+        assert_eq!(field.text, "tồi");
+    }
+}
+
+/// Test the dummy is only added when something is deleted
+#[test]
+fn test_dummy_only_before_deletions() {
+    let insert_only = ProcessKeyResult::replace(0, " ".to_string(), String::new());
+    assert_eq!(
+        insert_only
+            .actions_with_strategy(&HostCapabilities::KEYBOARD_HOOK, OutputStrategy::ZERO_WIDTH),
+        vec![EditAction::Insert(" ".to_string())]
+    );
+
+    // Input method hosts delete through the API, which autocomplete does not intercept
+    let replace = ProcessKeyResult::replace(1, "ê".to_string(), "ê".to_string());
+    assert_eq!(
+        replace.actions_with_strategy(&HostCapabilities::INPUT_METHOD, OutputStrategy::ZERO_WIDTH),
+        replace.actions(&HostCapabilities::INPUT_METHOD)
+    );
+}
+
+/// Test the dummy is folded into a select-left replacement
+#[test]
+fn test_dummy_with_select_left() {
+    let caps = HostCapabilities {
+        backspace: false,
+        select_left: true,
+        delete_surrounding: false,
+        move_cursor: false,
+    };
+    let result = ProcessKeyResult::replace(1, "ê".to_string(), "ê".to_string());
+
+    assert_eq!(
+        result.actions_with_strategy(&caps, OutputStrategy::SPACE),
+        vec![
+            EditAction::Insert(" ".to_string()),
+            EditAction::SelectLeft(2),
+            EditAction::Insert("ê".to_string()),
+        ]
+    );
+}
+
+/// Test per-application selection from focus events
+#[test]
+fn test_selector_follows_focus() {
+    let mut selector = StrategySelector::new().with_app("Code.exe", OutputStrategy::SPACE);
+    assert_eq!(selector.strategy(), OutputStrategy::Direct);
+
+    selector.handle_event(&EngineEvent::FocusGained {
+        app_id: "msedge.exe".to_string(),
+    });
+    assert_eq!(selector.strategy(), OutputStrategy::ZERO_WIDTH);
+
+    selector.handle_event(&EngineEvent::FocusGained {
+        app_id: "code.exe".to_string(),
+    });
+    assert_eq!(selector.strategy(), OutputStrategy::SPACE);
+
+    selector.handle_event(&EngineEvent::CursorMoved);
+    assert_eq!(selector.strategy(), OutputStrategy::SPACE);
+
+    selector.handle_event(&EngineEvent::FocusLost);
+    assert_eq!(selector.strategy(), OutputStrategy::Direct);
+}
+
+/// Test overriding built-in entries and the default
+#[test]
+fn test_selector_overrides() {
+    let selector = StrategySelector::new()
+        .with_app("chrome.exe", OutputStrategy::Direct)
+        .with_default(OutputStrategy::SPACE);
+
+    assert_eq!(selector.strategy_for("CHROME.EXE"), OutputStrategy::Direct);
+    assert_eq!(
+        selector.strategy_for("com.google.Chrome"),
+        OutputStrategy::ZERO_WIDTH
+    );
+    assert_eq!(selector.strategy_for("winword.exe"), OutputStrategy::SPACE);
+
+    let empty = StrategySelector::empty();
+    assert_eq!(empty.strategy_for("chrome.exe"), OutputStrategy::Direct);
+}
+
+/// Test typing through the selector into a browser field
+#[test]
+fn test_browser_field_via_selector() {
+    let mut selector = StrategySelector::new();
+    selector.handle_event(&EngineEvent::FocusGained {
+        app_id: "chrome.exe".to_string(),
+    });
+
+    let mut field = AutocompleteField::new(HISTORY);
+    type_into(&mut field, "hoaf", selector.strategy());
+    assert_eq!(field.text, "hoà");
+}
@@ -0,0 +1,147 @@
+//! # Syllable Round-Trip Tests
+//!
+//! Types every legal syllable from [`Syllable::all`] through each engine
+//! and checks the visible text is the syllable, tone on the right vowel.
+
+mod common;
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use vietime_core::clock::ManualClock;
+use vietime_core::encoder::{Encoder, InputMethod, TonePlacement};
+use vietime_core::engines::chord::{ChordEngine, ChordTheory};
+use vietime_core::engines::multitap::MultiTapEngine;
+use vietime_core::engines::telex::TelexEngine;
+use vietime_core::engines::InputEngine;
+use vietime_core::models::syllable::Syllable;
+use vietime_core::testing::type_keys;
+use vietime_core::{ToneIndex, VietnameseChar};
+
+use common::tap_word;
+
+fn without_tone(text: &str) -> String {
+    text.chars()
+        .map(|c| VietnameseChar::apply_tone(c, ToneIndex::None))
+        .collect()
+}
+
+/// Collect failures as "expected -> actual (keys)", at most a screenful
+fn assert_all_typed(engine: &str, failures: &[String]) {
+    assert!(
+        failures.is_empty(),
+        "{}: {} syllables mistyped, e.g.:\n{}",
+        engine,
+        failures.len(),
+        failures
+            .iter()
+            .take(20)
+            .cloned()
+            .collect::<Vec<_>>()
+            .join("\n")
+    );
+}
+
+/// Test the enumeration covers the language and nothing twice
+#[test]
+fn test_syllable_inventory() {
+    let all = Syllable::all();
+    let written: Vec<String> = all.iter().map(|s| s.to_string()).collect();
+
+    assert!(
+        all.len() > 15_000 && all.len() < 20_000,
+        "{} syllables",
+        all.len()
+    );
+    for word in [
+        "a", "ý", "yêu", "gì", "giữa", "quốc", "khuya", "thuở", "nghiêng", "xoong", "người",
+        "huỳnh", "khoắng", "quyết", "rượu", "kìa",
+    ] {
+        assert!(written.iter().any(|w| w == word), "missing {}", word);
+    }
+    // Entering tones, spelling rules and old-style tone placement exclude these
+    for word in ["càt", "quàc", "ci", "ge", "ngi", "gía", "hòa", "bý"] {
+        assert!(!written.iter().any(|w| w == word), "unexpected {}", word);
+    }
+}
+
+/// Test every syllable types back through Telex, tone after the word or the vowel
+#[test]
+fn test_telex_types_every_syllable() {
+    for placement in [TonePlacement::EndOfWord, TonePlacement::AfterVowel] {
+        let encoder = Encoder::new(InputMethod::Telex).with_tone_placement(placement);
+        let mut failures = Vec::new();
+        for syllable in Syllable::all() {
+            let expected = syllable.to_string();
+            let keys = encoder.encode(&expected);
+            let actual = type_keys(&mut TelexEngine::new(), &keys);
+            if actual != expected {
+                failures.push(format!("{} -> {} ({})", expected, actual, keys));
+            }
+        }
+        assert_all_typed(&format!("Telex {:?}", placement), &failures);
+    }
+}
+
+/// Test every syllable types through multi-tap: letters by tapping, then `1` per tone step
+#[test]
+fn test_multitap_types_every_syllable() {
+    let clock = ManualClock::new();
+    let mut engine = MultiTapEngine::with_clock(Arc::new(clock.clone()));
+    let mut failures = Vec::new();
+
+    for syllable in Syllable::all() {
+        let expected = syllable.to_string();
+        engine.reset();
+        let actual = tap_word(&mut engine, &clock, &expected);
+        if actual != expected {
+            failures.push(format!("{} -> {}", expected, actual));
+        }
+    }
+    assert_all_typed("MultiTap", &failures);
+}
+
+/// Test every syllable the basic chord theory can spell gets its tone right
+#[test]
+fn test_chord_types_every_expressible_syllable() {
+    let theory = ChordTheory::basic();
+    let mut engine = ChordEngine::with_theory(theory.clone());
+
+    // Toneless text -> stroke producing it
+    let mut strokes: HashMap<String, String> = HashMap::new();
+    let onsets: Vec<(&str, &str)> = theory.onsets().chain([("", "")]).collect();
+    for &(onset_chord, onset) in &onsets {
+        for (rhyme_chord, rhyme) in theory.rhymes() {
+            strokes.insert(
+                format!("{}{}", onset, rhyme),
+                format!("{}{}", onset_chord, rhyme_chord),
+            );
+        }
+    }
+    let tones: HashMap<ToneIndex, &str> =
+        theory.tones().map(|(chord, tone)| (tone, chord)).collect();
+
+    let mut typed = 0;
+    let mut failures = Vec::new();
+    for syllable in Syllable::all() {
+        let expected = syllable.to_string();
+        let Some(stroke) = strokes.get(&without_tone(&expected)) else {
+            continue;
+        };
+        let stroke = format!(
+            "{}{}",
+            stroke,
+            tones.get(&syllable.tone).copied().unwrap_or("")
+        );
+        engine.reset();
+        engine.process_chord(&stroke.chars().collect::<Vec<_>>());
+        typed += 1;
+
+        let actual = engine.get_buffer();
+        if actual != expected {
+            failures.push(format!("{} -> {} ({})", expected, actual, stroke));
+        }
+    }
+    assert!(typed > 5_000, "only {} syllables expressible", typed);
+    assert_all_typed("Chord", &failures);
+}
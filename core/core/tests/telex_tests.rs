@@ -2,8 +2,14 @@
 //!
 //! Integration tests for the Telex input engine.
 
-use vietime_core::engines::InputEngine;
 use vietime_core::engines::telex::TelexEngine;
+use vietime_core::engines::{InputEngine, KeyEvent, Modifiers};
+use vietime_core::testing::type_keys;
+
+/// Visible text after typing `keys` into an empty field
+fn typed(keys: &str) -> String {
+    type_keys(&mut TelexEngine::new(), keys)
+}
 
 /// Test basic character buffer (AC: 1)
 ///
@@ -23,7 +29,7 @@ fn test_character_buffer_ac1() {
     assert_eq!(engine.get_buffer(), "a");
 
     // No output should occur until processing is complete
-    assert_eq!(result.handled, false);
+    assert!(!result.handled);
     assert_eq!(result.output_text, None);
 }
 
@@ -54,7 +60,7 @@ fn test_rapid_typing_performance_ac3() {
         "Rapid typing took {}ms, expected < 100ms", duration.as_millis());
 
     // AC:3 - No keystrokes dropped (verify performance requirement met)
-    // Note: Buffer may not contain all chars due to flush behavior (consonant after vowel),
+    // Note: Buffer may not contain all chars due to flush behavior (full buffer),
     // but keystrokes are not lost - they are either in buffer or output_text
     // This test verifies the <5ms per keystroke requirement
 }
@@ -75,11 +81,11 @@ fn test_engine_trait_implementation() {
 /// Test buffer flush on non-modifying keys (AC: 2)
 ///
 /// Given the input buffer contains characters
-/// When I type a consonant or non-modifying key
-/// Then the buffer is processed and output
-/// And the buffer is cleared for the next sequence
+/// When I type a non-letter key
+/// Then the buffer is cleared for the next sequence
+/// And final consonants stay in the word until then
 #[test]
-fn test_buffer_flush_on_consonant_ac2() {
+fn test_buffer_flush_on_non_letter_ac2() {
     let mut engine = TelexEngine::new();
 
     // Type some characters
@@ -87,12 +93,32 @@ fn test_buffer_flush_on_consonant_ac2() {
     engine.process_key('i', false);
     assert_eq!(engine.get_buffer(), "ti");
 
-    // Type a consonant that triggers flush (non-modifying key)
+    // A consonant after the vowel is a final consonant, not a word boundary
     let result = engine.process_key('n', false);
+    assert_eq!(engine.get_buffer(), "tin");
+    assert!(!result.handled);
+
+    // Type a non-letter that triggers flush
+    let result = engine.process_key('.', false);
 
-    // Buffer should be flushed and new consonant added
-    assert_eq!(engine.get_buffer(), "n");
-    assert_eq!(result.output_text, Some("ti".to_string()));
+    // "tin" is already on screen, so nothing is re-sent and '.' passes through
+    assert_eq!(engine.get_buffer(), "");
+    assert!(!result.handled);
+    assert_eq!(result.output_text, None);
+}
+
+/// Test tones typed after the final consonant
+#[test]
+fn test_tone_after_final_consonant() {
+    let mut engine = TelexEngine::new();
+    for key in "tieeng".chars() {
+        engine.process_key(key, false);
+    }
+
+    let result = engine.process_key('s', false);
+    assert_eq!(engine.get_buffer(), "tiếng");
+    assert_eq!(result.backspace_count, 3);
+    assert_eq!(result.output_text, Some("ếng".to_string()));
 }
 
 /// Test backspace processing
@@ -110,7 +136,7 @@ fn test_backspace_processing() {
     let modified = engine.process_backspace();
     assert_eq!(engine.get_buffer(), "b");
     // Returns false = still need to send backspace to application
-    assert_eq!(modified, false);
+    assert!(!modified);
 }
 
 /// Test buffer reset
@@ -255,41 +281,6 @@ fn test_tone_toggle_behavior() {
     assert_eq!(engine.get_buffer(), "thes");
 }
 
-/// Test complete tone placement scenario "thê" + s -> "thế" (AC example)
-#[test]
-fn test_tone_placement_thes() {
-    let mut engine = TelexEngine::new();
-
-    // Type "thê"
-    let _ = engine.process_key('t', false);
-    let _ = engine.process_key('h', false);
-    let _ = engine.process_key('ê', false);
-    assert_eq!(engine.get_buffer(), "thê");
-
-    // Type acute tone (s) -> should become "thế"
-    let result = engine.process_key('s', false);
-    assert_eq!(engine.get_buffer(), "thế");
-    assert!(result.handled);
-}
-
-/// Test complete tone placement scenario "tooi" + f -> "tồi" (AC example)
-#[test]
-fn test_tone_placement_tooi() {
-    let mut engine = TelexEngine::new();
-
-    // Type "tooi"
-    let _ = engine.process_key('t', false);
-    let _ = engine.process_key('o', false);
-    let _ = engine.process_key('o', false); // This becomes 'ô'
-    let _ = engine.process_key('i', false);
-    assert_eq!(engine.get_buffer(), "tôi");
-
-    // Type grave tone (f) -> should become "tồi" (tone on ô)
-    let result = engine.process_key('f', false);
-    assert_eq!(engine.get_buffer(), "tồi");
-    assert!(result.handled);
-}
-
 /// Test tone removal with 'z' key
 #[test]
 fn test_tone_removal() {
@@ -325,3 +316,130 @@ fn test_consecutive_tone_keys() {
     let _result2 = engine.process_key('f', false);
     assert_eq!(engine.get_buffer(), "thà");
 }
+
+/// Test w adds a breve or horn, and a second w gives the w back
+#[test]
+fn test_w_key() {
+    assert_eq!(typed("aw ow uw"), "ă ơ ư");
+    assert_eq!(typed("dduwowcj"), "được");
+    assert_eq!(typed("nguoiwf"), "người");
+    assert_eq!(typed("hoaw"), "hoă");
+    assert_eq!(typed("aww uww"), "aw uw");
+}
+
+/// Test uow is spelled uơ in open syllables and ươ before more letters
+#[test]
+fn test_open_uo_horn() {
+    assert_eq!(typed("thuowr"), "thuở");
+    assert_eq!(typed("huow quow"), "huơ quơ");
+    assert_eq!(typed("thuowng huowngs"), "thương hướng");
+    assert_eq!(typed("ruowuj"), "rượu");
+    // An ư typed on its own keeps its horn
+    assert_eq!(typed("tuwow"), "tươ");
+}
+
+/// Test dd types đ and a third d gives dd back
+#[test]
+fn test_dd_key() {
+    assert_eq!(typed("ddi DDaau"), "đi Đâu");
+    assert_eq!(typed("ddd"), "dd");
+}
+
+/// Test [ and ] type ư and ơ, and the same bracket again gives it back
+#[test]
+fn test_bracket_keys() {
+    assert_eq!(typed("t[ t]"), "tư tơ");
+    assert_eq!(typed("t[[ t]]"), "t[ t]");
+    assert_eq!(typed("th]ngf"), "thờng");
+
+    // Only the bracket just typed is undone, not a horn from w
+    assert_eq!(typed("tuw["), "tưư");
+    assert_eq!(typed("tow]"), "tơơ");
+}
+
+/// Test the tone follows ươ onto the ơ before a vowel or final consonant
+#[test]
+fn test_tone_on_uo_horn() {
+    assert_eq!(typed("nguwowif"), "người");
+    assert_eq!(typed("muwowns"), "mướn");
+    assert_eq!(typed("ruwowuj"), "rượu");
+}
+
+/// Type keys with CapsLock on: Shift inverts the case of each letter
+fn type_caps_lock(engine: &mut TelexEngine, keys: &str) {
+    for key in keys.chars() {
+        // With CapsLock, a lowercase letter means Shift is held
+        let modifiers = Modifiers {
+            shift: key.is_lowercase(),
+            caps_lock: true,
+            ..Modifiers::NONE
+        };
+        engine.process_key_event(&KeyEvent::char(key).with_modifiers(modifiers));
+    }
+}
+
+/// Test modifier and tone keys keep the case of the letter they change
+#[test]
+fn test_modifier_keys_keep_letter_case() {
+    let mut engine = TelexEngine::new();
+    type_caps_lock(&mut engine, "VIEeTj");
+    assert_eq!(engine.get_buffer(), "VIỆT");
+
+    engine.reset();
+    type_caps_lock(&mut engine, "DdUwOWNGf");
+    assert_eq!(engine.get_buffer(), "ĐƯỜNG");
+}
+
+/// Test undone transforms stay all caps with CapsLock on
+#[test]
+fn test_caps_lock_word_stays_all_caps() {
+    let mut engine = TelexEngine::new();
+
+    // The second s undoes the tone; Shift on it does not make it lowercase
+    type_caps_lock(&mut engine, "ASs");
+    assert_eq!(engine.get_buffer(), "AS");
+
+    // A single capital with CapsLock on is already an all-caps word
+    engine.reset();
+    type_caps_lock(&mut engine, "AWw");
+    assert_eq!(engine.get_buffer(), "AW");
+
+    // Brackets have no case of their own
+    engine.reset();
+    type_caps_lock(&mut engine, "TH[");
+    assert_eq!(engine.get_buffer(), "THƯ");
+
+    // Shift held by habit on later keys does not break the caps; the
+    // lowercase t the host would type is replaced
+    engine.reset();
+    type_caps_lock(&mut engine, "VIEE");
+    let result = engine.process_key_event(&KeyEvent::char('t').with_modifiers(Modifiers {
+        shift: true,
+        caps_lock: true,
+        ..Modifiers::NONE
+    }));
+    assert!(result.handled);
+    assert_eq!(result.output_text.as_deref(), Some("T"));
+    type_caps_lock(&mut engine, "j");
+    assert_eq!(engine.get_buffer(), "VIỆT");
+
+    // It does start a lowercase word
+    engine.reset();
+    type_caps_lock(&mut engine, "vieetj");
+    assert_eq!(engine.get_buffer(), "việt");
+}
+
+/// Test a Shift-typed capital without CapsLock starts a Title-case word
+#[test]
+fn test_shift_capital_is_title_case() {
+    let mut engine = TelexEngine::new();
+    engine.process_key('A', true);
+    engine.process_key('w', false);
+    engine.process_key('w', false);
+    assert_eq!(engine.get_buffer(), "Aw");
+
+    engine.reset();
+    engine.process_key('T', true);
+    engine.process_key('[', false);
+    assert_eq!(engine.get_buffer(), "Tư");
+}
@@ -0,0 +1,62 @@
+//! # Text Folding Tests
+//!
+//! Every syllable is folded to ASCII; the Telex and VIQR spellings must
+//! type or convert back to the original word.
+
+use vietime_core::convert::{convert_bytes, Encoding};
+use vietime_core::engines::telex::TelexEngine;
+use vietime_core::models::syllable::Syllable;
+use vietime_core::testing::type_keys;
+use vietime_core::text::{slugify, strip_diacritics, to_ascii, AsciiStyle};
+use vietime_core::VietnameseChar;
+
+/// Test every syllable strips to plain ASCII letters
+#[test]
+fn test_every_syllable_strips() {
+    for syllable in Syllable::all() {
+        let word = syllable.to_string();
+        let ascii = to_ascii(&word, AsciiStyle::Strip);
+        assert!(
+            ascii.chars().all(|c| c.is_ascii_lowercase()),
+            "{} → {}",
+            word,
+            ascii
+        );
+        assert_eq!(ascii.len(), word.chars().count(), "{}", word);
+        assert_eq!(strip_diacritics(&word).replace('đ', "d"), ascii);
+    }
+}
+
+/// Test the Telex and VIQR spellings give the word back
+#[test]
+fn test_transliteration_round_trip() {
+    for syllable in Syllable::all() {
+        let word = syllable.to_string();
+        let telex = to_ascii(&word, AsciiStyle::Telex);
+        assert_eq!(
+            type_keys(&mut TelexEngine::new(), &telex),
+            word,
+            "keys {}",
+            telex
+        );
+
+        let viqr = to_ascii(&word, AsciiStyle::Viqr);
+        let back = convert_bytes(viqr.as_bytes(), Encoding::Viqr, Encoding::Unicode).bytes;
+        assert_eq!(String::from_utf8(back).unwrap(), word, "{}", viqr);
+    }
+}
+
+/// Test names and titles fold the same way precomposed or decomposed
+#[test]
+fn test_names_and_titles() {
+    let cases = [
+        ("Nguyễn Văn Đức", "nguyen-van-duc"),
+        ("Trần Thị Thuỷ Tiên", "tran-thi-thuy-tien"),
+        ("Phở bò — Hà Nội (2024)", "pho-bo-ha-noi-2024"),
+        ("ĐẠI HỌC QUỐC GIA", "dai-hoc-quoc-gia"),
+    ];
+    for (text, slug) in cases {
+        assert_eq!(slugify(text, '-'), slug);
+        assert_eq!(slugify(&VietnameseChar::to_nfd(text), '-'), slug);
+    }
+}
@@ -0,0 +1,71 @@
+//! # Text Transform Tests
+//!
+//! Every transform on every syllable and on a paragraph, precomposed and
+//! decomposed; both forms must give the same letters.
+
+use vietime_core::models::syllable::Syllable;
+use vietime_core::transform::Transform;
+use vietime_core::VietnameseChar;
+
+const PARAGRAPH: &str = "đường về quê ngoại xa lắm. ĐÊM QUA mưa to! \
+                         bà nguyễn thị minh khai hỏi: \"ở đâu?\" rồi thôi.";
+
+/// Test upper, lower and toggle case undo each other on every syllable
+#[test]
+fn test_every_syllable_round_trips() {
+    for syllable in Syllable::all() {
+        let word = syllable.to_string();
+        let upper = Transform::Upper.apply(&word);
+        assert!(upper.chars().all(char::is_uppercase), "{}", upper);
+        assert_eq!(Transform::Lower.apply(&upper), word);
+        assert_eq!(Transform::Toggle.apply(&word), upper);
+        assert_eq!(Transform::Toggle.apply(&upper), word);
+
+        let title = Transform::Title.apply(&upper);
+        let mut chars = word.chars();
+        let first = chars.next().map(VietnameseChar::to_upper).unwrap();
+        assert_eq!(
+            title,
+            std::iter::once(first).chain(chars).collect::<String>()
+        );
+    }
+}
+
+/// Test each transform of the paragraph
+#[test]
+fn test_paragraph() {
+    let expected = [
+        "ĐƯỜNG VỀ QUÊ NGOẠI XA LẮM. ĐÊM QUA MƯA TO! BÀ NGUYỄN THỊ MINH KHAI HỎI: \"Ở ĐÂU?\" RỒI THÔI.",
+        "đường về quê ngoại xa lắm. đêm qua mưa to! bà nguyễn thị minh khai hỏi: \"ở đâu?\" rồi thôi.",
+        "Đường Về Quê Ngoại Xa Lắm. Đêm Qua Mưa To! Bà Nguyễn Thị Minh Khai Hỏi: \"Ở Đâu?\" Rồi Thôi.",
+        "Đường về quê ngoại xa lắm. ĐÊM QUA mưa to! Bà nguyễn thị minh khai hỏi: \"ở đâu?\" rồi thôi.",
+        "ĐƯỜNG VỀ QUÊ NGOẠI XA LẮM. đêm qua MƯA TO! BÀ NGUYỄN THỊ MINH KHAI HỎI: \"Ở ĐÂU?\" RỒI THÔI.",
+        "duong ve que ngoai xa lam. DEM QUA mua to! ba nguyen thi minh khai hoi: \"o dau?\" roi thoi.",
+    ];
+    for (transform, expected) in Transform::ALL.into_iter().zip(expected) {
+        assert_eq!(transform.apply(PARAGRAPH), expected, "{}", transform.name());
+    }
+}
+
+/// Test decomposed text transforms to the same letters
+#[test]
+fn test_decomposed_paragraph() {
+    let nfd = VietnameseChar::to_nfd(PARAGRAPH);
+    for transform in Transform::ALL {
+        let result = transform.apply(&nfd);
+        assert_eq!(
+            VietnameseChar::to_nfc(&result),
+            transform.apply(PARAGRAPH),
+            "{}",
+            transform.name()
+        );
+        if transform != Transform::StripDiacritics {
+            assert_eq!(
+                result,
+                VietnameseChar::to_nfd(&result),
+                "{} stays decomposed",
+                transform.name()
+            );
+        }
+    }
+}
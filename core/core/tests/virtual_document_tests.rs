@@ -0,0 +1,116 @@
+//! # Virtual Document Integration Tests
+//!
+//! Tests asserting on the visible text after engine results are applied to
+//! a simulated text field.
+
+use std::sync::Arc;
+
+use vietime_core::clock::ManualClock;
+use vietime_core::engines::actions::{EditAction, HostCapabilities};
+use vietime_core::engines::multitap::MultiTapEngine;
+use vietime_core::engines::strategy::OutputStrategy;
+use vietime_core::engines::telex::TelexEngine;
+use vietime_core::engines::InputEngine;
+use vietime_core::testing::{type_keys, VirtualDocument, BACKSPACE};
+
+/// Test typing a sentence with Telex
+#[test]
+fn test_type_keys_telex_sentence() {
+    let mut engine = TelexEngine::new();
+    assert_eq!(type_keys(&mut engine, "tieengs vieetj "), "tiếng việt ");
+
+    let mut engine = TelexEngine::new();
+    assert_eq!(
+        type_keys(&mut engine, "xin chaof cacs banj."),
+        "xin chào các bạn."
+    );
+}
+
+/// Test the same keys give the same text on every host
+#[test]
+fn test_same_text_on_every_host() {
+    for caps in [
+        HostCapabilities::KEYBOARD_HOOK,
+        HostCapabilities::INPUT_METHOD,
+    ] {
+        for strategy in [OutputStrategy::Direct, OutputStrategy::ZERO_WIDTH] {
+            let mut engine = TelexEngine::new();
+            let mut document = VirtualDocument::new()
+                .with_capabilities(caps)
+                .with_strategy(strategy);
+            assert_eq!(
+                document.type_keys(&mut engine, "tooi ddi hocj"),
+                "tôi đi học"
+            );
+        }
+    }
+}
+
+/// Test Backspace keystrokes reach the document
+#[test]
+fn test_backspace_keystrokes() {
+    let mut engine = TelexEngine::new();
+    let keys = format!("thees{}aa", BACKSPACE);
+
+    // "thế" loses its last letter, then "aa" continues the word
+    assert_eq!(type_keys(&mut engine, &keys), "thâ");
+    assert_eq!(engine.get_buffer(), "thâ");
+}
+
+/// Test typing after existing text
+#[test]
+fn test_with_text() {
+    let mut engine = TelexEngine::new();
+    let mut document = VirtualDocument::with_text("xin ");
+
+    assert_eq!(document.type_keys(&mut engine, "chaof"), "xin chào");
+    assert_eq!(document.caret(), 8);
+}
+
+/// Test moving the caret ends the word being composed
+#[test]
+fn test_move_cursor_resets_engine() {
+    let mut engine = TelexEngine::new();
+    let mut document = VirtualDocument::new();
+    document.type_keys(&mut engine, "tooi");
+
+    document.move_cursor(&mut engine, -1);
+    assert_eq!(engine.get_buffer(), "");
+    assert_eq!(document.caret(), 2);
+
+    // The word before the caret is not rewritten
+    assert_eq!(document.type_keys(&mut engine, "x"), "tôxi");
+    assert_eq!(document.caret(), 3);
+}
+
+/// Test typing over a selection
+#[test]
+fn test_select_left_replaced_by_typing() {
+    let mut engine = TelexEngine::new();
+    let mut document = VirtualDocument::new();
+    document.type_keys(&mut engine, "tooi ");
+
+    document.select_left(&mut engine, 4);
+    assert_eq!(document.selection(), Some((0, 4)));
+
+    assert_eq!(document.type_keys(&mut engine, "banj"), "bạn");
+    assert_eq!(document.selection(), None);
+}
+
+/// Test applying actions directly
+#[test]
+fn test_apply_action() {
+    let mut document = VirtualDocument::with_text("viêt");
+    document.apply_action(&EditAction::SelectLeft(2));
+    document.apply_action(&EditAction::Insert("ệt".to_string()));
+
+    assert_eq!(document.text(), "việt");
+}
+
+/// Test the document drives any engine through the trait
+#[test]
+fn test_type_keys_multitap() {
+    let mut engine: Box<dyn InputEngine> =
+        Box::new(MultiTapEngine::with_clock(Arc::new(ManualClock::new())));
+    assert_eq!(type_keys(engine.as_mut(), "8226610"), "tằn ");
+}